@@ -0,0 +1,52 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This benchmark compares the bulk byte generation of `regd_testing::rand::generate_bytes`
+//! against the naive per-byte generation it replaced, both drawing from the generator of
+//! `regd_testing::rand::rng`.
+//!
+//! Run it with `cargo bench --bench generate_bytes`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+const LENGTH: usize = 16 * 1024 * 1024;
+const ITERATIONS: u32 = 8;
+
+/// Returns the average duration of `f` over the iterations.
+fn measure(f: impl Fn() -> Vec<u8>) -> Duration {
+    black_box(f());
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let naive = measure(|| {
+        let mut rng = regd_testing::rand::rng();
+        (0..LENGTH).map(|_| rng.random::<u8>()).collect()
+    });
+    let bulk = measure(|| regd_testing::rand::generate_bytes(LENGTH));
+    println!("generate_bytes({LENGTH}):");
+    println!("  per-byte:   {naive:?}");
+    println!("  fill_bytes: {bulk:?}");
+    println!(
+        "  speedup:    {:.1}x",
+        naive.as_secs_f64() / bulk.as_secs_f64()
+    );
+}
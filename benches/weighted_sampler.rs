@@ -0,0 +1,50 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This benchmark compares the draws of `regd_testing::rand::WeightedSampler` against the naive
+//! approach building a distribution on every draw with `regd_testing::rand::choose_weighted`.
+//!
+//! Run it with `cargo bench --bench weighted_sampler`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use regd_testing::rand::WeightedSampler;
+
+const WEIGHTS: usize = 1000;
+const DRAWS: u32 = 1_000_000;
+
+/// Returns the average duration of a draw of `f`.
+fn measure(mut f: impl FnMut() -> usize) -> Duration {
+    black_box(f());
+    let start = Instant::now();
+    for _ in 0..DRAWS {
+        black_box(f());
+    }
+    start.elapsed() / DRAWS
+}
+
+fn main() {
+    let items: Vec<(usize, f64)> = (0..WEIGHTS).map(|i| (i, (i + 1) as f64)).collect();
+    let naive = measure(|| *regd_testing::rand::choose_weighted(&items));
+    let sampler = WeightedSampler::new(items.iter().map(|(_, weight)| *weight));
+    let alias = measure(|| sampler.sample());
+    println!("draw among {WEIGHTS} weights:");
+    println!("  choose_weighted: {naive:?}");
+    println!("  alias method:    {alias:?}");
+    println!(
+        "  speedup:         {:.1}x",
+        naive.as_secs_f64() / alias.as_secs_f64()
+    );
+}
@@ -0,0 +1,182 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains the expansion of `#[derive(Generate)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Expr, ExprLit, Fields, GenericParam, Lit, LitInt, Path,
+    parse_quote,
+};
+
+/// The way a single field is generated.
+enum Strategy {
+    /// Calls `Generate::generate`.
+    Default,
+    /// Calls `generate_range` with the given range expression.
+    Range(Expr),
+    /// Calls `GenerateLen::generate_len` with the given length expression.
+    Len(Expr),
+    /// Calls the given function.
+    With(Path),
+}
+
+/// Parses the value of an attribute argument, unwrapping it if it is a string literal.
+fn parse_expr(expr: Expr) -> syn::Result<Expr> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => lit.parse(),
+        expr => Ok(expr),
+    }
+}
+
+/// Parses the `#[generate(...)]` attributes of a field.
+fn strategy(attrs: &[Attribute]) -> syn::Result<Strategy> {
+    let mut strategy = Strategy::Default;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("generate")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("range") {
+                strategy = Strategy::Range(parse_expr(meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("len") {
+                strategy = Strategy::Len(parse_expr(meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("with") {
+                let expr = parse_expr(meta.value()?.parse()?)?;
+                let path = syn::parse2(quote!(#expr)).map_err(|_| {
+                    syn::Error::new_spanned(&expr, "expected a path to a generator")
+                })?;
+                strategy = Strategy::With(path);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported generate attribute, expected `range`, `len` or `with`"))
+            }
+        })?;
+    }
+    Ok(strategy)
+}
+
+/// Parses the `#[generate(...)]` attributes of an enum variant, returning its weight.
+fn weight(attrs: &[Attribute]) -> syn::Result<u64> {
+    let mut weight = 1;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("generate")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("weight") {
+                weight = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                weight = 0;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported generate attribute, expected `weight` or `skip`"))
+            }
+        })?;
+    }
+    Ok(weight)
+}
+
+/// Expands the expression generating each field, followed by the construction of `fields`.
+fn construct(path: TokenStream, fields: &Fields) -> syn::Result<TokenStream> {
+    let values = fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            Ok(match strategy(&field.attrs)? {
+                Strategy::Default => quote!(<#ty as ::regd_testing::rand::Generate>::generate()),
+                Strategy::Range(range) => quote!(::regd_testing::rand::generate_range(#range)),
+                Strategy::Len(len) => {
+                    quote!(<#ty as ::regd_testing::rand::GenerateLen>::generate_len(#len))
+                }
+                Strategy::With(function) => quote!(#function()),
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names: #values),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#values),*)),
+        Fields::Unit => quote!(#path),
+    })
+}
+
+/// Expands `#[derive(Generate)]` for the given input.
+pub(crate) fn expand(mut input: DeriveInput) -> syn::Result<TokenStream> {
+    for param in input.generics.params.iter_mut() {
+        if let GenericParam::Type(param) = param {
+            param
+                .bounds
+                .push(parse_quote!(::regd_testing::rand::Generate));
+        }
+    }
+    let name = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => construct(quote!(Self), &data.fields)?,
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "cannot derive Generate for an enum without variants",
+                ));
+            }
+            let mut total: u64 = 0;
+            let mut arms = Vec::new();
+            let mut last = None;
+            for variant in &data.variants {
+                let weight = weight(&variant.attrs)?;
+                if weight == 0 {
+                    continue;
+                }
+                total = total.checked_add(weight).ok_or_else(|| {
+                    syn::Error::new_spanned(&variant.ident, "the total weight overflows u64")
+                })?;
+                let ident = &variant.ident;
+                let value = construct(quote!(Self::#ident), &variant.fields)?;
+                if let Some((bound, value)) = last.replace((total, value)) {
+                    arms.push(quote!(x if x < #bound => #value));
+                }
+            }
+            // The last variant takes the remaining draws, so that no arm is unreachable.
+            let Some((_, last)) = last else {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "cannot derive Generate for an enum whose variants are all skipped",
+                ));
+            };
+            quote! {
+                match ::regd_testing::rand::generate_range(0..#total) {
+                    #(#arms,)*
+                    _ => #last,
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "cannot derive Generate for a union",
+            ));
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::regd_testing::rand::Generate for #name #ty_generics #where_clause {
+            fn generate() -> Self {
+                #body
+            }
+        }
+    })
+}
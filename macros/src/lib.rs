@@ -0,0 +1,45 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This crate contains the procedural macros of regd testing.
+//! The macros are re-exported from `regd_testing` and should not be used directly.
+
+mod generate;
+mod test;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, ItemFn, parse_macro_input};
+
+/// Derives `regd_testing::rand::Generate` for structs and enums.
+///
+/// See the documentation of `regd_testing::rand::Generate` for the supported attributes.
+#[proc_macro_derive(Generate, attributes(generate))]
+pub fn derive_generate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    generate::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Expands a test function into one `#[test]` per case.
+///
+/// See the documentation of `regd_testing::regd_test` for the supported case sources.
+#[proc_macro_attribute]
+pub fn regd_test(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as test::Args);
+    let function = parse_macro_input!(input as ItemFn);
+    test::expand(args, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,306 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains the expansion of `#[regd_test(...)]`.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, FnArg, Ident, ItemFn, LitInt, LitStr, Token, parenthesized};
+
+/// A source of cases listed in `cases(...)`.
+enum Source {
+    /// A value, or a tuple of values, optionally named.
+    Value(Option<Ident>, Expr),
+    /// The fixture files matching a glob pattern relative to the manifest directory.
+    Glob(LitStr),
+}
+
+impl Parse for Source {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+            let name: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            if name == "glob" {
+                return Ok(Self::Glob(input.parse()?));
+            }
+            return Ok(Self::Value(Some(name), input.parse()?));
+        }
+        Ok(Self::Value(None, input.parse()?))
+    }
+}
+
+/// The arguments of the attribute, i.e., `cases(...)` and `repeat = N`, both optional.
+pub(crate) struct Args {
+    sources: Option<Punctuated<Source, Token![,]>>,
+    repeat: Option<LitInt>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut args = Self {
+            sources: None,
+            repeat: None,
+        };
+        if input.is_empty() {
+            return Ok(args);
+        }
+        loop {
+            let keyword: Ident = input.parse()?;
+            if keyword == "cases" && args.sources.is_none() {
+                let content;
+                parenthesized!(content in input);
+                args.sources = Some(content.parse_terminated(Source::parse, Token![,])?);
+            } else if keyword == "repeat" && args.repeat.is_none() {
+                input.parse::<Token![=]>()?;
+                let times: LitInt = input.parse()?;
+                times.base10_parse::<usize>()?;
+                args.repeat = Some(times);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    keyword,
+                    "expected `cases(...)` or `repeat = N`",
+                ));
+            }
+            if input.is_empty() {
+                return Ok(args);
+            }
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                return Ok(args);
+            }
+        }
+    }
+}
+
+/// Returns `true` if `name` matches the glob `pattern` of a single path segment.
+fn matches(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            matches(rest, name) || (!name.is_empty() && matches(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name))) => matches(rest, name),
+        (Some((p, rest)), Some((n, name))) => p == n && matches(rest, name),
+        _ => false,
+    }
+}
+
+/// Collects the paths under `dir` matching the remaining glob `segments`.
+fn walk(dir: &Path, segments: &[&str], paths: &mut Vec<PathBuf>) {
+    let Some((segment, rest)) = segments.split_first() else {
+        paths.push(dir.to_path_buf());
+        return;
+    };
+    if *segment == "**" {
+        walk(dir, rest, paths);
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if *segment == "**" {
+            if path.is_dir() {
+                walk(&path, segments, paths);
+            }
+        } else if matches(
+            segment.as_bytes(),
+            entry.file_name().to_string_lossy().as_bytes(),
+        ) {
+            walk(&path, rest, paths);
+        }
+    }
+}
+
+/// Converts a relative path into the name of a test function.
+fn case_name(path: &str) -> String {
+    let stem = path.rsplit_once('.').map_or(path, |(stem, _)| stem);
+    let name: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("case_{name}")
+    }
+}
+
+/// Expands `#[regd_test(...)]` for the given test function.
+pub(crate) fn expand(args: Args, function: ItemFn) -> syn::Result<TokenStream> {
+    if let Some(asyncness) = &function.sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "cannot expand async test functions",
+        ));
+    }
+    let arity = function.sig.inputs.len();
+    if let Some(receiver @ FnArg::Receiver(_)) = function.sig.inputs.first() {
+        return Err(syn::Error::new_spanned(
+            receiver,
+            "cannot expand methods into test cases",
+        ));
+    }
+    let Some(sources) = args.sources else {
+        return expand_repeat(args.repeat, function);
+    };
+    let mut cases: Vec<(Ident, Vec<TokenStream>)> = Vec::new();
+    for (i, source) in sources.into_iter().enumerate() {
+        match source {
+            Source::Value(name, expr) => {
+                let name = name.unwrap_or_else(|| format_ident!("case_{i}"));
+                let values = match expr {
+                    Expr::Tuple(tuple) if arity != 1 => {
+                        if tuple.elems.len() != arity {
+                            return Err(syn::Error::new_spanned(
+                                tuple,
+                                format!("expected a tuple of {arity} values"),
+                            ));
+                        }
+                        tuple.elems.iter().map(|elem| quote!(#elem)).collect()
+                    }
+                    expr if arity == 1 => vec![quote!(#expr)],
+                    expr => {
+                        return Err(syn::Error::new_spanned(
+                            expr,
+                            format!("expected a tuple of {arity} values"),
+                        ));
+                    }
+                };
+                cases.push((name, values));
+            }
+            Source::Glob(pattern) => {
+                if arity != 1 {
+                    return Err(syn::Error::new_spanned(
+                        pattern,
+                        "expected a test function taking a single path",
+                    ));
+                }
+                let root = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+                let value = pattern.value();
+                let segments: Vec<&str> = value.split('/').filter(|s| !s.is_empty()).collect();
+                let mut paths = Vec::new();
+                walk(&root, &segments, &mut paths);
+                paths.retain(|path| path.is_file());
+                paths.sort();
+                if paths.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        pattern,
+                        format!("no file matches {value:?}"),
+                    ));
+                }
+                for path in paths {
+                    let relative = path.strip_prefix(&root).unwrap_or(&path);
+                    let name = case_name(&relative.to_string_lossy());
+                    let path = path.to_string_lossy().into_owned();
+                    cases.push((
+                        Ident::new(&name, Span::call_site()),
+                        vec![quote!(::core::convert::From::from(::std::path::Path::new(#path)))],
+                    ));
+                }
+            }
+        }
+    }
+    let mut seen = HashSet::new();
+    for (name, _) in &cases {
+        if !seen.insert(name.to_string()) {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!("duplicate case name `{name}`"),
+            ));
+        }
+    }
+    let ident = &function.sig.ident;
+    let vis = &function.vis;
+    let output = &function.sig.output;
+    let attrs = &function.attrs;
+    // The cases call the function through `super::`, as a case may be named like it.
+    let tests = cases.iter().map(|(name, values)| match &args.repeat {
+        Some(times) => quote! {
+            #[test]
+            #(#attrs)*
+            fn #name() {
+                ::regd_testing::rand::install_failure_hook();
+                ::regd_testing::rand::repeat(#times, || #output { super::#ident(#(#values),*) })
+            }
+        },
+        None => quote! {
+            #[test]
+            #(#attrs)*
+            fn #name() #output {
+                ::regd_testing::rand::install_failure_hook();
+                super::#ident(#(#values),*)
+            }
+        },
+    });
+    Ok(quote! {
+        #[allow(dead_code)]
+        #function
+
+        #vis mod #ident {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #(#tests)*
+        }
+    })
+}
+
+/// Expands `#[regd_test]` or `#[regd_test(repeat = N)]` for the given test function without
+/// cases.
+fn expand_repeat(times: Option<LitInt>, mut function: ItemFn) -> syn::Result<TokenStream> {
+    if let Some(input) = function.sig.inputs.first() {
+        return Err(syn::Error::new_spanned(
+            input,
+            "cannot expand test functions taking arguments without `cases(...)`",
+        ));
+    }
+    let attrs = std::mem::take(&mut function.attrs);
+    let ident = &function.sig.ident;
+    let vis = &function.vis;
+    let mut inner = function.clone();
+    inner.vis = syn::Visibility::Inherited;
+    let Some(times) = times else {
+        let output = &function.sig.output;
+        return Ok(quote! {
+            #[test]
+            #(#attrs)*
+            #vis fn #ident() #output {
+                #inner
+                ::regd_testing::rand::install_failure_hook();
+                #ident()
+            }
+        });
+    };
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #ident() {
+            #inner
+            ::regd_testing::rand::install_failure_hook();
+            ::regd_testing::rand::repeat(#times, #ident)
+        }
+    })
+}
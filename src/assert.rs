@@ -0,0 +1,1007 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of assertions.
+//!
+//! Every assertion panics with a descriptive message on failure, and reports the location of its
+//! caller rather than its own.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt::{Debug, Display, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::env::ReentrantLock;
+use crate::poll;
+
+/// Asserts that a condition eventually holds, checking it at a fixed interval.
+///
+/// This is useful for tests of asynchronous behavior, which would otherwise retry in ad-hoc
+/// loops.
+///
+/// # Parameters
+/// - `timeout`: The maximum time to wait.
+/// - `interval`: The time between two checks.
+/// - `condition`: The condition to check.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::time::Duration;
+///
+/// use regd_testing::assert;
+///
+/// let done = Arc::new(AtomicBool::new(false));
+/// {
+///     let done = Arc::clone(&done);
+///     std::thread::spawn(move || done.store(true, Ordering::SeqCst));
+/// }
+/// assert::eventually(Duration::from_secs(5), Duration::from_millis(1), || {
+///     done.load(Ordering::SeqCst)
+/// });
+/// ```
+///
+/// # Panics
+/// - This function will panic with the elapsed time and the number of attempts if the condition
+///   does not hold before `timeout` elapses.
+#[track_caller]
+pub fn eventually(timeout: Duration, interval: Duration, condition: impl FnMut() -> bool) {
+    if let Err((elapsed, attempts)) = poll::poll_every(timeout, interval, condition) {
+        panic!("condition did not hold after {elapsed:?} ({attempts} attempts)");
+    }
+}
+
+/// Asserts that a value eventually equals the expected one, polling with exponential backoff.
+///
+/// # Parameters
+/// - `timeout`: The maximum time to wait.
+/// - `actual`: The function observing the current value.
+/// - `expected`: The expected value.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::time::Duration;
+///
+/// use regd_testing::assert;
+///
+/// let count = Arc::new(AtomicUsize::new(0));
+/// {
+///     let count = Arc::clone(&count);
+///     std::thread::spawn(move || {
+///         for _ in 0..3 {
+///             count.fetch_add(1, Ordering::SeqCst);
+///         }
+///     });
+/// }
+/// assert::eventually_eq(Duration::from_secs(5), || count.load(Ordering::SeqCst), 3);
+/// ```
+///
+/// # Panics
+/// - This function will panic with the last observed value if the value does not equal
+///   `expected` before `timeout` elapses.
+#[track_caller]
+pub fn eventually_eq<T>(timeout: Duration, mut actual: impl FnMut() -> T, expected: T)
+where
+    T: PartialEq + Debug,
+{
+    let mut last = None;
+    let result = poll::poll(timeout, || {
+        let value = actual();
+        let done = value == expected;
+        last = Some(value);
+        done
+    });
+    if let Err((elapsed, attempts)) = result {
+        panic!(
+            "value did not equal {expected:?} after {elapsed:?} ({attempts} attempts); \
+             last observed: {:?}",
+            last.unwrap_or_else(|| unreachable!("the value is observed at least once"))
+        );
+    }
+}
+
+/// The tolerance of [`approx_eq`], combining an absolute and a relative bound.
+///
+/// Two values are approximately equal if their difference is at most the absolute bound, or at
+/// most the relative bound times the larger magnitude. The absolute bound handles values near
+/// zero, where any relative bound becomes vanishingly small.
+///
+/// # Examples
+/// ```
+/// use regd_testing::assert::{self, AbsRel};
+///
+/// let tolerance = AbsRel {
+///     abs: 1e-9,
+///     rel: 1e-6,
+/// };
+/// assert::approx_eq(1_000_000.0, 1_000_000.5, tolerance);
+/// assert::approx_eq(0.0, 1e-10, tolerance);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AbsRel {
+    /// The absolute bound of the difference. Defaults to `1e-12`.
+    pub abs: f64,
+    /// The bound of the difference relative to the larger magnitude. Defaults to `1e-9`.
+    pub rel: f64,
+}
+
+impl Default for AbsRel {
+    fn default() -> Self {
+        Self {
+            abs: 1e-12,
+            rel: 1e-9,
+        }
+    }
+}
+
+impl AbsRel {
+    /// Returns the allowed difference between `a` and `b`.
+    fn tolerance(&self, a: f64, b: f64) -> f64 {
+        self.abs.max(self.rel * a.abs().max(b.abs()))
+    }
+
+    /// Returns `true` if `a` and `b` are approximately equal.
+    ///
+    /// Infinities are only equal to themselves, and NaN is equal to nothing.
+    pub fn is_close(&self, a: f64, b: f64) -> bool {
+        if !a.is_finite() || !b.is_finite() {
+            return a == b;
+        }
+        a == b || (a - b).abs() <= self.tolerance(a, b)
+    }
+}
+
+/// Asserts that two floating-point numbers are approximately equal.
+///
+/// # Parameters
+/// - `actual`: The actual value.
+/// - `expected`: The expected value.
+/// - `tolerance`: The absolute and relative bounds of the difference.
+///
+/// # Examples
+/// ```
+/// use regd_testing::assert::{self, AbsRel};
+///
+/// assert::approx_eq(0.1 + 0.2, 0.3, AbsRel::default());
+/// assert::approx_eq(1.0f32 / 3.0, 0.333_333_34f32, AbsRel::default());
+/// ```
+///
+/// ```should_panic
+/// use regd_testing::assert::{self, AbsRel};
+///
+/// assert::approx_eq(f64::INFINITY, 1.0, AbsRel::default());
+/// ```
+///
+/// ```should_panic
+/// use regd_testing::assert::{self, AbsRel};
+///
+/// assert::approx_eq(f64::INFINITY, f64::NEG_INFINITY, AbsRel::default());
+/// ```
+///
+/// # Panics
+/// - This function will panic with the difference and the tolerance if the values are not
+///   approximately equal, or if either value is NaN.
+#[track_caller]
+pub fn approx_eq(actual: impl Into<f64>, expected: impl Into<f64>, tolerance: AbsRel) {
+    let (a, b) = (actual.into(), expected.into());
+    if !tolerance.is_close(a, b) {
+        panic!(
+            "{a:?} is not approximately equal to {b:?}: delta {:?} exceeds tolerance {:?} \
+             (abs {:?}, rel {:?})",
+            (a - b).abs(),
+            tolerance.tolerance(a, b),
+            tolerance.abs,
+            tolerance.rel
+        );
+    }
+}
+
+/// Asserts that two slices of floating-point numbers are element-wise approximately equal.
+///
+/// # Parameters
+/// - `actual`: The actual values.
+/// - `expected`: The expected values.
+/// - `tolerance`: The absolute and relative bounds of the difference of each element.
+///
+/// # Examples
+/// ```
+/// use regd_testing::assert::{self, AbsRel};
+///
+/// let xs: Vec<f64> = (1..=3).map(|i| i as f64 / 10.0 * 3.0).collect();
+/// assert::slice_approx_eq(&xs, &[0.3, 0.6, 0.9], AbsRel::default());
+/// ```
+///
+/// # Panics
+/// - This function will panic if the lengths differ, or with the index, the difference and the
+///   tolerance of the first element which is not approximately equal.
+#[track_caller]
+pub fn slice_approx_eq<T>(actual: &[T], expected: &[T], tolerance: AbsRel)
+where
+    T: Copy + Into<f64>,
+{
+    if actual.len() != expected.len() {
+        panic!(
+            "slices differ in length: actual has {} elements, expected has {}",
+            actual.len(),
+            expected.len()
+        );
+    }
+    for (i, (&a, &b)) in actual.iter().zip(expected).enumerate() {
+        let (a, b) = (a.into(), b.into());
+        if !tolerance.is_close(a, b) {
+            panic!(
+                "slices differ at index {i}: {a:?} is not approximately equal to {b:?}: delta \
+                 {:?} exceeds tolerance {:?} (abs {:?}, rel {:?})",
+                (a - b).abs(),
+                tolerance.tolerance(a, b),
+                tolerance.abs,
+                tolerance.rel
+            );
+        }
+    }
+}
+
+/// A floating-point type whose values can be compared in units in the last place (ULPs).
+pub trait Ulps: Copy + Display {
+    /// Returns the number of representable values between `self` and `other`, or `None` if
+    /// either is NaN.
+    ///
+    /// Positive and negative zero are zero ULPs apart.
+    fn ulps(self, other: Self) -> Option<u64>;
+}
+
+impl Ulps for f64 {
+    fn ulps(self, other: Self) -> Option<u64> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        // Maps the sign-magnitude representation onto a monotonic two's complement one.
+        let key = |x: f64| {
+            let bits = x.to_bits() as i64;
+            if bits < 0 { i64::MIN - bits } else { bits }
+        };
+        Some(key(self).abs_diff(key(other)))
+    }
+}
+
+impl Ulps for f32 {
+    fn ulps(self, other: Self) -> Option<u64> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        // Maps the sign-magnitude representation onto a monotonic two's complement one.
+        let key = |x: f32| {
+            let bits = x.to_bits() as i32;
+            if bits < 0 { i32::MIN - bits } else { bits }
+        };
+        Some(u64::from(key(self).abs_diff(key(other))))
+    }
+}
+
+/// Asserts that two floating-point numbers are at most the given number of ULPs apart.
+///
+/// Comparing in ULPs scales with the magnitude of the values, which suits results of a few
+/// floating-point operations, but not values near zero, e.g., results of cancellation.
+///
+/// # Parameters
+/// - `actual`: The actual value.
+/// - `expected`: The expected value.
+/// - `max_ulps`: The maximum number of representable values between them.
+///
+/// # Examples
+/// ```
+/// use regd_testing::assert;
+///
+/// assert::ulps_eq(0.1 + 0.2, 0.3, 1);
+/// assert::ulps_eq(-0.0f32, 0.0f32, 0);
+/// ```
+///
+/// # Panics
+/// - This function will panic with the distance in ULPs if it exceeds `max_ulps`, or if either
+///   value is NaN.
+#[track_caller]
+pub fn ulps_eq<T: Ulps>(actual: T, expected: T, max_ulps: u64) {
+    match actual.ulps(expected) {
+        Some(ulps) if ulps <= max_ulps => {}
+        Some(ulps) => panic!(
+            "{actual} is not approximately equal to {expected}: {ulps} ULPs apart exceeds \
+             tolerance {max_ulps} ULPs"
+        ),
+        None => panic!("{actual} is not approximately equal to {expected}: NaN is never equal"),
+    }
+}
+
+/// The number of bytes per row of a hex dump.
+const HEX_ROW_LEN: usize = 16;
+
+/// The number of rows of a hex dump shown before and after the row of the first difference.
+const HEX_CONTEXT_ROWS: usize = 2;
+
+/// Renders a row of a hex dump of `bytes` starting at `offset`, padding bytes past the end.
+fn hex_row(label: &str, bytes: &[u8], offset: usize) -> String {
+    let mut row = format!("{label:<8}  {offset:08x} ");
+    let mut ascii = String::with_capacity(HEX_ROW_LEN);
+    for i in offset..offset + HEX_ROW_LEN {
+        if i % 8 == 0 {
+            row.push(' ');
+        }
+        match bytes.get(i) {
+            Some(&byte) => {
+                let _ = write!(row, "{byte:02x} ");
+                ascii.push(if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                });
+            }
+            None => row.push_str("   "),
+        }
+    }
+    let _ = write!(row, " |{ascii}|");
+    row
+}
+
+/// Renders the marker row underlining the bytes of the row at `offset` which differ.
+fn marker_row(actual: &[u8], expected: &[u8], offset: usize) -> String {
+    let mut row = " ".repeat(8 + 2 + 8 + 1);
+    for i in offset..offset + HEX_ROW_LEN {
+        if i % 8 == 0 {
+            row.push(' ');
+        }
+        let differs = actual.get(i) != expected.get(i);
+        row.push_str(if differs { "^^ " } else { "   " });
+    }
+    row.trim_end().to_string()
+}
+
+/// Asserts that two byte slices are equal.
+///
+/// On mismatch, this assertion prints an aligned hex and ASCII dump of both slices around the
+/// first differing offset, instead of their whole `Debug` representations.
+///
+/// # Parameters
+/// - `actual`: The actual bytes.
+/// - `expected`: The expected bytes.
+///
+/// # Examples
+/// ```
+/// use regd_testing::assert;
+///
+/// assert::bytes_eq(b"\x01\x02hello", b"\x01\x02hello");
+/// ```
+///
+/// ```should_panic
+/// use regd_testing::assert;
+///
+/// // Panics with:
+/// // byte slices differ at offset 0x7 (actual is 11 bytes, expected is 11 bytes)
+/// //           offset    00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f
+/// // expected  00000000  68 65 6c 6c 6f 2c 20 77  6f 72 6c                 |hello, worl|
+/// // actual    00000000  68 65 6c 6c 6f 2c 20 57  6f 72 6c                 |hello, Worl|
+/// //                                          ^^
+/// assert::bytes_eq(b"hello, Worl", b"hello, worl");
+/// ```
+///
+/// # Panics
+/// - This function will panic with a hex dump around the first difference if the slices differ.
+#[track_caller]
+pub fn bytes_eq(actual: impl AsRef<[u8]>, expected: impl AsRef<[u8]>) {
+    let (actual, expected) = (actual.as_ref(), expected.as_ref());
+    if actual == expected {
+        return;
+    }
+    let first = actual
+        .iter()
+        .zip(expected)
+        .position(|(a, b)| a != b)
+        .unwrap_or_else(|| actual.len().min(expected.len()));
+    let row = first / HEX_ROW_LEN;
+    let last_row = actual.len().max(expected.len()).saturating_sub(1) / HEX_ROW_LEN;
+
+    let mut dump = format!(
+        "byte slices differ at offset {first:#x} (actual is {} bytes, expected is {} bytes)\n",
+        actual.len(),
+        expected.len()
+    );
+    let _ = write!(dump, "{:<8}  offset   ", "");
+    for i in 0..HEX_ROW_LEN {
+        if i % 8 == 0 {
+            dump.push(' ');
+        }
+        let _ = write!(dump, "{i:02x} ");
+    }
+    dump.truncate(dump.trim_end().len());
+    dump.push('\n');
+    for row in row.saturating_sub(HEX_CONTEXT_ROWS)..=(row + HEX_CONTEXT_ROWS).min(last_row) {
+        let offset = row * HEX_ROW_LEN;
+        let _ = writeln!(dump, "{}", hex_row("expected", expected, offset));
+        let _ = writeln!(dump, "{}", hex_row("actual", actual, offset));
+        if (offset..offset + HEX_ROW_LEN).any(|i| actual.get(i) != expected.get(i)) {
+            let _ = writeln!(dump, "{}", marker_row(actual, expected, offset));
+        }
+    }
+    panic!("{dump}");
+}
+
+/// Matches the elements of `actual` against those of `expected` with multiplicity, returning
+/// the unmatched elements of each.
+fn unmatched<'a, T: PartialEq>(actual: &'a [T], expected: &'a [T]) -> (Vec<&'a T>, Vec<&'a T>) {
+    let mut matched = vec![false; actual.len()];
+    let mut missing = Vec::new();
+    for x in expected {
+        match (0..actual.len()).find(|&i| !matched[i] && actual[i] == *x) {
+            Some(i) => matched[i] = true,
+            None => missing.push(x),
+        }
+    }
+    let unexpected = actual
+        .iter()
+        .zip(matched)
+        .filter_map(|(x, matched)| (!matched).then_some(x))
+        .collect();
+    (missing, unexpected)
+}
+
+/// Asserts that two slices contain the same elements regardless of order.
+///
+/// Elements are compared with multiplicity, i.e., `[1, 1, 2]` and `[1, 2, 2]` differ.
+///
+/// # Parameters
+/// - `actual`: The actual elements.
+/// - `expected`: The expected elements.
+///
+/// # Examples
+/// ```
+/// use regd_testing::assert;
+///
+/// assert::same_elements(&["b", "a", "c", "a"], &["a", "a", "b", "c"]);
+/// ```
+///
+/// # Panics
+/// - This function will panic with the missing and unexpected elements if the slices differ.
+#[track_caller]
+pub fn same_elements<T>(actual: &[T], expected: &[T])
+where
+    T: PartialEq + Debug,
+{
+    let (missing, unexpected) = unmatched(actual, expected);
+    if !missing.is_empty() || !unexpected.is_empty() {
+        panic!(
+            "collections do not contain the same elements\nmissing: {missing:?}\nunexpected: \
+             {unexpected:?}"
+        );
+    }
+}
+
+/// Asserts that every element of a slice is contained in another regardless of order.
+///
+/// Elements are compared with multiplicity, i.e., `[1, 1]` is not a subset of `[1, 2]`.
+///
+/// # Parameters
+/// - `actual`: The actual elements.
+/// - `superset`: The elements which must contain every actual element.
+///
+/// # Examples
+/// ```
+/// use regd_testing::assert;
+///
+/// assert::subset_of(&[3, 1], &[1, 2, 3]);
+/// ```
+///
+/// # Panics
+/// - This function will panic with the unexpected elements if `actual` is not a subset of
+///   `superset`.
+#[track_caller]
+pub fn subset_of<T>(actual: &[T], superset: &[T])
+where
+    T: PartialEq + Debug,
+{
+    let (_, unexpected) = unmatched(actual, superset);
+    if !unexpected.is_empty() {
+        panic!("collection is not a subset of the expected one\nunexpected: {unexpected:?}");
+    }
+}
+
+/// The lock serializing every replacement of the panic hook made through this module.
+static HOOK_LOCK: ReentrantLock = ReentrantLock::new();
+
+thread_local! {
+    /// Whether panics of the current thread are silenced by [`panics_with`].
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Asserts that a closure panics with a message containing the given substring.
+///
+/// Unlike `#[should_panic(expected = ...)]`, this assertion can be used in the middle of a test
+/// and combined with other assertions. The panic message of the closure is not printed, while
+/// panics of other threads are reported by the panic hook as usual, which is restored afterwards.
+///
+/// # Parameters
+/// - `substring`: The substring the panic message must contain.
+/// - `f`: The closure to run.
+///
+/// # Returns
+/// - The panic message, for further assertions.
+///
+/// # Examples
+/// ```
+/// use regd_testing::assert;
+///
+/// let message = assert::panics_with("out of bounds", || {
+///     let xs: Vec<u32> = Vec::new();
+///     xs[1]
+/// });
+/// assert!(message.contains("len is 0"));
+/// ```
+///
+/// # Panics
+/// - This function will panic if the closure does not panic, or if its panic message does not
+///   contain `substring`.
+#[track_caller]
+pub fn panics_with<T>(substring: &str, f: impl FnOnce() -> T) -> String {
+    let result = {
+        let _lock = HOOK_LOCK.lock();
+        let previous = Arc::new(panic::take_hook());
+        {
+            let previous = Arc::clone(&previous);
+            panic::set_hook(Box::new(move |info| {
+                if !SILENCED.get() {
+                    previous(info);
+                }
+            }));
+        }
+        let silenced = SILENCED.replace(true);
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        SILENCED.set(silenced);
+        drop(panic::take_hook());
+        if let Ok(previous) = Arc::try_unwrap(previous) {
+            panic::set_hook(previous);
+        }
+        result
+    };
+    let Err(payload) = result else {
+        panic!("closure did not panic; expected a panic containing {substring:?}");
+    };
+    let message = panic_message(&*payload);
+    if !message.contains(substring) {
+        panic!("closure panicked with {message:?}, which does not contain {substring:?}");
+    }
+    message
+}
+
+/// Returns the message of a panic payload.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "<non-string panic payload>".to_string())
+}
+
+/// Returns an iterator over an error and its sources, outermost first.
+fn chain<'a>(err: &'a (dyn Error + 'static)) -> impl Iterator<Item = &'a (dyn Error + 'static)> {
+    std::iter::successors(Some(err), |&err| err.source())
+}
+
+/// Renders an error and its sources, one per line.
+fn render_chain(err: &(dyn Error + 'static)) -> String {
+    chain(err)
+        .enumerate()
+        .map(|(i, err)| format!("  {i}: {err}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Asserts that an error, or any error in its chain of sources, has a message containing the
+/// given substring.
+///
+/// # Parameters
+/// - `err`: The outermost error.
+/// - `substring`: The substring one of the messages must contain.
+///
+/// # Examples
+/// ```
+/// use std::io;
+///
+/// use regd_testing::assert;
+///
+/// let cause = io::Error::new(io::ErrorKind::NotFound, "config.toml not found");
+/// let err = io::Error::other(cause);
+/// assert::error_chain_contains(&err, "config.toml");
+/// ```
+///
+/// # Panics
+/// - This function will panic with the whole chain if no message contains `substring`.
+#[track_caller]
+pub fn error_chain_contains(err: &(dyn Error + 'static), substring: &str) {
+    if !chain(err).any(|err| err.to_string().contains(substring)) {
+        panic!(
+            "no error in the chain contains {substring:?}; chain:\n{}",
+            render_chain(err)
+        );
+    }
+}
+
+/// Asserts that an error, or any error in its chain of sources, is of the given type.
+///
+/// # Parameters
+/// - `err`: The outermost error.
+///
+/// # Returns
+/// - The outermost error of type `E` in the chain, for further assertions.
+///
+/// # Examples
+/// ```
+/// use std::error::Error;
+/// use std::fmt;
+/// use std::num::ParseIntError;
+///
+/// use regd_testing::assert;
+///
+/// #[derive(Debug)]
+/// struct ConfigError(ParseIntError);
+///
+/// impl fmt::Display for ConfigError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "invalid config")
+///     }
+/// }
+///
+/// impl Error for ConfigError {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         Some(&self.0)
+///     }
+/// }
+///
+/// let err = ConfigError("x".parse::<u32>().unwrap_err());
+/// let cause: &ParseIntError = assert::error_is(&err);
+/// assert_eq!(cause.to_string(), "invalid digit found in string");
+/// ```
+///
+/// # Panics
+/// - This function will panic with the whole chain if no error is of type `E`.
+#[track_caller]
+pub fn error_is<'a, E>(err: &'a (dyn Error + 'static)) -> &'a E
+where
+    E: Error + 'static,
+{
+    chain(err)
+        .find_map(|err| err.downcast_ref::<E>())
+        .unwrap_or_else(|| {
+            panic!(
+                "no error in the chain is a `{}`; chain:\n{}",
+                std::any::type_name::<E>(),
+                render_chain(err)
+            )
+        })
+}
+
+/// Options of [`json_eq_with`] and [`json_includes_with`].
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "json")]
+/// # {
+/// use regd_testing::assert::{self, JsonOpts};
+/// use serde_json::json;
+///
+/// let opts = JsonOpts {
+///     ignore: vec![
+///         "id".to_string(),
+///         "items[*].created_at".to_string(),
+///         "tags[*]".to_string(),
+///     ],
+/// };
+/// assert::json_eq_with(
+///     &json!({"id": 42, "items": [{"name": "a", "created_at": 1}], "tags": ["x"]}),
+///     &json!({"id": 7, "items": [{"name": "a", "created_at": 2}], "tags": ["y", "z"]}),
+///     &opts,
+/// );
+/// # }
+/// ```
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonOpts {
+    /// The paths whose values are not compared, e.g., of timestamps or generated ids.
+    ///
+    /// A path is a sequence of object keys separated by `.` and array indices in brackets, e.g.,
+    /// `items[0].id`, optionally prefixed with `$.`. The wildcards `*` and `[*]` match any key and
+    /// any index respectively.
+    pub ignore: Vec<String>,
+}
+
+/// A segment of the path of a JSON value, or of a pattern of such paths.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    AnyKey,
+    AnyIndex,
+}
+
+/// Parses a path of [`JsonOpts::ignore`] into its segments.
+#[cfg(feature = "json")]
+fn parse_path(path: &str) -> Vec<Segment> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = Vec::new();
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let (key, mut rest) = part
+            .split_once('[')
+            .map_or((part, ""), |(key, rest)| (key, rest));
+        match key {
+            "" => {}
+            "*" => segments.push(Segment::AnyKey),
+            key => segments.push(Segment::Key(key.to_string())),
+        }
+        while let Some((index, tail)) = rest.split_once(']') {
+            segments.push(match index {
+                "*" => Segment::AnyIndex,
+                index => index
+                    .parse()
+                    .map(Segment::Index)
+                    .unwrap_or_else(|_| panic!("cannot parse JSON path {path:?}")),
+            });
+            rest = tail.strip_prefix('[').unwrap_or(tail);
+        }
+    }
+    segments
+}
+
+/// Returns `true` if a path matches a pattern of [`JsonOpts::ignore`].
+#[cfg(feature = "json")]
+fn path_matches(pattern: &[Segment], path: &[Segment]) -> bool {
+    pattern.len() == path.len()
+        && pattern.iter().zip(path).all(|(p, s)| match (p, s) {
+            (Segment::AnyKey, Segment::Key(_)) | (Segment::AnyIndex, Segment::Index(_)) => true,
+            (p, s) => p == s,
+        })
+}
+
+/// Renders a path of a JSON value, e.g., `$.items[0].id`.
+#[cfg(feature = "json")]
+fn render_path(path: &[Segment]) -> String {
+    let mut rendered = "$".to_string();
+    for segment in path {
+        match segment {
+            Segment::Key(key) => {
+                rendered.push('.');
+                rendered.push_str(key);
+            }
+            Segment::Index(index) => rendered.push_str(&format!("[{index}]")),
+            Segment::AnyKey => rendered.push_str(".*"),
+            Segment::AnyIndex => rendered.push_str("[*]"),
+        }
+    }
+    rendered
+}
+
+/// Collects the differences between two JSON values.
+///
+/// If `partial` is `true`, keys of `expected` objects missing from `actual` are differences,
+/// while keys of `actual` objects missing from `expected` are not.
+#[cfg(feature = "json")]
+fn json_diff(
+    actual: &serde_json::Value,
+    expected: &serde_json::Value,
+    partial: bool,
+    ignore: &[Vec<Segment>],
+    path: &mut Vec<Segment>,
+    diffs: &mut Vec<String>,
+) {
+    use serde_json::Value;
+
+    if ignore.iter().any(|pattern| path_matches(pattern, path)) {
+        return;
+    }
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => {
+            for (key, expected) in expected {
+                path.push(Segment::Key(key.clone()));
+                match actual.get(key) {
+                    Some(actual) => json_diff(actual, expected, partial, ignore, path, diffs),
+                    None if ignore.iter().any(|pattern| path_matches(pattern, path)) => {}
+                    None => diffs.push(format!(
+                        "{}: missing, expected {expected}",
+                        render_path(path)
+                    )),
+                }
+                path.pop();
+            }
+            if !partial {
+                for (key, actual) in actual
+                    .iter()
+                    .filter(|(key, _)| !expected.contains_key(*key))
+                {
+                    path.push(Segment::Key(key.clone()));
+                    if !ignore.iter().any(|pattern| path_matches(pattern, path)) {
+                        diffs.push(format!("{}: unexpected {actual}", render_path(path)));
+                    }
+                    path.pop();
+                }
+            }
+        }
+        (Value::Array(actual), Value::Array(expected)) => {
+            for i in 0..actual.len().max(expected.len()) {
+                path.push(Segment::Index(i));
+                match (actual.get(i), expected.get(i)) {
+                    (Some(actual), Some(expected)) => {
+                        json_diff(actual, expected, partial, ignore, path, diffs);
+                    }
+                    _ if ignore.iter().any(|pattern| path_matches(pattern, path)) => {}
+                    (None, Some(expected)) => {
+                        diffs.push(format!(
+                            "{}: missing, expected {expected}",
+                            render_path(path)
+                        ));
+                    }
+                    (Some(actual), None) => {
+                        diffs.push(format!("{}: unexpected {actual}", render_path(path)));
+                    }
+                    (None, None) => {}
+                }
+                path.pop();
+            }
+        }
+        (actual, expected) if actual != expected => {
+            diffs.push(format!(
+                "{}: expected {expected}, actual {actual}",
+                render_path(path)
+            ));
+        }
+        _ => {}
+    }
+}
+
+/// Serializes both values and panics with every difference between them, if any.
+#[cfg(feature = "json")]
+#[track_caller]
+fn assert_json<A, E>(actual: &A, expected: &E, partial: bool, opts: &JsonOpts)
+where
+    A: serde::Serialize + ?Sized,
+    E: serde::Serialize + ?Sized,
+{
+    let actual = serde_json::to_value(actual)
+        .unwrap_or_else(|e| panic!("cannot serialize actual value: {e}"));
+    let expected = serde_json::to_value(expected)
+        .unwrap_or_else(|e| panic!("cannot serialize expected value: {e}"));
+    let ignore: Vec<Vec<Segment>> = opts.ignore.iter().map(|path| parse_path(path)).collect();
+    let mut diffs = Vec::new();
+    json_diff(
+        &actual,
+        &expected,
+        partial,
+        &ignore,
+        &mut Vec::new(),
+        &mut diffs,
+    );
+    if !diffs.is_empty() {
+        let relation = if partial { "include" } else { "equal" };
+        panic!(
+            "JSON values do not {relation} the expected one ({} differences):\n{}",
+            diffs.len(),
+            diffs.join("\n")
+        );
+    }
+}
+
+/// Asserts that two values are structurally equal as JSON, ignoring the order of object keys.
+///
+/// # Parameters
+/// - `actual`: The actual value, e.g., a `serde_json::Value` or any serializable type.
+/// - `expected`: The expected value.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "json")]
+/// # {
+/// use regd_testing::assert;
+/// use serde_json::json;
+///
+/// assert::json_eq(&json!({"a": 1, "b": [true, null]}), &json!({"b": [true, null], "a": 1}));
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic with the path of every difference if the values differ, or if
+///   either value cannot be serialized.
+#[cfg(feature = "json")]
+#[track_caller]
+pub fn json_eq<A, E>(actual: &A, expected: &E)
+where
+    A: serde::Serialize + ?Sized,
+    E: serde::Serialize + ?Sized,
+{
+    assert_json(actual, expected, false, &JsonOpts::default());
+}
+
+/// Asserts that two values are structurally equal as JSON with the given options.
+///
+/// # Parameters
+/// - `actual`: The actual value.
+/// - `expected`: The expected value.
+/// - `opts`: The paths to ignore.
+///
+/// # Panics
+/// - This function will panic with the path of every difference outside the ignored paths if the
+///   values differ, or if either value cannot be serialized.
+#[cfg(feature = "json")]
+#[track_caller]
+pub fn json_eq_with<A, E>(actual: &A, expected: &E, opts: &JsonOpts)
+where
+    A: serde::Serialize + ?Sized,
+    E: serde::Serialize + ?Sized,
+{
+    assert_json(actual, expected, false, opts);
+}
+
+/// Asserts that a value includes a partial one as JSON, i.e., every key of every object of
+/// `partial` is present in `actual` with an equal value.
+///
+/// Arrays are compared element-wise and must have the same length.
+///
+/// # Parameters
+/// - `actual`: The actual value.
+/// - `partial`: The expected subset of the value.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "json")]
+/// # {
+/// use regd_testing::assert;
+/// use serde_json::json;
+///
+/// let response = json!({"id": 42, "user": {"name": "Alice", "age": 30}});
+/// assert::json_includes(&response, &json!({"user": {"name": "Alice"}}));
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic with the path of every difference if `actual` does not include
+///   `partial`, or if either value cannot be serialized.
+#[cfg(feature = "json")]
+#[track_caller]
+pub fn json_includes<A, E>(actual: &A, partial: &E)
+where
+    A: serde::Serialize + ?Sized,
+    E: serde::Serialize + ?Sized,
+{
+    assert_json(actual, partial, true, &JsonOpts::default());
+}
+
+/// Asserts that a value includes a partial one as JSON with the given options.
+///
+/// # Parameters
+/// - `actual`: The actual value.
+/// - `partial`: The expected subset of the value.
+/// - `opts`: The paths to ignore.
+///
+/// # Panics
+/// - This function will panic with the path of every difference outside the ignored paths if
+///   `actual` does not include `partial`, or if either value cannot be serialized.
+#[cfg(feature = "json")]
+#[track_caller]
+pub fn json_includes_with<A, E>(actual: &A, partial: &E, opts: &JsonOpts)
+where
+    A: serde::Serialize + ?Sized,
+    E: serde::Serialize + ?Sized,
+{
+    assert_json(actual, partial, true, opts);
+}
@@ -0,0 +1,313 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of asynchronous code.
+//!
+//! The delays are drawn from the thread-local random number generator of [`crate::rand`], so
+//! that they are reproduced under the same [`set_seed`](crate::rand::set_seed) when the futures
+//! are polled on a single thread, e.g., by a current-thread Tokio runtime whose clock is paused.
+
+use std::collections::BTreeSet;
+use std::future::{IntoFuture, poll_fn};
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use std::{error, fmt};
+
+use futures_core::Stream;
+use rand::distr::uniform::SampleRange;
+use tokio::time::Sleep;
+
+use crate::rand::generator::Gen;
+
+/// Sleeps for a random duration within the given range.
+///
+/// A zero delay yields to the runtime instead, so that other tasks are still given a chance to
+/// run first.
+///
+/// # Parameters
+/// - `range`: The range of the delay, e.g., `Duration::ZERO..Duration::from_millis(5)`.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "async")]
+/// # {
+/// use std::time::Duration;
+///
+/// use regd_testing::async_util;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread()
+///     .enable_time()
+///     .start_paused(true)
+///     .build()
+///     .unwrap();
+/// runtime.block_on(async {
+///     let start = tokio::time::Instant::now();
+///     async_util::random_delay(Duration::from_millis(10)..Duration::from_millis(20)).await;
+///     assert!(start.elapsed() >= Duration::from_millis(10));
+/// });
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the range is empty, or if it is not awaited within a Tokio
+///   runtime with the time driver enabled.
+pub async fn random_delay<R>(range: R)
+where
+    R: SampleRange<Duration>,
+{
+    let delay = crate::rand::generate_duration(range);
+    if delay.is_zero() {
+        tokio::task::yield_now().await;
+    } else {
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Injects random pauses around awaited operations to perturb the ordering of tasks.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "async")]
+/// # {
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// use regd_testing::async_util::Jitter;
+///
+/// fn order(seed: u64) -> Vec<u32> {
+///     regd_testing::rand::set_seed(seed);
+///     let runtime = tokio::runtime::Builder::new_current_thread()
+///         .enable_time()
+///         .start_paused(true)
+///         .build()
+///         .unwrap();
+///     let log = Arc::new(Mutex::new(Vec::new()));
+///     runtime.block_on(async {
+///         let jitter = Jitter::new(Duration::ZERO..=Duration::from_millis(10));
+///         let tasks: Vec<_> = (0..4)
+///             .map(|i| {
+///                 let (jitter, log) = (jitter.clone(), Arc::clone(&log));
+///                 tokio::spawn(async move {
+///                     jitter.around(async { log.lock().unwrap().push(i) }).await;
+///                 })
+///             })
+///             .collect();
+///         for task in tasks {
+///             task.await.unwrap();
+///         }
+///     });
+///     let order = log.lock().unwrap().clone();
+///     order
+/// }
+///
+/// assert_eq!(order(42), order(42));
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Jitter {
+    range: RangeInclusive<Duration>,
+}
+
+impl Jitter {
+    /// Creates a jitter pausing for a random duration within the given range.
+    ///
+    /// # Panics
+    /// - This method will panic if the range is empty.
+    pub fn new(range: RangeInclusive<Duration>) -> Self {
+        assert!(!range.is_empty(), "{}", crate::error::Error::EmptyRange);
+        Self { range }
+    }
+
+    /// Pauses for a random duration.
+    pub async fn pause(&self) {
+        random_delay(self.range.clone()).await;
+    }
+
+    /// Awaits an operation between two random pauses.
+    pub async fn around<F: IntoFuture>(&self, operation: F) -> F::Output {
+        self.pause().await;
+        let output = operation.await;
+        self.pause().await;
+        output
+    }
+}
+
+impl Default for Jitter {
+    /// Creates a jitter pausing for up to one millisecond.
+    fn default() -> Self {
+        Self::new(Duration::ZERO..=Duration::from_millis(1))
+    }
+}
+
+/// The error yielded by a [`RandomStream`] at the positions chosen through
+/// [`RandomStream::error_at`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InjectedError {
+    /// The position of the item replaced by the error, starting at 0.
+    pub index: usize,
+}
+
+impl fmt::Display for InjectedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "injected error at item {}", self.index)
+    }
+}
+
+impl error::Error for InjectedError {}
+
+/// A stream of random items, which is created through [`random_stream`].
+///
+/// The stream implements `futures::Stream`, and may also be consumed without any stream
+/// combinator library through [`next`](Self::next).
+pub struct RandomStream<T, G> {
+    generator: G,
+    interval: Duration,
+    jitter: Option<Jitter>,
+    errors: BTreeSet<usize>,
+    end: Option<usize>,
+    index: usize,
+    sleep: Option<Pin<Box<Sleep>>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, G> fmt::Debug for RandomStream<T, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RandomStream")
+            .field("interval", &self.interval)
+            .field("jitter", &self.jitter)
+            .field("errors", &self.errors)
+            .field("end", &self.end)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, G: Gen<T>> RandomStream<T, G> {
+    /// Adds a random pause drawn from the given jitter to the interval before every item.
+    pub fn jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Yields an [`InjectedError`] instead of the item at the given position, starting at 0.
+    pub fn error_at(mut self, index: usize) -> Self {
+        self.errors.insert(index);
+        self
+    }
+
+    /// Ends the stream after the given number of items, including injected errors.
+    ///
+    /// The stream is endless by default.
+    pub fn end_at(mut self, len: usize) -> Self {
+        self.end = Some(len);
+        self
+    }
+
+    /// Returns the number of items yielded so far, including injected errors.
+    pub fn yielded(&self) -> usize {
+        self.index
+    }
+
+    /// Waits for the next item, or returns `None` once the stream has ended.
+    pub async fn next(&mut self) -> Option<Result<T, InjectedError>> {
+        poll_fn(|cx| self.poll_item(cx)).await
+    }
+
+    fn poll_item(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<T, InjectedError>>> {
+        if self.end.is_some_and(|end| self.index >= end) {
+            return Poll::Ready(None);
+        }
+        let sleep = self.sleep.get_or_insert_with(|| {
+            let jitter = self.jitter.as_ref().map_or(Duration::ZERO, |jitter| {
+                crate::rand::generate_duration(jitter.range.clone())
+            });
+            Box::pin(tokio::time::sleep(self.interval + jitter))
+        });
+        if sleep.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        self.sleep = None;
+        let index = self.index;
+        self.index += 1;
+        if self.errors.contains(&index) {
+            Poll::Ready(Some(Err(InjectedError { index })))
+        } else {
+            Poll::Ready(Some(Ok(self.generator.generate())))
+        }
+    }
+}
+
+impl<T, G: Gen<T> + Unpin> Stream for RandomStream<T, G> {
+    type Item = Result<T, InjectedError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_item(cx)
+    }
+}
+
+/// Creates a stream yielding random items at a fixed interval.
+///
+/// # Parameters
+/// - `generator`: The generator of the items.
+/// - `interval`: The time waited before every item.
+///
+/// # Returns
+/// - An endless [`RandomStream`], which may be configured to jitter, fail, or end.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "async")]
+/// # {
+/// use std::time::Duration;
+///
+/// use regd_testing::async_util;
+/// use regd_testing::rand::generator as g;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread()
+///     .enable_time()
+///     .start_paused(true)
+///     .build()
+///     .unwrap();
+/// runtime.block_on(async {
+///     let start = tokio::time::Instant::now();
+///     let mut stream = async_util::random_stream(g::range(0..10u8), Duration::from_millis(100))
+///         .error_at(1)
+///         .end_at(3);
+///     assert!(stream.next().await.unwrap().unwrap() < 10);
+///     assert_eq!(stream.next().await.unwrap().unwrap_err().index, 1);
+///     assert!(stream.next().await.unwrap().is_ok());
+///     assert!(stream.next().await.is_none());
+///     assert_eq!(start.elapsed(), Duration::from_millis(300));
+/// });
+/// # }
+/// ```
+///
+/// # Panics
+/// - The stream will panic if it is not polled within a Tokio runtime with the time driver
+///   enabled.
+pub fn random_stream<T, G: Gen<T>>(generator: G, interval: Duration) -> RandomStream<T, G> {
+    RandomStream {
+        generator,
+        interval,
+        jitter: None,
+        errors: BTreeSet::new(),
+        end: None,
+        index: 0,
+        sleep: None,
+        _marker: PhantomData,
+    }
+}
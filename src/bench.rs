@@ -0,0 +1,190 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of coarse performance checks.
+//!
+//! These utilities are meant for guarding against gross performance regressions, e.g., an
+//! accidentally quadratic algorithm, within ordinary tests, rather than for precise benchmarking.
+//! Timings of tests running in parallel, often in debug builds, are noisy, so that the budgets
+//! given to the assertions should be generous.
+
+use std::fmt;
+use std::hint;
+use std::time::{Duration, Instant};
+
+/// The number of iterations measured by [`assert_faster_than`].
+const DEFAULT_ITERATIONS: usize = 100;
+
+/// The timings of a closure measured by [`measure`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    /// The name of the measurement.
+    pub name: String,
+    /// The number of timed iterations, including the rejected outliers.
+    pub iterations: usize,
+    /// The number of iterations rejected as outliers.
+    pub outliers: usize,
+    /// The fastest iteration.
+    pub min: Duration,
+    /// The median iteration.
+    pub median: Duration,
+    /// The 95th percentile of the iterations.
+    pub p95: Duration,
+    /// The slowest iteration which was not rejected as an outlier.
+    pub max: Duration,
+}
+
+impl Measurement {
+    /// Asserts that the median iteration is faster than the given duration.
+    ///
+    /// # Parameters
+    /// - `duration`: The budget of a single iteration.
+    ///
+    /// # Returns
+    /// - The measurement itself, for chaining.
+    ///
+    /// # Panics
+    /// - This method will panic with the measurement if the median is not below `duration`.
+    #[track_caller]
+    pub fn assert_faster_than(&self, duration: Duration) -> &Self {
+        if self.median >= duration {
+            panic!("expected median faster than {duration:?}, but measured {self}");
+        }
+        self
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: min {:?}, median {:?}, p95 {:?}, max {:?} ({} iterations, {} outliers)",
+            self.name, self.min, self.median, self.p95, self.max, self.iterations, self.outliers
+        )
+    }
+}
+
+/// Returns the sample at the given quantile of sorted samples, by the nearest-rank method.
+fn quantile(sorted: &[Duration], q: f64) -> Duration {
+    let rank = (q * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Measures the time taken by a closure over the given number of iterations.
+///
+/// The closure first runs a tenth of the iterations, and at least once, as a warmup whose
+/// timings are discarded. Each of the following iterations is then timed separately, and the
+/// iterations slower than the upper Tukey fence, i.e., the third quartile plus 1.5 times the
+/// interquartile range, are rejected as outliers, e.g., caused by preemption. The values returned
+/// by the closure are passed through [`std::hint::black_box`], so that the computation is not
+/// optimized away.
+///
+/// # Parameters
+/// - `name`: The name of the measurement, used in its report.
+/// - `iterations`: The number of timed iterations.
+/// - `f`: The closure to measure.
+///
+/// # Returns
+/// - The [`Measurement`] of the iterations which were not rejected.
+///
+/// # Examples
+/// ```
+/// use regd_testing::bench;
+///
+/// let m = bench::measure("sort", 50, || {
+///     let mut v: Vec<u32> = (0..1000).rev().collect();
+///     v.sort();
+///     v
+/// });
+/// assert_eq!(m.name, "sort");
+/// assert_eq!(m.iterations, 50);
+/// assert!(m.outliers < m.iterations);
+/// assert!(m.min > std::time::Duration::ZERO);
+/// assert!(m.min <= m.median && m.median <= m.p95 && m.p95 <= m.max);
+/// assert!(m.to_string().starts_with("sort: min "));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `iterations` is zero.
+pub fn measure<T>(name: &str, iterations: usize, mut f: impl FnMut() -> T) -> Measurement {
+    if iterations == 0 {
+        panic!("cannot measure zero iterations");
+    }
+    for _ in 0..iterations.div_ceil(10) {
+        hint::black_box(f());
+    }
+    let mut samples: Vec<Duration> = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            hint::black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    samples.sort_unstable();
+    let q1 = quantile(&samples, 0.25);
+    let q3 = quantile(&samples, 0.75);
+    let fence = q3 + (q3 - q1) * 3 / 2;
+    let retained = samples.partition_point(|sample| *sample <= fence);
+    samples.truncate(retained);
+    Measurement {
+        name: name.to_string(),
+        iterations,
+        outliers: iterations - retained,
+        min: samples[0],
+        median: quantile(&samples, 0.5),
+        p95: quantile(&samples, 0.95),
+        max: samples[retained - 1],
+    }
+}
+
+/// Asserts that a closure runs faster than the given duration.
+///
+/// The closure is measured with [`measure`] over 100 iterations, and its median iteration is
+/// compared with `duration`, so that a few slow iterations do not fail the assertion.
+///
+/// # Parameters
+/// - `duration`: The budget of a single iteration.
+/// - `f`: The closure to measure.
+///
+/// # Returns
+/// - The [`Measurement`] of the closure.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::bench;
+///
+/// bench::assert_faster_than(Duration::from_millis(100), || (0..1000u64).sum::<u64>());
+/// ```
+///
+/// ```should_panic
+/// use std::time::Duration;
+///
+/// use regd_testing::bench;
+///
+/// bench::assert_faster_than(Duration::from_micros(10), || {
+///     std::thread::sleep(Duration::from_millis(1));
+/// });
+/// ```
+///
+/// # Panics
+/// - This function will panic with the measurement if the median iteration is not faster than
+///   `duration`.
+#[track_caller]
+pub fn assert_faster_than<T>(duration: Duration, f: impl FnMut() -> T) -> Measurement {
+    let measurement = measure("closure", DEFAULT_ITERATIONS, f);
+    measurement.assert_faster_than(duration);
+    measurement
+}
@@ -0,0 +1,212 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of standard output and error capture.
+//!
+//! The capture redirects the file descriptors of the process rather than the Rust streams, so
+//! that output written by spawned native code, e.g., through `printf`, is captured as well. Since
+//! the file descriptors are global to the process, captures are serialized by a process-wide
+//! lock, and output written by other threads during a capture is captured too.
+//!
+//! # Notes
+//! - When run by the libtest harness without `--nocapture`, `print!` and `eprint!` of the test
+//!   thread are captured by the harness itself and never reach the file descriptors. Output
+//!   written directly to `std::io::stdout()` or `std::io::stderr()`, or by native code, is not
+//!   affected.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::fd::{AsRawFd, RawFd};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::env::ReentrantLock;
+
+/// The lock serializing every capture made through this module.
+static CAPTURE_LOCK: ReentrantLock = ReentrantLock::new();
+
+/// A redirection of a file descriptor into a temporary file, which is reverted on drop.
+struct Redirect {
+    fd: RawFd,
+    saved: RawFd,
+    file: File,
+}
+
+impl Redirect {
+    /// Redirects the given file descriptor into a new temporary file.
+    fn new(fd: RawFd) -> Self {
+        let file =
+            tempfile::tempfile().unwrap_or_else(|e| panic!("cannot create capture file: {e}"));
+        flush();
+        // SAFETY: `fd` is a standard stream.
+        let saved = unsafe { libc::dup(fd) };
+        if saved < 0 {
+            panic!(
+                "cannot redirect file descriptor {fd}: {}",
+                io::Error::last_os_error()
+            );
+        }
+        let redirect = Self { fd, saved, file };
+        // SAFETY: Both file descriptors are valid and open.
+        if unsafe { libc::dup2(redirect.file.as_raw_fd(), fd) } < 0 {
+            panic!(
+                "cannot redirect file descriptor {fd}: {}",
+                io::Error::last_os_error()
+            );
+        }
+        redirect
+    }
+
+    /// Reverts the redirection and returns the captured text.
+    fn finish(mut self) -> String {
+        self.restore();
+        let mut captured = Vec::new();
+        let _ = self.file.seek(SeekFrom::Start(0));
+        let _ = self.file.read_to_end(&mut captured);
+        String::from_utf8_lossy(&captured).into_owned()
+    }
+
+    /// Reverts the redirection, if not reverted yet.
+    fn restore(&mut self) {
+        if self.saved < 0 {
+            return;
+        }
+        flush();
+        // SAFETY: `saved` is a duplicate of the original stream owned by this redirection.
+        unsafe {
+            libc::dup2(self.saved, self.fd);
+            libc::close(self.saved);
+        }
+        self.saved = -1;
+    }
+}
+
+impl Drop for Redirect {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+/// Flushes the buffers of both the Rust and the C standard streams.
+fn flush() {
+    let _ = io::stdout().flush();
+    let _ = io::stderr().flush();
+    // SAFETY: Flushing every C stream is always sound.
+    unsafe {
+        libc::fflush(std::ptr::null_mut());
+    }
+}
+
+/// Runs a closure while redirecting the given file descriptors, returning the captured texts in
+/// the same order.
+fn capture<T, const N: usize>(fds: [RawFd; N], f: impl FnOnce() -> T) -> (T, [String; N]) {
+    let _lock = CAPTURE_LOCK.lock();
+    let redirects = fds.map(Redirect::new);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let captured = redirects.map(Redirect::finish);
+    match result {
+        Ok(value) => (value, captured),
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// Runs a closure while capturing the standard output of the process.
+///
+/// # Parameters
+/// - `f`: The closure to run.
+///
+/// # Returns
+/// - The value returned by `f` and the text written to the standard output, with invalid UTF-8
+///   sequences replaced.
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+///
+/// use regd_testing::capture;
+///
+/// let (x, out) = capture::stdout(|| {
+///     writeln!(std::io::stdout(), "hello").unwrap();
+///     42
+/// });
+/// assert_eq!(x, 42);
+/// assert_eq!(out, "hello\n");
+/// ```
+///
+/// # Panics
+/// - This function will panic if the standard output cannot be redirected. A panic of `f` is
+///   propagated after the standard output is restored.
+pub fn stdout<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let (value, [out]) = capture([libc::STDOUT_FILENO], f);
+    (value, out)
+}
+
+/// Runs a closure while capturing the standard error of the process.
+///
+/// # Parameters
+/// - `f`: The closure to run.
+///
+/// # Returns
+/// - The value returned by `f` and the text written to the standard error, with invalid UTF-8
+///   sequences replaced.
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+///
+/// use regd_testing::capture;
+///
+/// let ((), err) = capture::stderr(|| {
+///     write!(std::io::stderr(), "warning").unwrap();
+/// });
+/// assert_eq!(err, "warning");
+/// ```
+///
+/// # Panics
+/// - This function will panic if the standard error cannot be redirected. A panic of `f` is
+///   propagated after the standard error is restored.
+pub fn stderr<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let (value, [err]) = capture([libc::STDERR_FILENO], f);
+    (value, err)
+}
+
+/// Runs a closure while capturing both the standard output and the standard error of the
+/// process.
+///
+/// # Parameters
+/// - `f`: The closure to run.
+///
+/// # Returns
+/// - The value returned by `f`, the text written to the standard output, and the text written
+///   to the standard error.
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+///
+/// use regd_testing::capture;
+///
+/// let ((), out, err) = capture::both(|| {
+///     write!(std::io::stdout(), "out").unwrap();
+///     write!(std::io::stderr(), "err").unwrap();
+/// });
+/// assert_eq!((out.as_str(), err.as_str()), ("out", "err"));
+/// ```
+///
+/// # Panics
+/// - This function will panic if the standard streams cannot be redirected. A panic of `f` is
+///   propagated after the standard streams are restored.
+pub fn both<T>(f: impl FnOnce() -> T) -> (T, String, String) {
+    let (value, [out, err]) = capture([libc::STDOUT_FILENO, libc::STDERR_FILENO], f);
+    (value, out, err)
+}
@@ -0,0 +1,249 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of concurrent code.
+//!
+//! Besides stress testing and watchdogs, it provides a lightweight exploration of thread
+//! interleavings through [`explore`], whose synchronization primitives inject seeded random
+//! delays.
+
+use std::fmt::Write as _;
+use std::io::{self, Write as _};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Barrier;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::assert::panic_message;
+
+mod explore;
+
+pub use self::explore::{
+    ExploreOpts, TestMutex, TestMutexGuard, TestNotify, explore, explore_with, yield_point,
+};
+
+/// Runs a closure repeatedly on several threads at once to smoke out races.
+///
+/// Every thread waits on a barrier before its first iteration, so that all threads start
+/// contending at the same time. A thread stops at its first panic, while the other threads run
+/// every iteration.
+///
+/// # Parameters
+/// - `threads`: The number of threads.
+/// - `iterations`: The number of times each thread runs `f`.
+/// - `f`: The closure to run, which usually touches shared state and asserts its invariants.
+///
+/// # Examples
+/// ```
+/// use std::sync::Mutex;
+///
+/// use regd_testing::concurrency;
+///
+/// let counter = Mutex::new(0);
+/// concurrency::stress(8, 100, || {
+///     *counter.lock().unwrap() += 1;
+/// });
+/// assert_eq!(*counter.lock().unwrap(), 800);
+/// ```
+///
+/// Every panic is reported with the thread and the iteration it happened in:
+/// ```
+/// use std::cell::Cell;
+///
+/// use regd_testing::{assert, concurrency};
+///
+/// thread_local! {
+///     static CALLS: Cell<usize> = const { Cell::new(0) };
+/// }
+///
+/// let report = assert::panics_with("2 of 2 threads panicked", || {
+///     concurrency::stress(2, 10, || {
+///         CALLS.set(CALLS.get() + 1);
+///         assert!(CALLS.get() < 4, "invariant violated");
+///     })
+/// });
+/// assert!(report.contains("thread 1, iteration 3: invariant violated"));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `threads` is zero, or with a report of the thread and the
+///   iteration of every panic of `f`, after every thread has finished.
+#[track_caller]
+pub fn stress(threads: usize, iterations: usize, f: impl Fn() + Sync) {
+    assert!(threads > 0, "cannot stress with zero threads");
+    let barrier = Barrier::new(threads);
+    let failures: Vec<(usize, usize, String)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread| {
+                let (barrier, f) = (&barrier, &f);
+                scope.spawn(move || {
+                    barrier.wait();
+                    (0..iterations).find_map(|iteration| {
+                        panic::catch_unwind(AssertUnwindSafe(f))
+                            .err()
+                            .map(|payload| (thread, iteration, panic_message(&*payload)))
+                    })
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok().flatten())
+            .collect()
+    });
+    if !failures.is_empty() {
+        let mut report = format!("{} of {threads} threads panicked:", failures.len());
+        for (thread, iteration, message) in failures {
+            let _ = write!(
+                report,
+                "\n  thread {thread}, iteration {iteration}: {message}"
+            );
+        }
+        panic!("{report}");
+    }
+}
+
+/// Runs a closure, aborting the process with diagnostics if it does not finish in time.
+///
+/// A monitor thread waits for the closure. Once `timeout` elapses, it prints the name of the
+/// watched thread and, on Linux, the state and a best-effort backtrace of every thread of the
+/// process to the standard error, then aborts. This turns a deadlocked test, which would
+/// otherwise hang the test run without any output, into a failure pointing at the culprit.
+///
+/// # Parameters
+/// - `timeout`: The budget of the closure.
+/// - `f`: The closure to run.
+///
+/// # Returns
+/// - The value returned by `f`. A panic of `f` is propagated.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::concurrency;
+///
+/// let x = concurrency::with_watchdog(Duration::from_secs(10), || 6 * 7);
+/// assert_eq!(x, 42);
+/// ```
+///
+/// # Notes
+/// - The backtraces are captured by signal handlers of the stuck threads, which is not
+///   async-signal-safe; a thread which cannot report within a second is skipped. The whole
+///   process is aborted, so that the other tests of the same binary are aborted too.
+pub fn with_watchdog<T>(timeout: Duration, f: impl FnOnce() -> T) -> T {
+    let (done, finished) = mpsc::channel::<()>();
+    let watched = thread::current().name().unwrap_or("<unnamed>").to_string();
+    let monitor = thread::Builder::new()
+        .name("regd-watchdog".to_string())
+        .spawn(move || {
+            if let Err(RecvTimeoutError::Timeout) = finished.recv_timeout(timeout) {
+                report(&format!(
+                    "watchdog: thread '{watched}' did not finish within {timeout:?}"
+                ));
+                dump_threads();
+                report("watchdog: aborting");
+                std::process::abort();
+            }
+        })
+        .unwrap_or_else(|e| panic!("cannot spawn watchdog: {e}"));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    drop(done);
+    let _ = monitor.join();
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+/// Writes a line of diagnostics to the standard error and flushes it.
+///
+/// Unlike `eprintln!`, this bypasses the output capture of the libtest harness, which would
+/// otherwise swallow the diagnostics when the process aborts.
+fn report(line: &str) {
+    let mut stderr = io::stderr().lock();
+    let _ = writeln!(stderr, "{line}");
+    let _ = stderr.flush();
+}
+
+/// Prints the state and the backtrace of every other thread of the process.
+#[cfg(target_os = "linux")]
+fn dump_threads() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Instant;
+
+    static DUMPED: AtomicUsize = AtomicUsize::new(0);
+
+    extern "C" fn dump(_: libc::c_int) {
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        let thread = thread::current();
+        report(&format!(
+            "watchdog: backtrace of thread '{}':\n{backtrace}",
+            thread.name().unwrap_or("<unnamed>")
+        ));
+        DUMPED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let signal = libc::SIGRTMIN();
+    // SAFETY: The handler is a valid function for the whole lifetime of the process.
+    unsafe {
+        libc::signal(
+            signal,
+            dump as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+    // SAFETY: These system calls have no preconditions.
+    let (pid, current) = unsafe {
+        (
+            libc::getpid(),
+            libc::syscall(libc::SYS_gettid) as libc::pid_t,
+        )
+    };
+    let Ok(tasks) = std::fs::read_dir("/proc/self/task") else {
+        return;
+    };
+    for tid in tasks
+        .flatten()
+        .filter_map(|task| task.file_name().to_str()?.parse::<libc::pid_t>().ok())
+        .filter(|&tid| tid != current)
+    {
+        let stat =
+            std::fs::read_to_string(format!("/proc/self/task/{tid}/stat")).unwrap_or_default();
+        // The command name may contain spaces, so that the fields are split after its `)`.
+        let (name, state) = match (stat.find('('), stat.rfind(')')) {
+            (Some(open), Some(close)) => (
+                &stat[open + 1..close],
+                stat[close + 1..].split_whitespace().next().unwrap_or("?"),
+            ),
+            _ => ("?", "?"),
+        };
+        report(&format!(
+            "watchdog: thread {tid} ({name}) is in state {state}"
+        ));
+        let before = DUMPED.load(Ordering::SeqCst);
+        // SAFETY: Sending a signal with an installed handler to a thread of this process.
+        if unsafe { libc::syscall(libc::SYS_tgkill, pid, tid, signal) } == 0 {
+            let start = Instant::now();
+            while DUMPED.load(Ordering::SeqCst) == before
+                && start.elapsed() < Duration::from_secs(1)
+            {
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+    }
+}
+
+/// Prints the threads of the process, which are not available on this platform.
+#[cfg(not(target_os = "linux"))]
+fn dump_threads() {
+    report("watchdog: thread backtraces are not available on this platform");
+}
@@ -0,0 +1,394 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a lightweight exploration of thread interleavings.
+//!
+//! Rather than enumerating every interleaving like `loom`, the synchronization points of
+//! [`TestMutex`] and [`TestNotify`], as well as explicit [`yield_point`]s, are perturbed by
+//! random yields and sleeps drawn from a seeded generator, so that a run of many iterations
+//! covers orderings which the OS scheduler rarely produces.
+
+use std::ops::{Deref, DerefMut};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::assert::panic_message;
+use crate::env::ReentrantLock;
+
+/// The lock serializing every exploration, so that the perturbations of concurrent tests do not
+/// draw from the same schedule.
+static EXPLORE_LOCK: ReentrantLock = ReentrantLock::new();
+
+/// The schedule of the running exploration, if any.
+static SCHEDULE: Mutex<Option<Schedule>> = Mutex::new(None);
+
+/// The perturbations applied at synchronization points during an iteration.
+struct Schedule {
+    rng: StdRng,
+    max_delay: Duration,
+}
+
+/// Options of [`explore_with`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExploreOpts {
+    /// The number of times the closure is run.
+    pub iterations: usize,
+    /// The maximum delay injected at a synchronization point.
+    pub max_delay: Duration,
+    /// The seed of the first iteration, or `None` to draw it from [`crate::rand::rng`]; the
+    /// iteration `i` is seeded with `seed + i`.
+    pub seed: Option<u64>,
+}
+
+impl Default for ExploreOpts {
+    fn default() -> Self {
+        Self {
+            iterations: 100,
+            max_delay: Duration::from_micros(50),
+            seed: None,
+        }
+    }
+}
+
+/// Perturbs the current thread if an exploration is running, and does nothing otherwise.
+///
+/// Each synchronization point of [`TestMutex`] and [`TestNotify`] calls this function; it may
+/// also be called explicitly between two operations on shared state which are not otherwise
+/// synchronized, e.g., between the load and the store of an atomic.
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::{AtomicU32, Ordering};
+///
+/// use regd_testing::concurrency;
+///
+/// let counter = AtomicU32::new(0);
+/// let value = counter.load(Ordering::SeqCst);
+/// concurrency::yield_point();
+/// counter.store(value + 1, Ordering::SeqCst);
+/// ```
+pub fn yield_point() {
+    let perturbation = SCHEDULE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_mut()
+        .map(|schedule| match schedule.rng.random_range(0..3) {
+            0 => None,
+            _ if schedule.max_delay.is_zero() => Some(Duration::ZERO),
+            1 => Some(Duration::ZERO),
+            _ => Some(
+                schedule
+                    .rng
+                    .random_range(Duration::ZERO..=schedule.max_delay),
+            ),
+        });
+    match perturbation.flatten() {
+        Some(Duration::ZERO) => thread::yield_now(),
+        Some(delay) => thread::sleep(delay),
+        None => {}
+    }
+}
+
+/// Runs a closure 100 times with perturbed synchronization points.
+///
+/// This function is a shorthand of [`explore_with`] called with the default [`ExploreOpts`].
+///
+/// # Parameters
+/// - `f`: The closure to run, which usually spawns threads sharing [`TestMutex`]es and
+///   [`TestNotify`]s, and asserts the final state.
+///
+/// # Examples
+/// ```
+/// use std::thread;
+///
+/// use regd_testing::concurrency::{self, TestMutex};
+///
+/// concurrency::explore(|| {
+///     let counter = TestMutex::new(0);
+///     thread::scope(|scope| {
+///         for _ in 0..4 {
+///             scope.spawn(|| *counter.lock() += 1);
+///         }
+///     });
+///     assert_eq!(counter.into_inner(), 4);
+/// });
+/// ```
+///
+/// A lost update between two critical sections is found within a few iterations:
+/// ```
+/// use std::thread;
+///
+/// use regd_testing::assert;
+/// use regd_testing::concurrency::{self, TestMutex};
+///
+/// assert::panics_with("exploration failed", || {
+///     concurrency::explore(|| {
+///         let counter = TestMutex::new(0);
+///         thread::scope(|scope| {
+///             for _ in 0..2 {
+///                 scope.spawn(|| {
+///                     let value = *counter.lock();
+///                     *counter.lock() = value + 1;
+///                 });
+///             }
+///         });
+///         assert_eq!(counter.into_inner(), 2);
+///     })
+/// });
+/// ```
+///
+/// # Panics
+/// - This function will panic with the seed of the failing iteration if `f` panics.
+#[track_caller]
+pub fn explore(f: impl Fn()) {
+    explore_with(&ExploreOpts::default(), f);
+}
+
+/// Runs a closure repeatedly with perturbed synchronization points.
+///
+/// Explorations are serialized process-wide. The iterations stop at the first panic of `f`,
+/// which is reported with the seed of the iteration, so that the failure may be replayed with
+/// that `seed` and a single iteration. Since the OS scheduler is not controlled, a replay makes
+/// the failure much more likely but not certain.
+///
+/// # Parameters
+/// - `opts`: The options of the exploration.
+/// - `f`: The closure to run.
+///
+/// # Examples
+/// ```
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// use regd_testing::concurrency::{self, ExploreOpts, TestMutex};
+///
+/// let opts = ExploreOpts {
+///     iterations: 20,
+///     max_delay: Duration::from_micros(10),
+///     seed: Some(42),
+/// };
+/// concurrency::explore_with(&opts, || {
+///     let log = TestMutex::new(Vec::new());
+///     thread::scope(|scope| {
+///         scope.spawn(|| log.lock().push('a'));
+///         scope.spawn(|| log.lock().push('b'));
+///     });
+///     assert_eq!(log.into_inner().len(), 2);
+/// });
+/// ```
+///
+/// # Panics
+/// - This function will panic with the seed of the failing iteration if `f` panics.
+#[track_caller]
+pub fn explore_with(opts: &ExploreOpts, f: impl Fn()) {
+    let _lock = EXPLORE_LOCK.lock();
+    let seed = opts.seed.unwrap_or_else(crate::rand::generate);
+    for iteration in 0..opts.iterations {
+        let iteration_seed = seed.wrapping_add(iteration as u64);
+        *SCHEDULE.lock().unwrap_or_else(PoisonError::into_inner) = Some(Schedule {
+            rng: StdRng::seed_from_u64(iteration_seed),
+            max_delay: opts.max_delay,
+        });
+        let result = panic::catch_unwind(AssertUnwindSafe(&f));
+        *SCHEDULE.lock().unwrap_or_else(PoisonError::into_inner) = None;
+        if let Err(payload) = result {
+            panic!(
+                "exploration failed at iteration {iteration} with seed {iteration_seed}: {}",
+                panic_message(&*payload)
+            );
+        }
+    }
+}
+
+/// A mutex whose lock and unlock are synchronization points of an exploration.
+///
+/// Outside of an exploration, it behaves like a `std::sync::Mutex` which ignores poisoning.
+#[derive(Debug, Default)]
+pub struct TestMutex<T> {
+    inner: Mutex<T>,
+}
+
+impl<T> TestMutex<T> {
+    /// Creates a new mutex holding the given value.
+    pub const fn new(value: T) -> Self {
+        Self {
+            inner: Mutex::new(value),
+        }
+    }
+
+    /// Acquires the mutex, blocking while another thread holds it.
+    pub fn lock(&self) -> TestMutexGuard<'_, T> {
+        yield_point();
+        let guard = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        yield_point();
+        TestMutexGuard { inner: Some(guard) }
+    }
+
+    /// Consumes the mutex and returns the value it holds.
+    pub fn into_inner(self) -> T {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A guard of a [`TestMutex`], which releases the mutex on drop.
+#[derive(Debug)]
+pub struct TestMutexGuard<'a, T> {
+    inner: Option<MutexGuard<'a, T>>,
+}
+
+impl<T> Deref for TestMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.inner.as_deref().expect("guard is held until dropped")
+    }
+}
+
+impl<T> DerefMut for TestMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner
+            .as_deref_mut()
+            .expect("guard is held until dropped")
+    }
+}
+
+impl<T> Drop for TestMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        drop(self.inner.take());
+        yield_point();
+    }
+}
+
+/// A notification primitive whose operations are synchronization points of an exploration.
+///
+/// Like `tokio::sync::Notify`, [`notify_one`](Self::notify_one) stores a single permit if no
+/// thread is waiting, which is consumed by the next [`wait`](Self::wait), while
+/// [`notify_all`](Self::notify_all) only wakes the threads already waiting.
+///
+/// # Examples
+/// ```
+/// use std::thread;
+///
+/// use regd_testing::concurrency::{self, TestMutex, TestNotify};
+///
+/// concurrency::explore(|| {
+///     let ready = TestMutex::new(false);
+///     let notify = TestNotify::new();
+///     thread::scope(|scope| {
+///         scope.spawn(|| {
+///             *ready.lock() = true;
+///             notify.notify_one();
+///         });
+///         notify.wait();
+///         assert!(*ready.lock());
+///     });
+/// });
+/// ```
+#[derive(Debug, Default)]
+pub struct TestNotify {
+    state: Mutex<NotifyState>,
+    changed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct NotifyState {
+    permit: bool,
+    generation: u64,
+}
+
+impl TestNotify {
+    /// Creates a new notification without a stored permit.
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(NotifyState {
+                permit: false,
+                generation: 0,
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Wakes one waiting thread, or stores a permit for the next one.
+    pub fn notify_one(&self) {
+        yield_point();
+        self.lock().permit = true;
+        self.changed.notify_one();
+        yield_point();
+    }
+
+    /// Wakes every waiting thread without storing a permit.
+    pub fn notify_all(&self) {
+        yield_point();
+        self.lock().generation += 1;
+        self.changed.notify_all();
+        yield_point();
+    }
+
+    /// Blocks until notified.
+    pub fn wait(&self) {
+        self.wait_until(None);
+    }
+
+    /// Blocks until notified or until the timeout elapses.
+    ///
+    /// # Returns
+    /// - `true` if notified, or `false` on timeout.
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        self.wait_until(Some(Instant::now() + timeout))
+    }
+
+    fn wait_until(&self, deadline: Option<Instant>) -> bool {
+        yield_point();
+        let mut state = self.lock();
+        let generation = state.generation;
+        let notified = loop {
+            if state.permit {
+                state.permit = false;
+                break true;
+            }
+            if state.generation != generation {
+                break true;
+            }
+            state = match deadline {
+                None => self
+                    .changed
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner),
+                Some(deadline) => {
+                    let Some(timeout) = deadline.checked_duration_since(Instant::now()) else {
+                        break false;
+                    };
+                    self.changed
+                        .wait_timeout(state, timeout)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+            };
+        };
+        drop(state);
+        yield_point();
+        notified
+    }
+
+    fn lock(&self) -> MutexGuard<'_, NotifyState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of Docker container fixtures.
+//!
+//! Containers are managed through the `docker` command line, so that no Docker client library
+//! is required, and any compatible command, e.g., `podman`, may be used by setting the
+//! `REGD_TESTING_DOCKER` environment variable.
+
+use std::collections::BTreeMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use crate::proc::{self, ProcOutput};
+
+/// The default time to wait for a container to be ready.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Returns the Docker command to run.
+fn docker() -> String {
+    std::env::var("REGD_TESTING_DOCKER").unwrap_or_else(|_| "docker".to_string())
+}
+
+/// A builder of a [`Container`], which is created through [`Container::run`].
+#[derive(Clone, Debug)]
+pub struct ContainerBuilder {
+    image: String,
+    ports: Vec<u16>,
+    env: Vec<(String, String)>,
+    args: Vec<String>,
+    ready_log: Option<String>,
+    timeout: Duration,
+}
+
+impl ContainerBuilder {
+    /// Publishes a port of the container on a free port of the loopback interface of the host.
+    pub fn port(mut self, port: u16) -> Self {
+        self.ports.push(port);
+        self
+    }
+
+    /// Sets an environment variable of the container.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Adds an argument passed to the entrypoint of the container.
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Waits, when started, until the logs of the container contain the given message.
+    pub fn wait_for_log(mut self, message: impl Into<String>) -> Self {
+        self.ready_log = Some(message.into());
+        self
+    }
+
+    /// Sets the maximum time to wait for the ready message. Defaults to 60 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Starts the container and waits until it is ready.
+    ///
+    /// # Returns
+    /// - The running [`Container`], which is removed on drop.
+    ///
+    /// # Panics
+    /// - This method will panic with the output of `docker` if the container cannot be started,
+    ///   or with its logs if it exits or is not ready in time, in which case it is removed.
+    #[track_caller]
+    pub fn start(self) -> Container {
+        let ports: BTreeMap<u16, u16> = self
+            .ports
+            .iter()
+            .map(|&port| (port, crate::net::free_port()))
+            .collect();
+        let mut run = proc::run(docker()).args(["run", "--detach"]);
+        for (port, host_port) in &ports {
+            run = run
+                .arg("--publish")
+                .arg(format!("127.0.0.1:{host_port}:{port}"));
+        }
+        for (key, value) in &self.env {
+            run = run.arg("--env").arg(format!("{key}={value}"));
+        }
+        let output = run.arg(&self.image).args(&self.args).output();
+        if !output.status.success() {
+            panic!("cannot start container {}\n{output}", self.image);
+        }
+        let container = Container {
+            id: output.stdout_string().trim().to_string(),
+            image: self.image,
+            ports,
+        };
+        if let Some(message) = &self.ready_log {
+            container.await_log(message, self.timeout);
+        }
+        container
+    }
+}
+
+/// A running Docker container, which is forcibly removed on drop, including when a test panics.
+///
+/// # Examples
+/// ```no_run
+/// # #[cfg(feature = "docker")]
+/// # {
+/// use std::net::TcpStream;
+///
+/// use regd_testing::container::Container;
+///
+/// let redis = Container::run("redis:7")
+///     .port(6379)
+///     .wait_for_log("Ready to accept connections")
+///     .start();
+/// let stream = TcpStream::connect(redis.addr(6379)).unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Container {
+    id: String,
+    image: String,
+    ports: BTreeMap<u16, u16>,
+}
+
+impl Container {
+    /// Creates a builder running the given image.
+    ///
+    /// # Parameters
+    /// - `image`: The image to run, e.g., `redis:7`, which is pulled if missing.
+    ///
+    /// # Returns
+    /// - A [`ContainerBuilder`] whose [`start`](ContainerBuilder::start) starts the container.
+    pub fn run(image: impl Into<String>) -> ContainerBuilder {
+        ContainerBuilder {
+            image: image.into(),
+            ports: Vec::new(),
+            env: Vec::new(),
+            args: Vec::new(),
+            ready_log: None,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Returns the identifier of the container.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the host port on which the given port of the container is published.
+    ///
+    /// # Panics
+    /// - This method will panic if the port was not published through
+    ///   [`ContainerBuilder::port`].
+    pub fn port(&self, port: u16) -> u16 {
+        *self
+            .ports
+            .get(&port)
+            .unwrap_or_else(|| panic!("cannot find published port {port} of {}", self.image))
+    }
+
+    /// Returns the loopback address on which the given port of the container is published.
+    ///
+    /// # Panics
+    /// - This method will panic if the port was not published through
+    ///   [`ContainerBuilder::port`].
+    pub fn addr(&self, port: u16) -> SocketAddr {
+        SocketAddr::from((Ipv4Addr::LOCALHOST, self.port(port)))
+    }
+
+    /// Returns the standard output and the standard error logged by the container so far.
+    pub fn logs(&self) -> String {
+        let output = self.docker(&["logs", &self.id]);
+        output.stdout_string() + &output.stderr_string()
+    }
+
+    /// Runs a `docker` subcommand.
+    fn docker(&self, args: &[&str]) -> ProcOutput {
+        proc::run(docker()).args(args).output()
+    }
+
+    /// Returns `true` if the container is still running.
+    fn is_running(&self) -> bool {
+        let output = self.docker(&["inspect", "--format", "{{.State.Running}}", &self.id]);
+        output.stdout_string().trim() == "true"
+    }
+
+    /// Waits until the logs contain the given message.
+    #[track_caller]
+    fn await_log(&self, message: &str, timeout: Duration) {
+        let mut exited = false;
+        let result = crate::poll::poll(timeout, || {
+            if self.logs().contains(message) {
+                return true;
+            }
+            exited = !self.is_running();
+            exited
+        });
+        if exited || result.is_err() {
+            let state = if exited { "exited" } else { "timed out" };
+            panic!(
+                "container {} {state} before logging {message:?}; logs:\n{}",
+                self.image,
+                self.logs()
+            );
+        }
+    }
+}
+
+impl Drop for Container {
+    fn drop(&mut self) {
+        let _ = self.docker(&["rm", "--force", "--volumes", &self.id]);
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of scoped process environment changes.
+//!
+//! The environment and the current directory are global to the process while tests run in
+//! parallel threads, so that every change made through this module holds a process-wide lock
+//! until it is reverted. The locks are reentrant, so that a single test may hold several guards
+//! at once.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::thread::{self, ThreadId};
+
+/// A process-wide lock which may be acquired several times by the same thread.
+pub(crate) struct ReentrantLock {
+    state: Mutex<(Option<ThreadId>, usize)>,
+    released: Condvar,
+}
+
+/// A guard of a [`ReentrantLock`], which releases one level of the lock on drop.
+pub(crate) struct ReentrantLockGuard {
+    lock: &'static ReentrantLock,
+}
+
+impl ReentrantLock {
+    /// Creates a new, unlocked lock.
+    pub(crate) const fn new() -> Self {
+        Self {
+            state: Mutex::new((None, 0)),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Acquires the lock, blocking while another thread holds it.
+    pub(crate) fn lock(&'static self) -> ReentrantLockGuard {
+        let current = thread::current().id();
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        while state.0.is_some_and(|owner| owner != current) {
+            state = self
+                .released
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        state.0 = Some(current);
+        state.1 += 1;
+        ReentrantLockGuard { lock: self }
+    }
+}
+
+impl Drop for ReentrantLockGuard {
+    fn drop(&mut self) {
+        let mut state = self
+            .lock
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        state.1 -= 1;
+        if state.1 == 0 {
+            state.0 = None;
+            self.lock.released.notify_one();
+        }
+    }
+}
+
+/// The lock serializing every change of the environment made through this module.
+pub(crate) static ENV_LOCK: ReentrantLock = ReentrantLock::new();
+
+/// The lock serializing every change of the current directory made through this module.
+static CWD_LOCK: ReentrantLock = ReentrantLock::new();
+
+/// A guard which restores the previous values of environment variables on drop.
+///
+/// Variables which were not set before are removed again. This guard is created through
+/// [`set_scoped`], [`remove_scoped`] or [`clear_scoped`], and holds the process-wide environment
+/// lock until it is dropped.
+#[must_use = "the environment is restored as soon as the guard is dropped"]
+pub struct EnvGuard {
+    previous: Vec<(OsString, Option<OsString>)>,
+    _lock: ReentrantLockGuard,
+}
+
+impl EnvGuard {
+    /// Acquires the environment lock and applies `change` to each of the given variables.
+    fn new(keys: Vec<OsString>, change: impl Fn(&OsStr)) -> Self {
+        let lock = ENV_LOCK.lock();
+        let previous = keys
+            .into_iter()
+            .map(|key| {
+                let value = std::env::var_os(&key);
+                change(&key);
+                (key, value)
+            })
+            .collect();
+        Self {
+            previous,
+            _lock: lock,
+        }
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (key, value) in self.previous.drain(..).rev() {
+            // SAFETY: The environment is only mutated while holding `ENV_LOCK`.
+            unsafe {
+                match value {
+                    Some(value) => std::env::set_var(&key, value),
+                    None => std::env::remove_var(&key),
+                }
+            }
+        }
+    }
+}
+
+/// Sets an environment variable until the returned guard is dropped.
+///
+/// # Parameters
+/// - `key`: The name of the variable.
+/// - `value`: The value of the variable.
+///
+/// # Returns
+/// - An [`EnvGuard`] which restores the previous value, or removes the variable if it was not
+///   set, when dropped.
+///
+/// # Examples
+/// ```
+/// use regd_testing::env;
+///
+/// {
+///     let _guard = env::set_scoped("REGD_TESTING_EXAMPLE", "1");
+///     assert_eq!(std::env::var("REGD_TESTING_EXAMPLE").unwrap(), "1");
+/// }
+/// assert!(std::env::var("REGD_TESTING_EXAMPLE").is_err());
+/// ```
+///
+/// # Notes
+/// - Only changes made through this module are serialized; code reading or writing the
+///   environment directly may still race with it.
+pub fn set_scoped(key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> EnvGuard {
+    let value = value.as_ref();
+    EnvGuard::new(vec![key.as_ref().to_os_string()], |key| {
+        // SAFETY: The environment is only mutated while holding `ENV_LOCK`.
+        unsafe { std::env::set_var(key, value) }
+    })
+}
+
+/// Removes an environment variable until the returned guard is dropped.
+///
+/// # Parameters
+/// - `key`: The name of the variable.
+///
+/// # Returns
+/// - An [`EnvGuard`] which restores the previous value, if any, when dropped.
+///
+/// # Examples
+/// ```
+/// use regd_testing::env;
+///
+/// let _outer = env::set_scoped("REGD_TESTING_REMOVED", "1");
+/// {
+///     let _inner = env::remove_scoped("REGD_TESTING_REMOVED");
+///     assert!(std::env::var("REGD_TESTING_REMOVED").is_err());
+/// }
+/// assert_eq!(std::env::var("REGD_TESTING_REMOVED").unwrap(), "1");
+/// ```
+pub fn remove_scoped(key: impl AsRef<OsStr>) -> EnvGuard {
+    EnvGuard::new(vec![key.as_ref().to_os_string()], |key| {
+        // SAFETY: The environment is only mutated while holding `ENV_LOCK`.
+        unsafe { std::env::remove_var(key) }
+    })
+}
+
+/// Removes every environment variable whose name starts with the given prefix until the
+/// returned guard is dropped.
+///
+/// This is useful for isolating code which reads a whole family of variables, e.g., every
+/// `MYAPP_*` setting, from the environment of the developer running the tests.
+///
+/// # Parameters
+/// - `prefix`: The prefix of the names of the variables to remove.
+///
+/// # Returns
+/// - An [`EnvGuard`] which restores every removed variable when dropped.
+///
+/// # Examples
+/// ```
+/// use regd_testing::env;
+///
+/// let _a = env::set_scoped("REGD_TESTING_PREFIX_A", "a");
+/// let _b = env::set_scoped("REGD_TESTING_PREFIX_B", "b");
+/// {
+///     let _cleared = env::clear_scoped("REGD_TESTING_PREFIX_");
+///     assert!(std::env::vars().all(|(k, _)| !k.starts_with("REGD_TESTING_PREFIX_")));
+/// }
+/// assert_eq!(std::env::var("REGD_TESTING_PREFIX_B").unwrap(), "b");
+/// ```
+pub fn clear_scoped(prefix: &str) -> EnvGuard {
+    let lock = ENV_LOCK.lock();
+    let keys = std::env::vars_os()
+        .map(|(key, _)| key)
+        .filter(|key| key.as_encoded_bytes().starts_with(prefix.as_bytes()))
+        .collect();
+    let guard = EnvGuard::new(keys, |key| {
+        // SAFETY: The environment is only mutated while holding `ENV_LOCK`.
+        unsafe { std::env::remove_var(key) }
+    });
+    drop(lock);
+    guard
+}
+
+/// A guard which changes the current directory and returns to the original one on drop.
+///
+/// The guard holds the process-wide current directory lock until it is dropped.
+///
+/// # Examples
+/// ```
+/// use regd_testing::env::CwdGuard;
+/// use regd_testing::fs::TempDir;
+///
+/// let dir = TempDir::new();
+/// let original = std::env::current_dir().unwrap();
+/// {
+///     let _guard = CwdGuard::new(dir.path());
+///     assert_eq!(
+///         std::env::current_dir().unwrap().canonicalize().unwrap(),
+///         dir.path().canonicalize().unwrap()
+///     );
+/// }
+/// assert_eq!(std::env::current_dir().unwrap(), original);
+/// ```
+#[must_use = "the current directory is restored as soon as the guard is dropped"]
+pub struct CwdGuard {
+    original: PathBuf,
+    _lock: ReentrantLockGuard,
+}
+
+impl CwdGuard {
+    /// Changes the current directory to `path`.
+    ///
+    /// # Panics
+    /// - This method will panic if the current directory cannot be read or changed.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let lock = CWD_LOCK.lock();
+        let original = std::env::current_dir()
+            .unwrap_or_else(|e| panic!("cannot read current directory: {e}"));
+        std::env::set_current_dir(path)
+            .unwrap_or_else(|e| panic!("cannot change directory to {}: {e}", path.display()));
+        Self {
+            original,
+            _lock: lock,
+        }
+    }
+
+    /// Returns the directory which was current before the guard was created.
+    pub fn original(&self) -> &Path {
+        &self.original
+    }
+}
+
+impl Drop for CwdGuard {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.original);
+    }
+}
+
+/// Runs a closure with the current directory changed to the given path.
+///
+/// This is useful for code resolving relative paths, e.g., [`generate_badfile`], which checks
+/// the current directory.
+///
+/// [`generate_badfile`]: crate::rand::generate_badfile
+///
+/// # Parameters
+/// - `path`: The directory to change into, often the path of a [`TempDir`].
+/// - `f`: The closure to run.
+///
+/// [`TempDir`]: crate::fs::TempDir
+///
+/// # Returns
+/// - The value returned by `f`. The original directory is restored even if `f` panics.
+///
+/// # Examples
+/// ```
+/// use regd_testing::env;
+/// use regd_testing::fs::TempDir;
+///
+/// let dir = TempDir::new();
+/// let name = env::with_cwd(dir.path(), || {
+///     std::fs::write("relative.txt", "hello").unwrap();
+///     regd_testing::rand::generate_badfile(12)
+/// });
+/// assert!(dir.path().join("relative.txt").exists());
+/// assert!(!dir.path().join(name).exists());
+/// ```
+///
+/// # Panics
+/// - This function will panic if the current directory cannot be read or changed.
+pub fn with_cwd<T>(path: impl AsRef<Path>, f: impl FnOnce() -> T) -> T {
+    let _guard = CwdGuard::new(path);
+    f()
+}
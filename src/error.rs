@@ -0,0 +1,63 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains the error type of the non-panicking utilities.
+
+use std::time::Duration;
+use std::{error, fmt, result};
+
+/// The error type returned by the non-panicking `try_*` variants of the utilities.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The range to sample from is empty.
+    EmptyRange,
+    /// The length of the file name to generate is zero.
+    EmptyFileName,
+    /// A condition was not met before the timeout elapsed.
+    Timeout {
+        /// A description of the awaited condition.
+        condition: String,
+        /// The time spent waiting.
+        elapsed: Duration,
+        /// The number of times the condition was checked.
+        attempts: u32,
+        /// A description of the last observed state.
+        last_observed: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyRange => write!(f, "cannot sample empty range"),
+            Self::EmptyFileName => write!(f, "cannot sample empty file name"),
+            Self::Timeout {
+                condition,
+                elapsed,
+                attempts,
+                last_observed,
+            } => write!(
+                f,
+                "timed out after {elapsed:?} ({attempts} attempts) waiting for {condition}; \
+                 last observed: {last_observed}"
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// A specialized `Result` type whose error type defaults to [`Error`].
+pub type Result<T, E = Error> = result::Result<T, E>;
@@ -0,0 +1,172 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of fixture file loading.
+//!
+//! Fixtures are resolved relative to the `tests/fixtures` directory of the package under test,
+//! i.e., `$CARGO_MANIFEST_DIR/tests/fixtures`, where `CARGO_MANIFEST_DIR` is the environment
+//! variable set by Cargo when running tests.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// The directory of fixtures relative to the manifest directory.
+const FIXTURES_DIR: &str = "tests/fixtures";
+
+/// Returns the fixtures directory of the package under test.
+///
+/// # Panics
+/// - This function will panic if `CARGO_MANIFEST_DIR` is not set, i.e., when not run by Cargo.
+pub fn dir() -> PathBuf {
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")
+        .unwrap_or_else(|| panic!("cannot locate fixtures: CARGO_MANIFEST_DIR is not set"));
+    Path::new(&manifest_dir).join(FIXTURES_DIR)
+}
+
+/// Collects the paths of every file under `dir`, relative to `base`.
+fn collect(base: &Path, dir: &Path, fixtures: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect(base, &path, fixtures);
+        } else if let Ok(relative) = path.strip_prefix(base) {
+            fixtures.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+}
+
+/// Returns the names of every available fixture, sorted alphabetically.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing::fixture;
+///
+/// for name in fixture::available() {
+///     println!("{name}");
+/// }
+/// ```
+pub fn available() -> Vec<String> {
+    let dir = dir();
+    let mut fixtures = Vec::new();
+    collect(&dir, &dir, &mut fixtures);
+    fixtures.sort();
+    fixtures
+}
+
+/// Resolves the path of the fixture of the given name.
+///
+/// # Parameters
+/// - `name`: The path of the fixture relative to `tests/fixtures`, e.g., `users/alice.json`.
+///
+/// # Returns
+/// - The absolute path of the fixture.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing::fixture;
+///
+/// let path = fixture::path("config.toml");
+/// assert!(path.ends_with("tests/fixtures/config.toml"));
+/// ```
+///
+/// # Panics
+/// - This function will panic if the fixture does not exist. The panic message lists every
+///   available fixture.
+pub fn path(name: &str) -> PathBuf {
+    let path = dir().join(name);
+    if !path.is_file() {
+        let available = available();
+        let available = if available.is_empty() {
+            "none".to_string()
+        } else {
+            available.join(", ")
+        };
+        panic!(
+            "cannot find fixture `{name}` in {}; available fixtures: {available}",
+            dir().display()
+        );
+    }
+    path
+}
+
+/// Reads the fixture of the given name into a string.
+///
+/// # Parameters
+/// - `name`: The path of the fixture relative to `tests/fixtures`.
+///
+/// # Returns
+/// - The contents of the fixture.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing::fixture;
+///
+/// let contents = fixture::read("greeting.txt");
+/// assert!(!contents.is_empty());
+/// ```
+///
+/// # Panics
+/// - This function will panic if the fixture does not exist or is not valid UTF-8.
+pub fn read(name: &str) -> String {
+    let path = path(name);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("cannot read fixture `{name}`: {e}"))
+}
+
+/// Reads the fixture of the given name into a byte vector.
+///
+/// # Parameters
+/// - `name`: The path of the fixture relative to `tests/fixtures`.
+///
+/// # Returns
+/// - The contents of the fixture.
+///
+/// # Panics
+/// - This function will panic if the fixture does not exist or cannot be read.
+pub fn read_bytes(name: &str) -> Vec<u8> {
+    let path = path(name);
+    fs::read(&path).unwrap_or_else(|e| panic!("cannot read fixture `{name}`: {e}"))
+}
+
+/// Reads and deserializes the JSON fixture of the given name.
+///
+/// # Parameters
+/// - `name`: The path of the fixture relative to `tests/fixtures`.
+///
+/// # Returns
+/// - The deserialized value.
+///
+/// # Examples
+/// ```no_run
+/// # #[cfg(feature = "json")]
+/// # {
+/// use regd_testing::fixture;
+///
+/// let user: serde_json::Value = fixture::read_json("users/alice.json");
+/// assert_eq!(user["name"], "Alice");
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the fixture does not exist or cannot be deserialized into `T`.
+#[cfg(feature = "json")]
+pub fn read_json<T>(name: &str) -> T
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_str(&read(name))
+        .unwrap_or_else(|e| panic!("cannot deserialize fixture `{name}`: {e}"))
+}
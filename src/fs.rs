@@ -0,0 +1,1013 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of filesystem fixtures.
+
+use std::collections::HashSet;
+use std::io::{BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::mem::{self, ManuallyDrop};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fmt, fs, io};
+
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+use crate::error::{Error, Result};
+use crate::poll;
+use crate::rand::{self, BadFileOpts, ReservedPath};
+
+mod config;
+#[cfg(feature = "mmap")]
+mod mmap;
+
+pub use self::config::{
+    ConfigFile, ConfigSpec, ConfigValue, random_config_toml, random_config_yaml,
+};
+#[cfg(feature = "mmap")]
+pub use self::mmap::MmapFixture;
+
+/// The prefix of the names of the directories and files created by [`TempDir`] and [`TempFile`].
+const TEMP_PREFIX: &str = "regd-testing-";
+
+/// A uniquely-named temporary directory which is removed, with all its contents, on drop.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::TempDir;
+///
+/// let dir = TempDir::new();
+/// let file = dir.create_file("config/app.toml", "name = \"app\"");
+/// assert_eq!(std::fs::read_to_string(&file).unwrap(), "name = \"app\"");
+///
+/// let path = dir.path().to_path_buf();
+/// drop(dir);
+/// assert!(!path.exists());
+/// ```
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates a new temporary directory in the system's temporary directory.
+    ///
+    /// # Panics
+    /// - This method will panic if the directory cannot be created.
+    pub fn new() -> Self {
+        Self::try_new().unwrap_or_else(|e| panic!("cannot create temporary directory: {e}"))
+    }
+
+    /// Creates a new temporary directory in the system's temporary directory without panicking.
+    pub fn try_new() -> io::Result<Self> {
+        Self::try_new_in(env::temp_dir())
+    }
+
+    /// Creates a new temporary directory under the given directory.
+    ///
+    /// The name of the directory is a random alphanumeric string, and the directory is created
+    /// atomically, so that two fixtures never share the same directory.
+    pub fn try_new_in(parent: impl AsRef<Path>) -> io::Result<Self> {
+        let opts = BadFileOpts {
+            prefix: TEMP_PREFIX.to_string(),
+            ..Default::default()
+        };
+        loop {
+            let path = rand::generate_badfile_in(parent.as_ref(), &opts);
+            match fs::create_dir(&path) {
+                Ok(()) => return Ok(Self { path }),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Returns the path of the temporary directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Creates a file at the given path relative to the temporary directory.
+    ///
+    /// Missing parent directories are created, and an existing file is overwritten.
+    ///
+    /// # Parameters
+    /// - `name`: The path of the file relative to the temporary directory.
+    /// - `contents`: The contents of the file.
+    ///
+    /// # Returns
+    /// - The absolute path of the created file.
+    ///
+    /// # Panics
+    /// - This method will panic if the file cannot be created or written.
+    pub fn create_file(&self, name: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.path.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .unwrap_or_else(|e| panic!("cannot create {}: {e}", parent.display()));
+        }
+        fs::write(&path, contents)
+            .unwrap_or_else(|e| panic!("cannot write {}: {e}", path.display()));
+        path
+    }
+
+    /// Consumes the fixture without removing the directory and returns its path.
+    ///
+    /// This is an escape hatch for post-mortem debugging, e.g., to inspect the files left behind
+    /// by a failing test.
+    pub fn persist(self) -> PathBuf {
+        let mut this = ManuallyDrop::new(self);
+        mem::take(&mut this.path)
+    }
+}
+
+impl Default for TempDir {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// A uniquely-named temporary file which is deleted on drop.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::TempFile;
+///
+/// let file = TempFile::with_random_bytes(1024);
+/// assert_eq!(std::fs::metadata(file.path()).unwrap().len(), 1024);
+///
+/// let path = file.path().to_path_buf();
+/// drop(file);
+/// assert!(!path.exists());
+/// ```
+#[derive(Debug)]
+pub struct TempFile {
+    reserved: ReservedPath,
+}
+
+impl TempFile {
+    /// Creates a new temporary file with the given contents in the system's temporary directory.
+    ///
+    /// # Panics
+    /// - This method will panic if the file cannot be created or written.
+    pub fn with_contents(contents: impl AsRef<[u8]>) -> Self {
+        Self::with_suffix("", contents)
+    }
+
+    /// Creates a new temporary file whose name ends with the given suffix, e.g., an extension.
+    pub(crate) fn with_suffix(suffix: &str, contents: impl AsRef<[u8]>) -> Self {
+        let opts = BadFileOpts {
+            prefix: TEMP_PREFIX.to_string(),
+            suffix: suffix.to_string(),
+            ..Default::default()
+        };
+        let reserved = rand::reserve_badfile_in(&env::temp_dir(), &opts);
+        fs::write(reserved.path(), contents)
+            .unwrap_or_else(|e| panic!("cannot write {}: {e}", reserved.path().display()));
+        Self { reserved }
+    }
+
+    /// Creates a new temporary file filled with the specified number of random bytes.
+    ///
+    /// # Panics
+    /// - This method will panic if the file cannot be created or written.
+    pub fn with_random_bytes(len: usize) -> Self {
+        Self::with_contents(rand::generate_bytes(len))
+    }
+
+    /// Creates a new temporary file filled with lines of random alphanumeric text.
+    ///
+    /// Every line, including the last one, is terminated by `\n`.
+    ///
+    /// # Examples
+    /// ```
+    /// use regd_testing::fs::TempFile;
+    ///
+    /// let file = TempFile::with_random_text(3, 16);
+    /// let text = std::fs::read_to_string(file.path()).unwrap();
+    /// assert_eq!(text.lines().count(), 3);
+    /// assert!(text.lines().all(|line| line.len() == 16));
+    /// ```
+    ///
+    /// # Panics
+    /// - This method will panic if the file cannot be created or written.
+    pub fn with_random_text(lines: usize, line_len: usize) -> Self {
+        let mut text = String::with_capacity(lines * (line_len + 1));
+        for _ in 0..lines {
+            text.push_str(&rand::generate_alphanumeric(line_len));
+            text.push('\n');
+        }
+        Self::with_contents(text)
+    }
+
+    /// Returns the path of the temporary file.
+    pub fn path(&self) -> &Path {
+        self.reserved.path()
+    }
+
+    /// Consumes the fixture without deleting the file and returns its path.
+    ///
+    /// This is an escape hatch for post-mortem debugging, e.g., to inspect the file left behind
+    /// by a failing test.
+    pub fn persist(self) -> PathBuf {
+        self.reserved.into_path()
+    }
+}
+
+impl AsRef<Path> for TempFile {
+    fn as_ref(&self) -> &Path {
+        self.path()
+    }
+}
+
+/// The shape of a directory tree built by [`populate_tree`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempDir, TreeSpec};
+///
+/// let dir = TempDir::new();
+/// let spec = TreeSpec {
+///     depth: 1,
+///     files_per_dir: 2,
+///     dirs_per_dir: 3,
+///     file_size_range: 16..32,
+/// };
+/// let manifest = fs::populate_tree(dir.path(), &spec);
+/// assert_eq!(manifest.dirs.len(), 3);
+/// assert_eq!(manifest.files.len(), 2 + 3 * 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeSpec {
+    /// The number of levels of subdirectories below the root. Defaults to 2.
+    pub depth: usize,
+    /// The number of files created in every directory, including the root. Defaults to 3.
+    pub files_per_dir: usize,
+    /// The number of subdirectories created in every directory above the deepest level.
+    /// Defaults to 2.
+    pub dirs_per_dir: usize,
+    /// The range of the sizes of the files, in bytes. Defaults to `0..1024`.
+    pub file_size_range: Range<usize>,
+}
+
+impl Default for TreeSpec {
+    fn default() -> Self {
+        Self {
+            depth: 2,
+            files_per_dir: 3,
+            dirs_per_dir: 2,
+            file_size_range: 0..1024,
+        }
+    }
+}
+
+/// The paths created by [`populate_tree`], in creation order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TreeManifest {
+    /// The created directories, excluding the root.
+    pub dirs: Vec<PathBuf>,
+    /// The created files.
+    pub files: Vec<PathBuf>,
+}
+
+/// Builds a randomized directory tree under the given root.
+///
+/// The names of the directories and files, as well as the sizes and contents of the files, are
+/// drawn from the thread-local random number generator, so that the same tree is built again
+/// under the same [`set_seed`](crate::rand::set_seed). This is useful for testing recursive
+/// scanners, watchers and synchronization logic.
+///
+/// # Parameters
+/// - `root`: The directory under which to build the tree. It is created if missing.
+/// - `spec`: The shape of the tree.
+///
+/// # Returns
+/// - A [`TreeManifest`] listing every created directory and file.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempDir, TreeSpec};
+///
+/// let dir = TempDir::new();
+/// let manifest = fs::populate_tree(dir.path(), &TreeSpec::default());
+/// assert!(manifest.files.iter().all(|f| f.is_file()));
+/// assert!(manifest.dirs.iter().all(|d| d.is_dir()));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `spec.file_size_range` is empty while files are requested, or if
+///   any directory or file cannot be created.
+pub fn populate_tree(root: impl AsRef<Path>, spec: &TreeSpec) -> TreeManifest {
+    let root = root.as_ref();
+    fs::create_dir_all(root).unwrap_or_else(|e| panic!("cannot create {}: {e}", root.display()));
+    let mut manifest = TreeManifest::default();
+    populate_dir(root, spec.depth, spec, &mut manifest);
+    manifest
+}
+
+/// Populates a single directory and recurses into its subdirectories.
+fn populate_dir(dir: &Path, depth: usize, spec: &TreeSpec, manifest: &mut TreeManifest) {
+    let mut names = HashSet::new();
+    let mut unique_name = |suffix: &str| loop {
+        let name = format!("{}{suffix}", rand::generate_alphanumeric(8));
+        if names.insert(name.to_lowercase()) {
+            return name;
+        }
+    };
+    for _ in 0..spec.files_per_dir {
+        let path = dir.join(unique_name(".bin"));
+        let len = rand::generate_range(spec.file_size_range.clone());
+        fs::write(&path, rand::generate_bytes(len))
+            .unwrap_or_else(|e| panic!("cannot write {}: {e}", path.display()));
+        manifest.files.push(path);
+    }
+    if depth == 0 {
+        return;
+    }
+    let subdirs: Vec<PathBuf> = (0..spec.dirs_per_dir)
+        .map(|_| dir.join(unique_name("")))
+        .collect();
+    for subdir in subdirs {
+        fs::create_dir(&subdir)
+            .unwrap_or_else(|e| panic!("cannot create {}: {e}", subdir.display()));
+        manifest.dirs.push(subdir.clone());
+        populate_dir(&subdir, depth - 1, spec, manifest);
+    }
+}
+
+/// A guard which restores the original permissions of a file or directory on drop.
+///
+/// This guard is created through [`make_readonly`] or, on Unix only, [`make_unreadable`].
+#[derive(Debug)]
+pub struct PermissionsGuard {
+    path: PathBuf,
+    original: fs::Permissions,
+}
+
+impl PermissionsGuard {
+    /// Changes the permissions of `path` with `change`, remembering the original ones.
+    fn new(path: &Path, change: impl FnOnce(&mut fs::Permissions)) -> Self {
+        let original = fs::metadata(path)
+            .unwrap_or_else(|e| panic!("cannot read permissions of {}: {e}", path.display()))
+            .permissions();
+        let mut permissions = original.clone();
+        change(&mut permissions);
+        fs::set_permissions(path, permissions)
+            .unwrap_or_else(|e| panic!("cannot set permissions of {}: {e}", path.display()));
+        Self {
+            path: path.to_path_buf(),
+            original,
+        }
+    }
+
+    /// Returns the path whose permissions have been changed.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for PermissionsGuard {
+    fn drop(&mut self) {
+        let _ = fs::set_permissions(&self.path, self.original.clone());
+    }
+}
+
+/// Makes a file or directory read-only until the returned guard is dropped.
+///
+/// On Unix, every write bit of the mode is cleared; on other platforms, the read-only attribute
+/// is set. This is useful for testing the handling of `EACCES`/`EPERM` errors on writes.
+///
+/// # Parameters
+/// - `path`: The file or directory to make read-only.
+///
+/// # Returns
+/// - A [`PermissionsGuard`] which restores the original permissions when dropped.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempFile};
+///
+/// let file = TempFile::with_random_bytes(16);
+/// let guard = fs::make_readonly(file.path());
+/// assert!(std::fs::metadata(file.path()).unwrap().permissions().readonly());
+///
+/// drop(guard);
+/// assert!(!std::fs::metadata(file.path()).unwrap().permissions().readonly());
+/// ```
+///
+/// # Panics
+/// - This function will panic if the permissions cannot be read or changed.
+///
+/// # Notes
+/// - Privileged users, e.g., `root` on Unix, bypass permission checks, so that writes may still
+///   succeed when tests are run as such a user.
+pub fn make_readonly(path: impl AsRef<Path>) -> PermissionsGuard {
+    PermissionsGuard::new(path.as_ref(), |permissions| {
+        #[cfg(unix)]
+        permissions.set_mode(permissions.mode() & !0o222);
+        #[cfg(not(unix))]
+        permissions.set_readonly(true);
+    })
+}
+
+/// Makes a file or directory unreadable until the returned guard is dropped.
+///
+/// Every read bit of the mode is cleared, so that opening the file for reading or listing the
+/// directory fails with `EACCES`.
+///
+/// This function is only available on Unix. Denying reads on Windows requires editing the
+/// access control list of the file, which this crate does not support, so that tests relying on
+/// unreadable files should be gated with `#[cfg(unix)]`.
+///
+/// # Parameters
+/// - `path`: The file or directory to make unreadable.
+///
+/// # Returns
+/// - A [`PermissionsGuard`] which restores the original permissions when dropped.
+///
+/// # Examples
+/// ```
+/// use std::os::unix::fs::PermissionsExt;
+///
+/// use regd_testing::fs::{self, TempFile};
+///
+/// let file = TempFile::with_random_bytes(16);
+/// let guard = fs::make_unreadable(file.path());
+/// let mode = std::fs::metadata(file.path()).unwrap().permissions().mode();
+/// assert_eq!(mode & 0o444, 0);
+/// # drop(guard);
+/// ```
+///
+/// # Panics
+/// - This function will panic if the permissions cannot be read or changed.
+///
+/// # Notes
+/// - Privileged users, e.g., `root`, bypass permission checks, so that reads may still succeed
+///   when tests are run as such a user.
+#[cfg(unix)]
+pub fn make_unreadable(path: impl AsRef<Path>) -> PermissionsGuard {
+    PermissionsGuard::new(path.as_ref(), |permissions| {
+        permissions.set_mode(permissions.mode() & !0o444);
+    })
+}
+
+/// Creates a symbolic link at `link` pointing to `target`, which may not exist.
+#[cfg(any(unix, windows))]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    return std::os::unix::fs::symlink(target, link);
+    #[cfg(windows)]
+    return if link
+        .parent()
+        .map_or(target.to_path_buf(), |p| p.join(target))
+        .is_dir()
+    {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    };
+}
+
+/// Creates a symbolic link at `link` pointing to `target`.
+///
+/// `target` is stored as given, so that a relative target is resolved relative to the directory
+/// containing `link`. On Windows, a directory link is created if `target` is an existing
+/// directory and a file link otherwise; creating symbolic links may require the corresponding
+/// privilege or developer mode.
+///
+/// # Parameters
+/// - `target`: The path the link points to. It does not need to exist.
+/// - `link`: The path of the link to create.
+///
+/// # Returns
+/// - The path of the created link.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempDir};
+///
+/// let dir = TempDir::new();
+/// let target = dir.create_file("target.txt", "hello");
+/// let link = fs::symlink_fixture(&target, dir.path().join("link.txt"));
+/// assert_eq!(std::fs::read_to_string(&link).unwrap(), "hello");
+/// assert!(link.is_symlink());
+/// ```
+///
+/// # Panics
+/// - This function will panic if the link cannot be created.
+#[cfg(any(unix, windows))]
+pub fn symlink_fixture(target: impl AsRef<Path>, link: impl AsRef<Path>) -> PathBuf {
+    let (target, link) = (target.as_ref(), link.as_ref());
+    symlink(target, link).unwrap_or_else(|e| {
+        panic!(
+            "cannot create symlink {} -> {}: {e}",
+            link.display(),
+            target.display()
+        )
+    });
+    link.to_path_buf()
+}
+
+/// Creates a hard link at `link` to the existing file `original`.
+///
+/// # Parameters
+/// - `original`: The existing file to link to.
+/// - `link`: The path of the link to create.
+///
+/// # Returns
+/// - The path of the created link.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempDir};
+///
+/// let dir = TempDir::new();
+/// let original = dir.create_file("original.txt", "hello");
+/// let link = fs::hardlink_fixture(&original, dir.path().join("link.txt"));
+/// std::fs::write(&original, "world").unwrap();
+/// assert_eq!(std::fs::read_to_string(&link).unwrap(), "world");
+/// ```
+///
+/// # Panics
+/// - This function will panic if the link cannot be created.
+pub fn hardlink_fixture(original: impl AsRef<Path>, link: impl AsRef<Path>) -> PathBuf {
+    let (original, link) = (original.as_ref(), link.as_ref());
+    fs::hard_link(original, link).unwrap_or_else(|e| {
+        panic!(
+            "cannot create hard link {} -> {}: {e}",
+            link.display(),
+            original.display()
+        )
+    });
+    link.to_path_buf()
+}
+
+/// Creates a randomly named, broken symbolic link in the given directory.
+///
+/// The link points to a randomly named sibling which does not exist.
+///
+/// # Parameters
+/// - `dir`: The directory in which to create the link.
+///
+/// # Returns
+/// - The path of the created link.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempDir};
+///
+/// let dir = TempDir::new();
+/// let link = fs::create_broken_symlink(dir.path());
+/// assert!(link.is_symlink());
+/// assert!(!link.exists());
+/// ```
+///
+/// # Panics
+/// - This function will panic if the link cannot be created.
+#[cfg(any(unix, windows))]
+pub fn create_broken_symlink(dir: impl AsRef<Path>) -> PathBuf {
+    let dir = dir.as_ref();
+    let opts = BadFileOpts::default();
+    let target = rand::generate_badfile_in(dir, &opts);
+    let link = rand::generate_badfile_in(dir, &opts);
+    let target = target.file_name().map(PathBuf::from).unwrap_or(target);
+    symlink_fixture(target, link)
+}
+
+/// Creates a pair of randomly named symbolic links pointing to each other in the given directory.
+///
+/// Resolving either link fails with a "too many levels of symbolic links" error.
+///
+/// # Parameters
+/// - `dir`: The directory in which to create the links.
+///
+/// # Returns
+/// - The paths of both links.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempDir};
+///
+/// let dir = TempDir::new();
+/// let [a, b] = fs::create_symlink_cycle(dir.path());
+/// assert!(a.is_symlink() && b.is_symlink());
+/// assert!(std::fs::metadata(&a).is_err());
+/// ```
+///
+/// # Panics
+/// - This function will panic if the links cannot be created.
+#[cfg(any(unix, windows))]
+pub fn create_symlink_cycle(dir: impl AsRef<Path>) -> [PathBuf; 2] {
+    let dir = dir.as_ref();
+    let opts = BadFileOpts::default();
+    let a = rand::generate_badfile_in(dir, &opts);
+    let b = loop {
+        let b = rand::generate_badfile_in(dir, &opts);
+        if b != a {
+            break b;
+        }
+    };
+    let name = |path: &Path| path.file_name().map(PathBuf::from).unwrap_or_default();
+    [symlink_fixture(name(&b), &a), symlink_fixture(name(&a), &b)]
+}
+
+/// Creates a randomly named symbolic link to the given directory inside of it.
+///
+/// Traversals which follow symbolic links loop forever on such a layout unless they detect
+/// already visited directories.
+///
+/// # Parameters
+/// - `dir`: The directory in which to create the link, and to which the link points.
+///
+/// # Returns
+/// - The path of the created link.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempDir};
+///
+/// let dir = TempDir::new();
+/// let link = fs::create_symlink_loop(dir.path());
+/// assert!(link.join(link.file_name().unwrap()).join("..").exists());
+/// ```
+///
+/// # Panics
+/// - This function will panic if the link cannot be created.
+#[cfg(any(unix, windows))]
+pub fn create_symlink_loop(dir: impl AsRef<Path>) -> PathBuf {
+    let dir = dir.as_ref();
+    let link = rand::generate_badfile_in(dir, &BadFileOpts::default());
+    symlink_fixture(".", link)
+}
+
+/// Creates a sparse file of the given logical size.
+///
+/// The file is created, or truncated if it exists, and then extended to `logical_size` bytes
+/// without writing any data, so that it reads as zeros and, on filesystems supporting sparse
+/// files, occupies almost no disk space.
+///
+/// # Parameters
+/// - `path`: The path of the file to create.
+/// - `logical_size`: The size of the file, in bytes.
+///
+/// # Returns
+/// - The path of the created file.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempDir};
+///
+/// let dir = TempDir::new();
+/// let file = fs::create_sparse_file(dir.path().join("sparse.img"), 1 << 30);
+/// assert_eq!(std::fs::metadata(&file).unwrap().len(), 1 << 30);
+/// ```
+///
+/// # Panics
+/// - This function will panic if the file cannot be created or extended.
+pub fn create_sparse_file(path: impl AsRef<Path>, logical_size: u64) -> PathBuf {
+    let path = path.as_ref();
+    fs::File::create(path)
+        .and_then(|file| file.set_len(logical_size))
+        .unwrap_or_else(|e| panic!("cannot create {}: {e}", path.display()));
+    path.to_path_buf()
+}
+
+/// Creates a file of the given size filled with random bytes, streamed in chunks.
+///
+/// Only a single chunk is held in memory at a time, so that multi-gigabyte inputs can be
+/// generated efficiently. The bytes are drawn from the thread-local random number generator,
+/// so that the same file is generated again under the same
+/// [`set_seed`](crate::rand::set_seed).
+///
+/// # Parameters
+/// - `path`: The path of the file to create.
+/// - `size`: The size of the file, in bytes.
+/// - `chunk`: The number of bytes generated and written at a time. Must be greater than 0.
+///
+/// # Returns
+/// - The path of the created file.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, TempDir};
+///
+/// let dir = TempDir::new();
+/// let file = fs::create_large_random_file(dir.path().join("random.bin"), 10_000, 4096);
+/// assert_eq!(std::fs::metadata(&file).unwrap().len(), 10_000);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `chunk == 0`, or if the file cannot be created or written.
+pub fn create_large_random_file(path: impl AsRef<Path>, size: u64, chunk: usize) -> PathBuf {
+    assert!(chunk > 0, "cannot write empty chunks");
+    let path = path.as_ref();
+    let write = || -> io::Result<()> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        let mut buffer = vec![0; chunk];
+        let mut remaining = size;
+        while remaining > 0 {
+            let len = usize::try_from(remaining).map_or(chunk, |r| r.min(chunk));
+            rand::fill_bytes(&mut buffer[..len]);
+            writer.write_all(&buffer[..len])?;
+            remaining -= len as u64;
+        }
+        writer.flush()
+    };
+    write().unwrap_or_else(|e| panic!("cannot write {}: {e}", path.display()));
+    path.to_path_buf()
+}
+
+/// The kind of damage inflicted by [`corrupt`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CorruptionSpec {
+    /// Flips the given number of randomly chosen bits.
+    FlipBits(usize),
+    /// Truncates the file at a random offset strictly before its end.
+    Truncate,
+    /// Overwrites a randomly placed region of at most the given length with random bytes, each
+    /// of which differs from the original one. A length of `0` leaves the file untouched.
+    Overwrite(usize),
+    /// Randomly picks one of the other kinds, flipping a single bit or overwriting up to 16 bytes.
+    Any,
+}
+
+/// A description of the damage inflicted by [`corrupt`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Corruption {
+    /// The bits at the given `(byte offset, bit index)` positions were flipped.
+    FlippedBits(Vec<(u64, u8)>),
+    /// The file was truncated from `from` to `to` bytes.
+    Truncated {
+        /// The original size of the file.
+        from: u64,
+        /// The new size of the file.
+        to: u64,
+    },
+    /// The region of `len` bytes starting at `offset` was overwritten with random bytes, each of
+    /// which differs from the original one.
+    Overwritten {
+        /// The offset of the first overwritten byte.
+        offset: u64,
+        /// The number of overwritten bytes.
+        len: usize,
+    },
+}
+
+impl fmt::Display for Corruption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FlippedBits(bits) => {
+                write!(f, "flipped bits at")?;
+                for (offset, bit) in bits {
+                    write!(f, " {offset}:{bit}")?;
+                }
+                Ok(())
+            }
+            Self::Truncated { from, to } => write!(f, "truncated from {from} to {to} bytes"),
+            Self::Overwritten { offset, len } => {
+                write!(f, "overwrote {len} bytes at offset {offset}")
+            }
+        }
+    }
+}
+
+/// Damages a file in place according to the given specification.
+///
+/// This is useful for testing checksum verification and recovery code paths. The location of
+/// the damage is drawn from the thread-local random number generator and reported back, so that
+/// a failing test can explain exactly what was damaged.
+///
+/// # Parameters
+/// - `path`: The file to damage. Must not be empty.
+/// - `spec`: The kind of damage to inflict.
+///
+/// # Returns
+/// - A [`Corruption`] describing the inflicted damage.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, CorruptionSpec, TempFile};
+///
+/// let file = TempFile::with_contents([0u8; 64]);
+/// let damage = fs::corrupt(file.path(), CorruptionSpec::FlipBits(3));
+/// println!("{damage}");
+///
+/// let contents = std::fs::read(file.path()).unwrap();
+/// assert_ne!(contents, [0u8; 64]);
+///
+/// let file = TempFile::with_contents([0u8; 64]);
+/// let damage = fs::corrupt(file.path(), CorruptionSpec::Overwrite(8));
+/// let fs::Corruption::Overwritten { offset, len } = damage else {
+///     unreachable!();
+/// };
+/// let contents = std::fs::read(file.path()).unwrap();
+/// let region = offset as usize..offset as usize + len;
+/// assert!((1..=8).contains(&len));
+/// assert!(contents[region.clone()].iter().all(|&b| b != 0));
+/// assert!(contents.iter().enumerate().all(|(i, &b)| region.contains(&i) || b == 0));
+///
+/// let damage = fs::corrupt(file.path(), CorruptionSpec::Overwrite(0));
+/// assert!(matches!(damage, fs::Corruption::Overwritten { len: 0, .. }));
+/// assert_eq!(std::fs::read(file.path()).unwrap(), contents);
+/// ```
+///
+/// # Panics
+/// - This function will panic if the file is empty, or if it cannot be read or written.
+pub fn corrupt(path: impl AsRef<Path>, spec: CorruptionSpec) -> Corruption {
+    let path = path.as_ref();
+    let damage = || -> io::Result<Corruption> {
+        let mut file = fs::OpenOptions::new().read(true).write(true).open(path)?;
+        let size = file.metadata()?.len();
+        assert!(size > 0, "cannot corrupt empty file {}", path.display());
+        let spec = match spec {
+            CorruptionSpec::Any => match rand::generate_range(0..3) {
+                0 => CorruptionSpec::FlipBits(1),
+                1 => CorruptionSpec::Truncate,
+                _ => CorruptionSpec::Overwrite(16),
+            },
+            spec => spec,
+        };
+        match spec {
+            CorruptionSpec::FlipBits(count) => {
+                let mut bits = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let offset = rand::generate_range(0..size);
+                    let bit = rand::generate_range(0..8);
+                    let mut byte = [0];
+                    file.seek(SeekFrom::Start(offset))?;
+                    file.read_exact(&mut byte)?;
+                    byte[0] ^= 1 << bit;
+                    file.seek(SeekFrom::Start(offset))?;
+                    file.write_all(&byte)?;
+                    bits.push((offset, bit));
+                }
+                Ok(Corruption::FlippedBits(bits))
+            }
+            CorruptionSpec::Truncate => {
+                let to = rand::generate_range(0..size);
+                file.set_len(to)?;
+                Ok(Corruption::Truncated { from: size, to })
+            }
+            CorruptionSpec::Overwrite(max_len) => {
+                let offset = rand::generate_range(0..size);
+                let available = usize::try_from(size - offset).unwrap_or(usize::MAX);
+                let len = match max_len.min(available) {
+                    0 => 0,
+                    max_len => rand::generate_range(1..=max_len),
+                };
+                let mut region = vec![0; len];
+                file.seek(SeekFrom::Start(offset))?;
+                file.read_exact(&mut region)?;
+                for byte in &mut region {
+                    *byte ^= rand::generate_range(1..=u8::MAX);
+                }
+                file.seek(SeekFrom::Start(offset))?;
+                file.write_all(&region)?;
+                Ok(Corruption::Overwritten { offset, len })
+            }
+            CorruptionSpec::Any => unreachable!("resolved above"),
+        }
+    };
+    damage().unwrap_or_else(|e| panic!("cannot corrupt {}: {e}", path.display()))
+}
+
+/// Describes the current state of a path for timeout errors.
+fn describe(path: &Path) -> String {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_dir() => format!("{} is a directory", path.display()),
+        Ok(metadata) => format!("{} has {} bytes", path.display(), metadata.len()),
+        Err(e) => format!("{} is not accessible: {e}", path.display()),
+    }
+}
+
+/// Polls `predicate` on `path` until it holds, reporting `condition` on timeout.
+fn wait_until(
+    path: &Path,
+    condition: String,
+    mut predicate: impl FnMut(&Path) -> bool,
+    timeout: Duration,
+) -> Result<()> {
+    poll::poll(timeout, || predicate(path)).map_err(|(elapsed, attempts)| Error::Timeout {
+        condition,
+        elapsed,
+        attempts,
+        last_observed: describe(path),
+    })
+}
+
+/// Waits until a predicate on a path holds, polling with exponential backoff.
+///
+/// This is useful for tests which spawn background writers, which would otherwise sleep for a
+/// fixed, and often either too long or too short, duration.
+///
+/// # Parameters
+/// - `path`: The path to watch.
+/// - `predicate`: The condition to wait for, called with `path`.
+/// - `timeout`: The maximum time to wait.
+///
+/// # Returns
+/// - `Ok(())` as soon as `predicate` returns `true`, or [`Error::Timeout`] describing the last
+///   observed state of `path` if `timeout` elapses first.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::fs::{self, TempDir};
+///
+/// let dir = TempDir::new();
+/// let path = dir.path().join("out.log");
+/// let writer = {
+///     let path = path.clone();
+///     std::thread::spawn(move || std::fs::write(path, "done"))
+/// };
+/// let done = |p: &std::path::Path| std::fs::read_to_string(p).is_ok_and(|s| s == "done");
+/// assert!(fs::wait_for(&path, done, Duration::from_secs(5)).is_ok());
+/// writer.join().unwrap().unwrap();
+/// ```
+pub fn wait_for(
+    path: impl AsRef<Path>,
+    predicate: impl FnMut(&Path) -> bool,
+    timeout: Duration,
+) -> Result<()> {
+    let path = path.as_ref();
+    let condition = format!("a condition on {}", path.display());
+    wait_until(path, condition, predicate, timeout)
+}
+
+/// Waits until a path exists.
+///
+/// # Parameters
+/// - `path`: The path to watch.
+/// - `timeout`: The maximum time to wait.
+///
+/// # Returns
+/// - `Ok(())` as soon as `path` exists, or [`Error::Timeout`] if `timeout` elapses first.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::fs::{self, TempDir};
+///
+/// let dir = TempDir::new();
+/// let missing = dir.path().join("missing");
+/// let err = fs::wait_for_exists(&missing, Duration::from_millis(20)).unwrap_err();
+/// assert!(err.to_string().contains("timed out"));
+/// ```
+pub fn wait_for_exists(path: impl AsRef<Path>, timeout: Duration) -> Result<()> {
+    let path = path.as_ref();
+    let condition = format!("{} to exist", path.display());
+    wait_until(path, condition, Path::exists, timeout)
+}
+
+/// Waits until a file has at least the given size.
+///
+/// # Parameters
+/// - `path`: The file to watch.
+/// - `size`: The minimum size, in bytes.
+/// - `timeout`: The maximum time to wait.
+///
+/// # Returns
+/// - `Ok(())` as soon as `path` has at least `size` bytes, or [`Error::Timeout`] if `timeout`
+///   elapses first.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::fs::{self, TempFile};
+///
+/// let file = TempFile::with_random_bytes(128);
+/// assert!(fs::wait_for_size_at_least(file.path(), 64, Duration::from_secs(1)).is_ok());
+/// ```
+pub fn wait_for_size_at_least(path: impl AsRef<Path>, size: u64, timeout: Duration) -> Result<()> {
+    let path = path.as_ref();
+    let has_size = |p: &Path| fs::metadata(p).is_ok_and(|m| m.len() >= size);
+    let condition = format!("{} to have at least {size} bytes", path.display());
+    wait_until(path, condition, has_size, timeout)
+}
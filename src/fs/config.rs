@@ -0,0 +1,430 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random configuration file fixtures.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::path::Path;
+
+use super::TempFile;
+use crate::rand::generator::Gen;
+
+/// A value of a configuration file, which is representable in both TOML and YAML.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigValue {
+    /// A boolean.
+    Bool(bool),
+    /// A signed integer.
+    Integer(i64),
+    /// A floating-point number.
+    Float(f64),
+    /// A string.
+    String(String),
+    /// An array of values.
+    Array(Vec<ConfigValue>),
+    /// A table of values indexed by their keys.
+    Table(BTreeMap<String, ConfigValue>),
+}
+
+macro_rules! impl_from_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for ConfigValue {
+                fn from(value: $ty) -> Self {
+                    Self::Integer(i64::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, u8, u16, u32);
+
+impl From<bool> for ConfigValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<f32> for ConfigValue {
+    fn from(value: f32) -> Self {
+        Self::Float(f64::from(value))
+    }
+}
+
+impl From<f64> for ConfigValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<String> for ConfigValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for ConfigValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl<T: Into<ConfigValue>> From<Vec<T>> for ConfigValue {
+    fn from(values: Vec<T>) -> Self {
+        Self::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// The schema of a random configuration file, mapping dotted keys to generators.
+///
+/// The segments of a dotted key, e.g., `server.port`, name nested tables.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::ConfigSpec;
+/// use regd_testing::rand::generator as g;
+///
+/// let spec = ConfigSpec::new()
+///     .key("name", g::alphanumeric(8))
+///     .key("server.port", g::range(1024..=65535u16))
+///     .key("server.tls", g::bool(0.5));
+/// ```
+#[derive(Default)]
+pub struct ConfigSpec {
+    keys: Vec<(String, Box<dyn Fn() -> ConfigValue>)>,
+}
+
+impl fmt::Debug for ConfigSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.keys.iter().map(|(key, _)| key))
+            .finish()
+    }
+}
+
+impl ConfigSpec {
+    /// Creates an empty schema.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key whose values are drawn from the given generator.
+    pub fn key<T, G>(mut self, key: &str, generator: G) -> Self
+    where
+        T: Into<ConfigValue>,
+        G: Gen<T> + 'static,
+    {
+        self.keys.push((
+            key.to_string(),
+            Box::new(move || generator.generate().into()),
+        ));
+        self
+    }
+
+    /// Draws a value for every key, nesting dotted keys into tables.
+    fn generate(&self) -> BTreeMap<String, ConfigValue> {
+        let mut root = BTreeMap::new();
+        for (key, generator) in &self.keys {
+            let mut table = &mut root;
+            let mut segments: Vec<&str> = key.split('.').collect();
+            let last = segments.pop().unwrap_or_default();
+            for segment in segments {
+                let entry = table
+                    .entry(segment.to_string())
+                    .or_insert_with(|| ConfigValue::Table(BTreeMap::new()));
+                table = match entry {
+                    ConfigValue::Table(table) => table,
+                    _ => panic!("cannot nest key {key:?} under a non-table value"),
+                };
+            }
+            table.insert(last.to_string(), generator());
+        }
+        root
+    }
+}
+
+/// A configuration file written by [`random_config_toml`] or [`random_config_yaml`], which is
+/// deleted on drop.
+#[derive(Debug)]
+pub struct ConfigFile {
+    file: TempFile,
+    values: BTreeMap<String, ConfigValue>,
+}
+
+impl ConfigFile {
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Returns the values written to the file, as a parser is expected to read them.
+    pub fn values(&self) -> &BTreeMap<String, ConfigValue> {
+        &self.values
+    }
+
+    /// Returns the value written for the given dotted key.
+    pub fn get(&self, key: &str) -> Option<&ConfigValue> {
+        let mut segments = key.split('.');
+        let mut value = self.values.get(segments.next()?)?;
+        for segment in segments {
+            match value {
+                ConfigValue::Table(table) => value = table.get(segment)?,
+                _ => return None,
+            }
+        }
+        Some(value)
+    }
+}
+
+impl AsRef<Path> for ConfigFile {
+    fn as_ref(&self) -> &Path {
+        self.path()
+    }
+}
+
+/// Returns `true` if the key may be written without quotes.
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Renders a string as a double-quoted string, which both TOML and YAML accept, escaping the
+/// characters which YAML does not allow to be printed.
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() || matches!(c, '\u{FEFF}' | '\u{FFFE}' | '\u{FFFF}') => {
+                let _ = write!(quoted, "\\u{:04X}", u32::from(c));
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Renders a finite float with a fractional part and a signed exponent, if any, which both TOML
+/// and YAML 1.1 parsers recognize as a float.
+fn float(value: f64, nan: &str, inf: &str) -> String {
+    if value.is_nan() {
+        return nan.to_string();
+    }
+    if value.is_infinite() {
+        return format!("{}{inf}", if value < 0.0 { "-" } else { "" });
+    }
+    let rendered = format!("{value:?}");
+    let (mantissa, exponent) = match rendered.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (rendered.as_str(), None),
+    };
+    let mut result = mantissa.to_string();
+    if !result.contains('.') {
+        result.push_str(".0");
+    }
+    if let Some(exponent) = exponent {
+        let sign = if exponent.starts_with('-') { "" } else { "+" };
+        let _ = write!(result, "e{sign}{exponent}");
+    }
+    result
+}
+
+/// Renders a TOML key.
+fn toml_key(key: &str) -> String {
+    if is_bare_key(key) {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+/// Renders a TOML value on a single line.
+fn toml_inline(value: &ConfigValue) -> String {
+    match value {
+        ConfigValue::Bool(b) => b.to_string(),
+        ConfigValue::Integer(i) => i.to_string(),
+        ConfigValue::Float(f) => float(*f, "nan", "inf"),
+        ConfigValue::String(s) => quote(s),
+        ConfigValue::Array(values) => {
+            let values: Vec<String> = values.iter().map(toml_inline).collect();
+            format!("[{}]", values.join(", "))
+        }
+        ConfigValue::Table(table) => {
+            let entries: Vec<String> = table
+                .iter()
+                .map(|(key, value)| format!("{} = {}", toml_key(key), toml_inline(value)))
+                .collect();
+            if entries.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", entries.join(", "))
+            }
+        }
+    }
+}
+
+/// Renders a TOML table, writing its values before its subtables.
+fn write_toml(out: &mut String, path: &[String], table: &BTreeMap<String, ConfigValue>) {
+    for (key, value) in table {
+        if !matches!(value, ConfigValue::Table(_)) {
+            let _ = writeln!(out, "{} = {}", toml_key(key), toml_inline(value));
+        }
+    }
+    for (key, value) in table {
+        if let ConfigValue::Table(subtable) = value {
+            let mut path = path.to_vec();
+            path.push(toml_key(key));
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            let _ = writeln!(out, "[{}]", path.join("."));
+            write_toml(out, &path, subtable);
+        }
+    }
+}
+
+/// Renders a YAML key, quoting those which would be read as another scalar.
+fn yaml_key(key: &str) -> String {
+    const RESERVED: &[&str] = &["true", "false", "yes", "no", "on", "off", "null", "y", "n"];
+    let plain = is_bare_key(key)
+        && !key.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+        && !RESERVED.contains(&key.to_ascii_lowercase().as_str());
+    if plain { key.to_string() } else { quote(key) }
+}
+
+/// Renders a YAML value in flow style.
+fn yaml_inline(value: &ConfigValue) -> String {
+    match value {
+        ConfigValue::Bool(b) => b.to_string(),
+        ConfigValue::Integer(i) => i.to_string(),
+        ConfigValue::Float(f) => float(*f, ".nan", ".inf"),
+        ConfigValue::String(s) => quote(s),
+        ConfigValue::Array(values) => {
+            let values: Vec<String> = values.iter().map(yaml_inline).collect();
+            format!("[{}]", values.join(", "))
+        }
+        ConfigValue::Table(table) => {
+            let entries: Vec<String> = table
+                .iter()
+                .map(|(key, value)| format!("{}: {}", yaml_key(key), yaml_inline(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+/// Renders a YAML mapping in block style at the given indentation.
+fn write_yaml(out: &mut String, indent: usize, table: &BTreeMap<String, ConfigValue>) {
+    for (key, value) in table {
+        let _ = write!(out, "{:indent$}{}:", "", yaml_key(key));
+        match value {
+            ConfigValue::Table(subtable) if !subtable.is_empty() => {
+                out.push('\n');
+                write_yaml(out, indent + 2, subtable);
+            }
+            value => {
+                let _ = writeln!(out, " {}", yaml_inline(value));
+            }
+        }
+    }
+}
+
+/// Writes a TOML file with random values for the given schema.
+///
+/// # Parameters
+/// - `spec`: The schema of the file.
+///
+/// # Returns
+/// - A [`ConfigFile`] with the `.toml` extension, holding the written values.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, ConfigSpec, ConfigValue};
+/// use regd_testing::rand::generator as g;
+///
+/// let spec = ConfigSpec::new()
+///     .key("name", g::just("regd"))
+///     .key("server.port", g::range(1024..=65535u16))
+///     .key("server.hosts", g::just(vec!["a", "b"]));
+/// let config = fs::random_config_toml(&spec);
+///
+/// let text = std::fs::read_to_string(config.path()).unwrap();
+/// let ConfigValue::Integer(port) = config.get("server.port").unwrap() else {
+///     unreachable!()
+/// };
+/// assert_eq!(
+///     text,
+///     format!("name = \"regd\"\n\n[server]\nhosts = [\"a\", \"b\"]\nport = {port}\n")
+/// );
+/// ```
+///
+/// # Panics
+/// - This function will panic if a key is nested under a non-table value, or if the file
+///   cannot be written.
+pub fn random_config_toml(spec: &ConfigSpec) -> ConfigFile {
+    let values = spec.generate();
+    let mut text = String::new();
+    write_toml(&mut text, &[], &values);
+    ConfigFile {
+        file: TempFile::with_suffix(".toml", text),
+        values,
+    }
+}
+
+/// Writes a YAML file with random values for the given schema.
+///
+/// # Parameters
+/// - `spec`: The schema of the file.
+///
+/// # Returns
+/// - A [`ConfigFile`] with the `.yaml` extension, holding the written values.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::{self, ConfigSpec, ConfigValue};
+/// use regd_testing::rand::generator as g;
+///
+/// let spec = ConfigSpec::new()
+///     .key("name", g::just("regd"))
+///     .key("server.tls", g::just(true))
+///     .key("server.ratio", g::just(0.5));
+/// let config = fs::random_config_yaml(&spec);
+///
+/// let text = std::fs::read_to_string(config.path()).unwrap();
+/// assert_eq!(text, "name: \"regd\"\nserver:\n  ratio: 0.5\n  tls: true\n");
+/// assert_eq!(config.get("server.tls"), Some(&ConfigValue::Bool(true)));
+/// ```
+///
+/// # Panics
+/// - This function will panic if a key is nested under a non-table value, or if the file
+///   cannot be written.
+pub fn random_config_yaml(spec: &ConfigSpec) -> ConfigFile {
+    let values = spec.generate();
+    let mut text = String::new();
+    write_yaml(&mut text, 0, &values);
+    ConfigFile {
+        file: TempFile::with_suffix(".yaml", text),
+        values,
+    }
+}
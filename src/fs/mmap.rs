@@ -0,0 +1,155 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of memory-mapped file fixtures.
+
+use std::fs::File;
+use std::ops::Deref;
+use std::path::Path;
+
+use memmap2::{Mmap, MmapMut, MmapOptions};
+
+use super::TempFile;
+
+/// The memory map of a [`MmapFixture`].
+#[derive(Debug)]
+enum Map {
+    ReadOnly(Mmap),
+    CopyOnWrite(MmapMut),
+}
+
+/// A temporary file of random content mapped into memory, which is unmapped and deleted on drop.
+///
+/// The map is either read-only, or copy-on-write, in which case writes to the map are private to
+/// it and never reach the file. This is useful for testing zero-copy readers, which take a
+/// `&[u8]` backed by a file rather than by the heap.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "mmap")]
+/// # {
+/// use regd_testing::fs::MmapFixture;
+///
+/// let fixture = MmapFixture::random(4096);
+/// assert_eq!(fixture.len(), 4096);
+/// assert_eq!(&fixture[..], &std::fs::read(fixture.path()).unwrap()[..]);
+///
+/// let path = fixture.path().to_path_buf();
+/// drop(fixture);
+/// assert!(!path.exists());
+/// # }
+/// ```
+///
+/// # Notes
+/// - The file must not be modified, e.g., by the code under test writing to [`path`], while it is
+///   mapped, since a mapped file changing under a `&[u8]` is undefined behavior.
+///
+/// [`path`]: Self::path
+#[derive(Debug)]
+pub struct MmapFixture {
+    // The map is declared before the file, so that it is unmapped before the file is deleted.
+    map: Map,
+    file: TempFile,
+}
+
+impl MmapFixture {
+    /// Creates a temporary file of the given number of random bytes, mapped read-only.
+    ///
+    /// # Panics
+    /// - This function will panic if the file cannot be created, written, or mapped.
+    pub fn random(len: usize) -> Self {
+        let file = TempFile::with_random_bytes(len);
+        // SAFETY: The file is private to the fixture, which never modifies it while mapped.
+        let map = unsafe { Mmap::map(&open(&file)) }
+            .unwrap_or_else(|e| panic!("cannot map {}: {e}", file.path().display()));
+        Self {
+            map: Map::ReadOnly(map),
+            file,
+        }
+    }
+
+    /// Creates a temporary file of the given number of random bytes, mapped copy-on-write.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "mmap")]
+    /// # {
+    /// use regd_testing::fs::MmapFixture;
+    ///
+    /// let mut fixture = MmapFixture::random_copy_on_write(16);
+    /// let original = std::fs::read(fixture.path()).unwrap();
+    /// fixture.as_mut_slice().unwrap().fill(0);
+    /// assert_eq!(&fixture[..], &[0; 16]);
+    /// assert_eq!(std::fs::read(fixture.path()).unwrap(), original);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// - This function will panic if the file cannot be created, written, or mapped.
+    pub fn random_copy_on_write(len: usize) -> Self {
+        let file = TempFile::with_random_bytes(len);
+        // SAFETY: The file is private to the fixture, which never modifies it while mapped.
+        let map = unsafe { MmapOptions::new().map_copy(&open(&file)) }
+            .unwrap_or_else(|e| panic!("cannot map {}: {e}", file.path().display()));
+        Self {
+            map: Map::CopyOnWrite(map),
+            file,
+        }
+    }
+
+    /// Returns the path of the mapped file.
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Returns the mapped bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.map {
+            Map::ReadOnly(map) => map,
+            Map::CopyOnWrite(map) => map,
+        }
+    }
+
+    /// Returns the mapped bytes mutably, or `None` if the map is read-only.
+    pub fn as_mut_slice(&mut self) -> Option<&mut [u8]> {
+        match &mut self.map {
+            Map::ReadOnly(_) => None,
+            Map::CopyOnWrite(map) => Some(map),
+        }
+    }
+
+    /// Returns `true` if the map is copy-on-write.
+    pub fn is_copy_on_write(&self) -> bool {
+        matches!(self.map, Map::CopyOnWrite(_))
+    }
+}
+
+impl Deref for MmapFixture {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for MmapFixture {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+/// Opens the given temporary file for reading.
+fn open(file: &TempFile) -> File {
+    File::open(file.path()).unwrap_or_else(|e| panic!("cannot open {}: {e}", file.path().display()))
+}
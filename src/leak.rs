@@ -0,0 +1,295 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of resource leak detection.
+//!
+//! A check snapshots the open file descriptors and the threads of the process before running a
+//! closure, and asserts that they return to the snapshot afterwards. Since these resources are
+//! global to the process, checks are serialized by a process-wide lock, yet resources opened or
+//! closed meanwhile by other threads, e.g., by other tests running in parallel, are attributed to
+//! the closure as well.
+//!
+//! # Notes
+//! - On Linux and Android, the file descriptors are enumerated from `/proc/self/fd` and
+//!   described by their targets, e.g., `/tmp/foo` or `socket:[1234]`. On other Unix systems,
+//!   they are enumerated from `/dev/fd`, or probed one by one if it cannot be read, and described
+//!   by their paths on Apple platforms only.
+//! - The threads are counted from `/proc/self/task`, so that they are only checked on Linux and
+//!   Android.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::os::fd::RawFd;
+use std::time::Duration;
+
+use crate::env::ReentrantLock;
+use crate::poll;
+
+/// The lock serializing every check made through this module.
+static LEAK_LOCK: ReentrantLock = ReentrantLock::new();
+
+/// The directory listing the open file descriptors of the process.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const FD_DIR: &str = "/proc/self/fd";
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const FD_DIR: &str = "/dev/fd";
+
+/// The number of file descriptors probed when [`FD_DIR`] cannot be read.
+const MAX_PROBED_FDS: RawFd = 4096;
+
+/// Options of [`check_with`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeakOpts {
+    /// Whether the open file descriptors are checked.
+    pub fds: bool,
+    /// Whether the number of threads is checked.
+    pub threads: bool,
+    /// The substrings of the descriptions of the file descriptors which are never reported,
+    /// e.g., `"anon_inode:[eventpoll]"` for the poller of a runtime created lazily.
+    pub allow: Vec<String>,
+    /// The time given to the resources to be released after the closure returns, e.g., for
+    /// detached threads to exit.
+    pub settle: Duration,
+}
+
+impl Default for LeakOpts {
+    fn default() -> Self {
+        Self {
+            fds: true,
+            threads: true,
+            allow: Vec::new(),
+            settle: Duration::from_secs(1),
+        }
+    }
+}
+
+/// A snapshot of the open file descriptors and the threads of the process.
+///
+/// # Examples
+/// ```
+/// use regd_testing::leak::Snapshot;
+///
+/// let before = Snapshot::take();
+/// let file = std::fs::File::open("Cargo.toml").unwrap();
+/// let after = Snapshot::take();
+/// assert_eq!(after.fds().len(), before.fds().len() + 1);
+/// # drop(file);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    fds: BTreeMap<RawFd, String>,
+    threads: Option<usize>,
+}
+
+impl Snapshot {
+    /// Takes a snapshot of the resources of the process.
+    pub fn take() -> Self {
+        Self {
+            fds: open_fds(),
+            threads: threads(),
+        }
+    }
+
+    /// Returns the open file descriptors, each with a description of what it refers to, which is
+    /// empty if unknown.
+    pub fn fds(&self) -> &BTreeMap<RawFd, String> {
+        &self.fds
+    }
+
+    /// Returns the number of threads, or `None` if it cannot be counted on this platform.
+    pub fn threads(&self) -> Option<usize> {
+        self.threads
+    }
+
+    /// Returns the resources of `after` which were not in this snapshot.
+    fn leaks(&self, after: &Self, opts: &LeakOpts) -> Vec<Leak> {
+        let mut leaks = Vec::new();
+        if opts.fds {
+            leaks.extend(
+                after
+                    .fds
+                    .iter()
+                    .filter(|&(fd, target)| self.fds.get(fd) != Some(target))
+                    .filter(|(_, target)| !opts.allow.iter().any(|a| target.contains(a.as_str())))
+                    .map(|(&fd, target)| Leak::Fd(fd, target.clone())),
+            );
+        }
+        if opts.threads {
+            if let (Some(before), Some(after)) = (self.threads, after.threads) {
+                if after > before {
+                    leaks.push(Leak::Threads(before, after));
+                }
+            }
+        }
+        leaks
+    }
+}
+
+/// A resource which was not released.
+enum Leak {
+    /// A file descriptor with its description.
+    Fd(RawFd, String),
+    /// A number of threads, before and after.
+    Threads(usize, usize),
+}
+
+impl fmt::Display for Leak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fd(fd, target) if target.is_empty() => write!(f, "file descriptor {fd}"),
+            Self::Fd(fd, target) => write!(f, "file descriptor {fd} ({target})"),
+            Self::Threads(before, after) => write!(f, "{} thread(s)", after - before),
+        }
+    }
+}
+
+/// Returns whether the given file descriptor is open.
+fn is_open(fd: RawFd) -> bool {
+    // SAFETY: Querying the flags of any file descriptor is always sound.
+    unsafe { libc::fcntl(fd, libc::F_GETFD) >= 0 }
+}
+
+/// Returns a description of what the given file descriptor refers to, or an empty string.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn describe(fd: RawFd) -> String {
+    fs::read_link(format!("{FD_DIR}/{fd}"))
+        .map(|target| target.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Returns a description of what the given file descriptor refers to, or an empty string.
+#[cfg(target_vendor = "apple")]
+fn describe(fd: RawFd) -> String {
+    let mut path = [0u8; libc::PATH_MAX as usize];
+    // SAFETY: `path` is `PATH_MAX` bytes long, as required by `F_GETPATH`.
+    if unsafe { libc::fcntl(fd, libc::F_GETPATH, path.as_mut_ptr()) } < 0 {
+        return String::new();
+    }
+    let len = path.iter().position(|&b| b == 0).unwrap_or(path.len());
+    String::from_utf8_lossy(&path[..len]).into_owned()
+}
+
+/// Returns a description of what the given file descriptor refers to, or an empty string.
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+fn describe(_fd: RawFd) -> String {
+    String::new()
+}
+
+/// Returns the open file descriptors of the process with their descriptions.
+fn open_fds() -> BTreeMap<RawFd, String> {
+    let listed: Vec<RawFd> = match fs::read_dir(FD_DIR) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+            .collect(),
+        Err(_) => (0..MAX_PROBED_FDS).collect(),
+    };
+    // The file descriptor of the listing itself is closed by now, and filtered out.
+    listed
+        .into_iter()
+        .filter(|&fd| is_open(fd))
+        .map(|fd| (fd, describe(fd)))
+        .collect()
+}
+
+/// Returns the number of threads of the process, if it can be counted on this platform.
+fn threads() -> Option<usize> {
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+        fs::read_dir("/proc/self/task")
+            .ok()
+            .map(|entries| entries.count())
+    } else {
+        None
+    }
+}
+
+/// Runs a closure and asserts that it releases every file descriptor and thread it creates.
+///
+/// This function is equivalent to [`check_with`] with the default [`LeakOpts`].
+///
+/// # Parameters
+/// - `f`: The closure to run.
+///
+/// # Returns
+/// - The value returned by `f`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::leak;
+///
+/// let len = leak::check(|| {
+///     let contents = std::fs::read("Cargo.toml").unwrap();
+///     std::thread::spawn(|| ()).join().unwrap();
+///     contents.len()
+/// });
+/// assert!(len > 0);
+/// ```
+///
+/// # Panics
+/// - This function will panic, listing the leaked resources, if the resources created by `f` are
+///   not released within one second after it returns. A panic of `f` is propagated.
+#[track_caller]
+pub fn check<T>(f: impl FnOnce() -> T) -> T {
+    check_with(&LeakOpts::default(), f)
+}
+
+/// Runs a closure and asserts that it releases the resources it creates, as configured by the
+/// given options.
+///
+/// # Parameters
+/// - `opts`: The resources to check and the leaks to allow.
+/// - `f`: The closure to run.
+///
+/// # Returns
+/// - The value returned by `f`.
+///
+/// # Examples
+/// ```should_panic
+/// use std::time::Duration;
+///
+/// use regd_testing::leak::{self, LeakOpts};
+///
+/// let opts = LeakOpts {
+///     settle: Duration::ZERO,
+///     ..LeakOpts::default()
+/// };
+/// let mut files = Vec::new();
+/// // Panics with, e.g., "closure leaked 1 resource(s): file descriptor 3 (/.../Cargo.toml)".
+/// leak::check_with(&opts, || files.push(std::fs::File::open("Cargo.toml").unwrap()));
+/// ```
+///
+/// # Panics
+/// - This function will panic, listing the leaked resources, if the resources created by `f` are
+///   not released within `opts.settle` after it returns. A panic of `f` is propagated.
+#[track_caller]
+pub fn check_with<T>(opts: &LeakOpts, f: impl FnOnce() -> T) -> T {
+    let _lock = LEAK_LOCK.lock();
+    let before = Snapshot::take();
+    let value = f();
+    let mut leaks = Vec::new();
+    if poll::poll(opts.settle, || {
+        leaks = before.leaks(&Snapshot::take(), opts);
+        leaks.is_empty()
+    })
+    .is_err()
+    {
+        let leaks: Vec<String> = leaks.iter().map(Leak::to_string).collect();
+        panic!(
+            "closure leaked {} resource(s): {}",
+            leaks.len(),
+            leaks.join(", ")
+        );
+    }
+    value
+}
@@ -16,7 +16,104 @@
 //! These utilities are not tested, are often optimized for developer experience,
 //! rather than performance, and should only be used in test code.  
 
+pub mod assert;
+#[cfg(feature = "async")]
+pub mod async_util;
+pub mod bench;
+#[cfg(unix)]
+pub mod capture;
+pub mod concurrency;
+#[cfg(feature = "docker")]
+pub mod container;
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+pub mod db;
+mod diff;
+pub mod env;
+pub mod error;
+pub mod fixture;
+pub mod fs;
 pub mod io;
+#[cfg(unix)]
+pub mod leak;
+#[cfg(feature = "tracing")]
+pub mod logs;
+pub mod net;
+mod poll;
 pub mod prelude;
+pub mod proc;
 pub mod rand;
+#[cfg(any(feature = "bincode", feature = "cbor", feature = "json"))]
+pub mod serde;
 pub mod slice_ext;
+pub mod snapshot;
+pub mod time;
+
+pub use self::error::Error;
+/// Expands a test function into one `#[test]` per case, each named and reported separately, or
+/// into a `#[test]` repeated with distinct seeds.
+///
+/// The attribute takes a list of case sources in `cases(...)`:
+/// - `(a, b)` or `name = (a, b)`: A tuple of values, passed as the arguments of the function.
+///   A function taking a single argument takes the value itself instead of a tuple.
+/// - `glob = "pattern"`: One case per file matching the pattern, relative to the directory of
+///   the manifest of the crate, e.g., `"tests/fixtures/**/*.json"`. The function takes the
+///   absolute path of the file, as a `&Path` or a `PathBuf`. `*` and `?` match within a path
+///   segment, and `**` matches any number of directories.
+///
+/// The function is kept alongside a module of the same name containing one test per case. Unnamed
+/// values are named `case_0`, `case_1`, ... after their position, and files after their path, so
+/// that `--exact` filters such as `parse::valid_json` select a single case, and a case may also be
+/// named like the function. The function may return a `Result`, as a `#[test]` may. The other
+/// attributes of the function, e.g., `#[ignore]` or `#[should_panic]`, are applied to every case.
+///
+/// With `repeat = N`, the test, or every case of it, runs `N` times through [`rand::repeat`],
+/// each time with a distinct seed installed in the random number generator of [`rand`], and a
+/// failure reports the seed to rerun with.
+///
+/// Without arguments, `#[regd_test]` expands to a single `#[test]`.
+///
+/// Every expanded test installs [`rand::install_failure_hook`], so that a failing test also
+/// reports the seed of the random number generator of [`rand`], wherever the panic occurred.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "macros")]
+/// # {
+/// use regd_testing::regd_test;
+///
+/// #[regd_test(cases(zero = (0, 0), (1, 2), (-3, 4)))]
+/// fn add_commutes(a: i32, b: i32) {
+///     assert_eq!(a + b, b + a);
+/// }
+///
+/// #[regd_test]
+/// fn sort_orders_bytes() {
+///     let mut v = regd_testing::rand::generate_bytes(16);
+///     v.sort();
+///     assert!(v.is_sorted());
+/// }
+///
+/// #[regd_test(repeat = 100)]
+/// fn reverse_is_involutive() {
+///     let s = regd_testing::rand::generate_alphanumeric(16);
+///     assert_eq!(s.chars().rev().rev().collect::<String>(), s);
+/// }
+///
+/// #[regd_test(cases(parse_port = "8080", max = "65535"), repeat = 10)]
+/// fn parse_port(s: &str) -> Result<(), std::num::ParseIntError> {
+///     s.parse::<u16>().map(|_| ())
+/// }
+///
+/// #[regd_test(cases(glob = "src/*.rs"))]
+/// fn has_license_header(path: &std::path::Path) {
+///     let source = std::fs::read_to_string(path).unwrap();
+///     assert!(source.starts_with("// Copyright"));
+/// }
+/// # }
+/// ```
+///
+/// # Notes
+/// - The glob patterns are resolved when the test is compiled, so that adding or removing a
+///   fixture file requires the test to be recompiled, e.g., by touching its source file.
+#[cfg(feature = "macros")]
+pub use regd_testing_macros::regd_test;
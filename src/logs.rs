@@ -0,0 +1,236 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of `tracing` event capture.
+//!
+//! A capture installs a subscriber as the default of the current thread only, so that tests
+//! running in parallel never observe the events of each other, and no global subscriber is
+//! required.
+//!
+//! # Notes
+//! - Events emitted by other threads, e.g., by spawned threads or by the worker threads of an
+//!   async runtime, are not captured unless the subscriber is propagated to them.
+
+use std::fmt::{self, Write};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tracing::field::{Field, Visit};
+use tracing::subscriber::DefaultGuard;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::Registry;
+
+/// An event captured by [`Logs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// The level of the event.
+    pub level: Level,
+    /// The target of the event, which is the module path of the callsite by default.
+    pub target: String,
+    /// The message of the event, which is empty if the event has no message.
+    pub message: String,
+    /// The other fields of the event, in the order they were recorded.
+    pub fields: Vec<(String, String)>,
+}
+
+impl Record {
+    /// Returns the value of the field with the given name, formatted as text.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>5} {}: {}", self.level, self.target, self.message)?;
+        for (key, value) in &self.fields {
+            write!(f, " {key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A visitor collecting the message and the fields of an event.
+#[derive(Default)]
+struct Collect {
+    message: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Visit for Collect {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.push(field, format!("{value:?}"));
+    }
+}
+
+impl Collect {
+    fn push(&mut self, field: &Field, value: String) {
+        match field.name() {
+            "message" => self.message = value,
+            name => self.fields.push((name.to_string(), value)),
+        }
+    }
+}
+
+/// A layer appending every event to a shared buffer.
+struct Recorder {
+    records: Arc<Mutex<Vec<Record>>>,
+}
+
+impl<S: Subscriber> Layer<S> for Recorder {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let mut collect = Collect::default();
+        event.record(&mut collect);
+        let metadata = event.metadata();
+        self.records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Record {
+                level: *metadata.level(),
+                target: metadata.target().to_string(),
+                message: collect.message,
+                fields: collect.fields,
+            });
+    }
+}
+
+/// A capture of the `tracing` events emitted by the current thread.
+///
+/// The capture is created through [`capture`] and stops when dropped.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "tracing")]
+/// # {
+/// use regd_testing::logs;
+/// use tracing::Level;
+///
+/// let logs = logs::capture();
+/// tracing::warn!(attempt = 2, "retrying request");
+/// logs.assert_contains(Level::WARN, "retrying");
+/// logs.assert_not_contains(Level::ERROR, "retrying");
+/// assert_eq!(logs.records()[0].field("attempt"), Some("2"));
+/// # }
+/// ```
+#[must_use = "events are only captured until the capture is dropped"]
+pub struct Logs {
+    records: Arc<Mutex<Vec<Record>>>,
+    _guard: DefaultGuard,
+}
+
+impl Logs {
+    /// Returns a copy of the events captured so far, in the order they were emitted.
+    pub fn records(&self) -> Vec<Record> {
+        self.lock().clone()
+    }
+
+    /// Discards the events captured so far.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Returns `true` if an event of the given level has a message containing the given
+    /// substring.
+    pub fn contains(&self, level: Level, substring: &str) -> bool {
+        self.lock()
+            .iter()
+            .any(|record| record.level == level && record.message.contains(substring))
+    }
+
+    /// Asserts that an event of the given level has a message containing the given substring.
+    ///
+    /// # Panics
+    /// - This method will panic with every captured event if no such event was captured.
+    #[track_caller]
+    pub fn assert_contains(&self, level: Level, substring: &str) {
+        if !self.contains(level, substring) {
+            panic!(
+                "no {level} event contains {substring:?}; captured:\n{}",
+                self.render()
+            );
+        }
+    }
+
+    /// Asserts that no event of the given level has a message containing the given substring.
+    ///
+    /// # Panics
+    /// - This method will panic with every captured event if such an event was captured.
+    #[track_caller]
+    pub fn assert_not_contains(&self, level: Level, substring: &str) {
+        if self.contains(level, substring) {
+            panic!(
+                "a {level} event contains {substring:?}; captured:\n{}",
+                self.render()
+            );
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Record>> {
+        self.records.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Renders every captured event on its own line.
+    fn render(&self) -> String {
+        let records = self.lock();
+        if records.is_empty() {
+            return "  (none)".to_string();
+        }
+        let mut rendered = String::new();
+        for record in records.iter() {
+            let _ = writeln!(rendered, "  {record}");
+        }
+        rendered.pop();
+        rendered
+    }
+}
+
+/// Starts capturing the `tracing` events emitted by the current thread.
+///
+/// # Returns
+/// - A [`Logs`] which captures every event, regardless of its level, until dropped. The previous
+///   default subscriber of the thread is restored on drop.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "tracing")]
+/// # {
+/// use regd_testing::logs;
+/// use tracing::Level;
+///
+/// let logs = logs::capture();
+/// tracing::info!(target: "app", "started");
+/// let records = logs.records();
+/// assert_eq!(records.len(), 1);
+/// assert_eq!(records[0].level, Level::INFO);
+/// assert_eq!(records[0].target, "app");
+/// assert_eq!(records[0].message, "started");
+/// # }
+/// ```
+pub fn capture() -> Logs {
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = Registry::default().with(Recorder {
+        records: Arc::clone(&records),
+    });
+    Logs {
+        records,
+        _guard: tracing::subscriber::set_default(subscriber),
+    }
+}
@@ -0,0 +1,331 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of local networking.
+//!
+//! Every utility binds the loopback interface only, and asks the operating system for an
+//! ephemeral port rather than relying on a hard-coded one, so that tests running in parallel never
+//! collide.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+use crate::error::{Error, Result};
+use crate::poll;
+
+mod chaos;
+mod http;
+mod tcp;
+#[cfg(feature = "tls")]
+mod tls;
+mod udp;
+#[cfg(unix)]
+mod unix;
+#[cfg(feature = "json")]
+mod vcr;
+
+pub use self::chaos::{ChaosOpts, ChaosProxy};
+pub use self::http::{
+    DELETE, GET, HEAD, Method, MockHttpServer, OPTIONS, PATCH, POST, PUT, Request, Response, When,
+};
+pub use self::tcp::{Connection, MockTcpServer, Script};
+#[cfg(feature = "tls")]
+pub use self::tls::{
+    TlsIdentity, TlsOpts, Validity, generate_expired_tls_identity,
+    generate_not_yet_valid_tls_identity, generate_tls_identity, generate_tls_identity_with,
+    generate_wrong_hostname_tls_identity,
+};
+pub use self::udp::{Datagram, MockUdpServer, UdpOpts};
+#[cfg(unix)]
+pub use self::unix::MockUnixServer;
+#[cfg(feature = "json")]
+pub use self::vcr::{MatchRule, VcrServer};
+
+/// A guard of a port reserved by keeping a TCP listener and a UDP socket bound to it.
+///
+/// The port cannot be taken by another test while the guard is alive. This guard is created
+/// through [`reserve_port`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::net;
+///
+/// let guard = net::reserve_port();
+/// assert!(std::net::TcpListener::bind(guard.addr()).is_err());
+///
+/// let port = guard.release();
+/// assert!(std::net::TcpListener::bind(("127.0.0.1", port)).is_ok());
+/// ```
+#[derive(Debug)]
+pub struct PortGuard {
+    listener: TcpListener,
+    socket: UdpSocket,
+}
+
+impl PortGuard {
+    /// Returns the reserved port.
+    pub fn port(&self) -> u16 {
+        self.addr().port()
+    }
+
+    /// Returns the loopback address of the reserved port.
+    pub fn addr(&self) -> SocketAddr {
+        self.listener
+            .local_addr()
+            .unwrap_or_else(|e| panic!("cannot read local address: {e}"))
+    }
+
+    /// Consumes the guard and returns the TCP listener bound to the reserved port.
+    ///
+    /// This is the race-free way of handing the port to a server under test which accepts an
+    /// existing listener.
+    pub fn into_listener(self) -> TcpListener {
+        self.listener
+    }
+
+    /// Consumes the guard and returns the UDP socket bound to the reserved port.
+    pub fn into_socket(self) -> UdpSocket {
+        self.socket
+    }
+
+    /// Releases the reserved port and returns it.
+    pub fn release(self) -> u16 {
+        self.port()
+    }
+}
+
+/// Reserves a port which is free for both TCP and UDP on the loopback interface.
+///
+/// # Returns
+/// - A [`PortGuard`] which keeps the port bound until it is dropped or released.
+///
+/// # Examples
+/// ```
+/// use regd_testing::net;
+///
+/// let guard = net::reserve_port();
+/// let listener = guard.into_listener();
+/// assert_ne!(listener.local_addr().unwrap().port(), 0);
+/// ```
+///
+/// # Panics
+/// - This function will panic if the loopback interface cannot be bound.
+pub fn reserve_port() -> PortGuard {
+    loop {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap_or_else(|e| panic!("cannot bind TCP listener: {e}"));
+        let port = listener
+            .local_addr()
+            .unwrap_or_else(|e| panic!("cannot read local address: {e}"))
+            .port();
+        if let Ok(socket) = UdpSocket::bind((Ipv4Addr::LOCALHOST, port)) {
+            return PortGuard { listener, socket };
+        }
+    }
+}
+
+/// Returns a port which is free for both TCP and UDP on the loopback interface.
+///
+/// # Returns
+/// - A port assigned by the operating system, which is no longer bound when returned.
+///
+/// # Examples
+/// ```
+/// use regd_testing::net;
+///
+/// let port = net::free_port();
+/// let listener = std::net::TcpListener::bind(("127.0.0.1", port)).unwrap();
+/// assert_eq!(listener.local_addr().unwrap().port(), port);
+/// ```
+///
+/// # Panics
+/// - This function will panic if the loopback interface cannot be bound.
+///
+/// # Notes
+/// - Another process may take the port between this function returning and the caller binding
+///   it; see [`reserve_port`] to keep the port bound until it is used.
+pub fn free_port() -> u16 {
+    reserve_port().release()
+}
+
+/// Returns two distinct ports which are free for both TCP and UDP on the loopback interface.
+///
+/// This is useful for testing a pair of peers, e.g., a client and a server, or a primary and a
+/// replica.
+///
+/// # Returns
+/// - A pair of distinct ports, which are no longer bound when returned.
+///
+/// # Examples
+/// ```
+/// use regd_testing::net;
+///
+/// let (a, b) = net::free_port_pair();
+/// assert_ne!(a, b);
+/// ```
+///
+/// # Panics
+/// - This function will panic if the loopback interface cannot be bound.
+pub fn free_port_pair() -> (u16, u16) {
+    let (a, b) = (reserve_port(), reserve_port());
+    (a.release(), b.release())
+}
+
+/// The maximum time a single attempt of the readiness helpers may block.
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Waits until a server accepts TCP connections on the given address, polling with exponential
+/// backoff.
+///
+/// This is useful for tests which spawn a service, which would otherwise sleep for a fixed, and
+/// often either too long or too short, duration before connecting.
+///
+/// # Parameters
+/// - `addr`: The address to connect to.
+/// - `timeout`: The maximum time to wait.
+///
+/// # Returns
+/// - `Ok(())` as soon as a connection succeeds, or [`Error::Timeout`] describing the last
+///   connection error if `timeout` elapses first.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::net;
+///
+/// let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+/// let addr = listener.local_addr().unwrap();
+/// assert!(net::wait_for_port(addr, Duration::from_secs(5)).is_ok());
+///
+/// drop(listener);
+/// assert!(net::wait_for_port(addr, Duration::from_millis(50)).is_err());
+/// ```
+pub fn wait_for_port(addr: SocketAddr, timeout: Duration) -> Result<()> {
+    let mut last_observed = "no attempt".to_string();
+    poll::poll(timeout, || {
+        match TcpStream::connect_timeout(&addr, ATTEMPT_TIMEOUT.min(timeout)) {
+            Ok(_) => true,
+            Err(e) => {
+                last_observed = e.to_string();
+                false
+            }
+        }
+    })
+    .map_err(|(elapsed, attempts)| Error::Timeout {
+        condition: format!("{addr} to accept connections"),
+        elapsed,
+        attempts,
+        last_observed,
+    })
+}
+
+/// Sends a `GET` request to an `http://` URL and returns the status code of the response.
+fn get_status(
+    authority: &str,
+    host: &str,
+    port: u16,
+    path: &str,
+    timeout: Duration,
+) -> std::io::Result<u16> {
+    let addr = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::other(format!("cannot resolve {host}")))?;
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    write!(
+        stream,
+        "GET {path} HTTP/1.1\r\nHost: {authority}\r\nConnection: close\r\n\r\n"
+    )?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    line.split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| std::io::Error::other(format!("malformed status line {line:?}")))
+}
+
+/// Waits until an HTTP server answers `GET` requests to the given URL with a `2xx` status code,
+/// polling with exponential backoff.
+///
+/// # Parameters
+/// - `url`: The `http://` URL to request, e.g., `http://127.0.0.1:8080/healthz`.
+/// - `timeout`: The maximum time to wait.
+///
+/// # Returns
+/// - `Ok(())` as soon as a request succeeds, or [`Error::Timeout`] describing the last status
+///   code or connection error if `timeout` elapses first.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::net::{self, GET, MockHttpServer};
+///
+/// let server = MockHttpServer::new();
+/// server.when(GET, "/healthz").respond(200, "ok");
+///
+/// let url = format!("{}/healthz", server.url());
+/// assert!(net::wait_for_http_ok(&url, Duration::from_secs(5)).is_ok());
+///
+/// let url = format!("{}/missing", server.url());
+/// assert!(net::wait_for_http_ok(&url, Duration::from_millis(50)).is_err());
+/// ```
+///
+/// # Panics
+/// - This function will panic if `url` is not an `http://` URL with a host. TLS is not supported.
+pub fn wait_for_http_ok(url: &str, timeout: Duration) -> Result<()> {
+    let rest = url
+        .strip_prefix("http://")
+        .unwrap_or_else(|| panic!("cannot parse URL {url:?}: expected an http:// URL"));
+    let (authority, path) = rest.find('/').map_or((rest, "/"), |i| rest.split_at(i));
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(bracketed) => bracketed
+            .split_once(']')
+            .map(|(host, port)| (host, port.strip_prefix(':')))
+            .unwrap_or_else(|| panic!("cannot parse URL {url:?}: unclosed bracket")),
+        None => match authority.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+    let port = port.map_or(80, |port| {
+        port.parse()
+            .unwrap_or_else(|_| panic!("cannot parse URL {url:?}: invalid port"))
+    });
+    assert!(!host.is_empty(), "cannot parse URL {url:?}: missing host");
+
+    let mut last_observed = "no attempt".to_string();
+    poll::poll(timeout, || {
+        match get_status(authority, host, port, path, ATTEMPT_TIMEOUT.min(timeout)) {
+            Ok(status) if (200..300).contains(&status) => true,
+            Ok(status) => {
+                last_observed = format!("status {status}");
+                false
+            }
+            Err(e) => {
+                last_observed = e.to_string();
+                false
+            }
+        }
+    })
+    .map_err(|(elapsed, attempts)| Error::Timeout {
+        condition: format!("{url} to respond with 2xx"),
+        elapsed,
+        attempts,
+        last_observed,
+    })
+}
@@ -0,0 +1,250 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of fault-injecting TCP proxies.
+
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use super::MockTcpServer;
+
+/// The size of the buffer of each direction of a proxied connection.
+const BUFFER_LEN: usize = 16 * 1024;
+
+/// Options of [`ChaosProxy::with_opts`].
+///
+/// The default options forward every byte unchanged, as fast as possible.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::net::ChaosOpts;
+///
+/// let opts = ChaosOpts {
+///     latency: Duration::from_millis(20),
+///     bandwidth: Some(64 * 1024),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChaosOpts {
+    /// The delay added before forwarding each chunk of data, in either direction.
+    pub latency: Duration,
+    /// The maximum throughput of each direction in bytes per second, if any.
+    pub bandwidth: Option<u64>,
+    /// The probability of closing the connection instead of forwarding a chunk of data, within
+    /// `[0.0, 1.0]`.
+    pub disconnect_probability: f64,
+    /// The probability of flipping a random bit of each forwarded byte, within `[0.0, 1.0]`.
+    pub corrupt_probability: f64,
+}
+
+impl ChaosOpts {
+    /// Panics if a probability is not within `[0.0, 1.0]`.
+    fn validate(&self) {
+        for p in [self.disconnect_probability, self.corrupt_probability] {
+            assert!((0.0..=1.0).contains(&p), "cannot use probability {p}");
+        }
+        assert!(self.bandwidth != Some(0), "cannot use zero bandwidth");
+    }
+}
+
+/// Reads the current options, ignoring the poisoning caused by a panicking thread.
+fn current(opts: &Mutex<ChaosOpts>) -> ChaosOpts {
+    opts.lock().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Forwards data from `src` to `dst` while injecting faults, until either side is closed.
+///
+/// When `src` is closed cleanly, only the write half of `dst` is shut down, so that a peer which
+/// half-closes its connection still receives the data flowing in the other direction. Otherwise,
+/// `dst` is shut down entirely. Returns whether `src` was closed cleanly.
+fn pump(mut src: impl Read, mut dst: TcpStream, opts: &Mutex<ChaosOpts>) -> bool {
+    let mut buf = vec![0; BUFFER_LEN];
+    loop {
+        let opts = current(opts);
+        let len = opts.bandwidth.map_or(BUFFER_LEN, |bandwidth| {
+            // Sends about ten chunks per second, so that the throughput stays smooth.
+            usize::try_from(bandwidth / 10).map_or(BUFFER_LEN, |len| len.clamp(1, BUFFER_LEN))
+        });
+        let n = match src.read(&mut buf[..len]) {
+            Ok(0) => {
+                let _ = dst.shutdown(Shutdown::Write);
+                return true;
+            }
+            Ok(n) => n,
+            Err(_) => break,
+        };
+        if crate::rand::generate_bool(opts.disconnect_probability) {
+            break;
+        }
+        thread::sleep(opts.latency);
+        for byte in &mut buf[..n] {
+            if crate::rand::generate_bool(opts.corrupt_probability) {
+                *byte ^= 1 << crate::rand::generate_range(0..8);
+            }
+        }
+        if dst.write_all(&buf[..n]).is_err() {
+            break;
+        }
+        if let Some(bandwidth) = opts.bandwidth {
+            thread::sleep(Duration::from_secs_f64(n as f64 / bandwidth as f64));
+        }
+    }
+    let _ = dst.shutdown(Shutdown::Both);
+    false
+}
+
+/// A TCP proxy listening on an ephemeral loopback port, which forwards every connection to an
+/// upstream server while injecting latency, bandwidth caps, random disconnects and byte
+/// corruption.
+///
+/// The options may be changed while the proxy is running, e.g., to simulate a network partition
+/// in the middle of a test. The proxy shuts down on drop.
+///
+/// The faults are drawn from generators seeded from the random number generator of the thread
+/// starting the proxy, distinct for each connection and direction, so that they are reproducible
+/// under a fixed seed set through [`set_seed`], as long as the connections are accepted in the
+/// same order and the data is read in the same chunks.
+///
+/// [`set_seed`]: crate::rand::set_seed
+///
+/// # Examples
+/// ```
+/// use std::io::{Read, Write};
+/// use std::net::TcpStream;
+/// use std::time::{Duration, Instant};
+///
+/// use regd_testing::net::{ChaosOpts, ChaosProxy, MockTcpServer, Script};
+///
+/// let upstream = MockTcpServer::with_script(Script::new().expect(b"PING").respond(b"PONG"));
+/// let opts = ChaosOpts {
+///     latency: Duration::from_millis(50),
+///     ..Default::default()
+/// };
+/// let proxy = ChaosProxy::with_opts(upstream.addr(), &opts);
+///
+/// let start = Instant::now();
+/// let mut client = TcpStream::connect(proxy.addr()).unwrap();
+/// client.write_all(b"PING").unwrap();
+/// let mut reply = String::new();
+/// client.read_to_string(&mut reply).unwrap();
+///
+/// assert_eq!(reply, "PONG");
+/// assert!(start.elapsed() >= Duration::from_millis(100));
+/// ```
+#[derive(Debug)]
+pub struct ChaosProxy {
+    upstream: SocketAddr,
+    opts: Arc<Mutex<ChaosOpts>>,
+    server: MockTcpServer,
+}
+
+impl ChaosProxy {
+    /// Starts a proxy to the given upstream server which forwards every byte unchanged.
+    ///
+    /// # Panics
+    /// - This function will panic if the loopback interface cannot be bound.
+    pub fn new(upstream: SocketAddr) -> Self {
+        Self::with_opts(upstream, &ChaosOpts::default())
+    }
+
+    /// Starts a proxy to the given upstream server with the given options.
+    ///
+    /// # Parameters
+    /// - `upstream`: The address of the server to forward connections to.
+    /// - `opts`: The faults to inject.
+    ///
+    /// # Returns
+    /// - A running [`ChaosProxy`].
+    ///
+    /// # Panics
+    /// - This function will panic if a probability of `opts` is not within `[0.0, 1.0]`, if the
+    ///   bandwidth is zero, or if the loopback interface cannot be bound.
+    pub fn with_opts(upstream: SocketAddr, opts: &ChaosOpts) -> Self {
+        opts.validate();
+        let opts = Arc::new(Mutex::new(opts.clone()));
+        // The pumps draw from their own generators, seeded from the caller's one so that the
+        // injected faults follow the seed of the test.
+        let seed: u64 = crate::rand::generate();
+        let connections = AtomicU64::new(0);
+        let server = {
+            let opts = Arc::clone(&opts);
+            MockTcpServer::new(move |conn| {
+                let index = connections.fetch_add(1, Ordering::SeqCst);
+                let seed = crate::rand::derive_seed(seed, index);
+                let Ok(server) = TcpStream::connect(upstream) else {
+                    return;
+                };
+                let (Ok(client), Ok(server_reader)) =
+                    (conn.stream().try_clone(), server.try_clone())
+                else {
+                    return;
+                };
+                let downstream = {
+                    let opts = Arc::clone(&opts);
+                    thread::spawn(move || {
+                        crate::rand::set_seed(crate::rand::derive_seed(seed, 1));
+                        pump(server_reader, client, &opts)
+                    })
+                };
+                crate::rand::set_seed(crate::rand::derive_seed(seed, 0));
+                if !pump(&mut *conn, server, &opts) {
+                    let _ = conn.stream().shutdown(Shutdown::Both);
+                }
+                let _ = downstream.join();
+            })
+        };
+        Self {
+            upstream,
+            opts,
+            server,
+        }
+    }
+
+    /// Replaces the options of the proxy, which apply to the subsequently forwarded data.
+    ///
+    /// # Panics
+    /// - This method will panic if a probability of `opts` is not within `[0.0, 1.0]`, or if the
+    ///   bandwidth is zero.
+    pub fn set_opts(&self, opts: &ChaosOpts) {
+        opts.validate();
+        *self.opts.lock().unwrap_or_else(PoisonError::into_inner) = opts.clone();
+    }
+
+    /// Returns the address the proxy listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.server.addr()
+    }
+
+    /// Returns the port the proxy listens on.
+    pub fn port(&self) -> u16 {
+        self.server.port()
+    }
+
+    /// Returns the address of the upstream server.
+    pub fn upstream(&self) -> SocketAddr {
+        self.upstream
+    }
+
+    /// Returns the number of connections accepted so far.
+    pub fn connections(&self) -> usize {
+        self.server.connections()
+    }
+}
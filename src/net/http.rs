@@ -0,0 +1,483 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of mock HTTP servers.
+
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use super::{Connection, MockTcpServer};
+
+/// The maximum length of the body of a request, beyond which it is answered with
+/// `413 Content Too Large`.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// An HTTP request method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// The `GET` method.
+    Get,
+    /// The `HEAD` method.
+    Head,
+    /// The `POST` method.
+    Post,
+    /// The `PUT` method.
+    Put,
+    /// The `DELETE` method.
+    Delete,
+    /// The `PATCH` method.
+    Patch,
+    /// The `OPTIONS` method.
+    Options,
+}
+
+/// The `GET` method.
+pub const GET: Method = Method::Get;
+/// The `HEAD` method.
+pub const HEAD: Method = Method::Head;
+/// The `POST` method.
+pub const POST: Method = Method::Post;
+/// The `PUT` method.
+pub const PUT: Method = Method::Put;
+/// The `DELETE` method.
+pub const DELETE: Method = Method::Delete;
+/// The `PATCH` method.
+pub const PATCH: Method = Method::Patch;
+/// The `OPTIONS` method.
+pub const OPTIONS: Method = Method::Options;
+
+impl Method {
+    /// Returns the name of the method as it appears in a request line.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Head => "HEAD",
+            Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Delete => "DELETE",
+            Self::Patch => "PATCH",
+            Self::Options => "OPTIONS",
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A request received by a [`MockHttpServer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request {
+    /// The method of the request, e.g., `GET`.
+    pub method: String,
+    /// The request target, including the query string, e.g., `/v1/items?page=2`.
+    pub target: String,
+    /// The headers of the request, in the order received.
+    pub headers: Vec<(String, String)>,
+    /// The body of the request.
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Returns the path of the request target, i.e., the target without the query string.
+    pub fn path(&self) -> &str {
+        self.target
+            .split_once('?')
+            .map_or(self.target.as_str(), |(path, _)| path)
+    }
+
+    /// Returns the value of the first header of the given name, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the body of the request as a string, replacing invalid UTF-8 sequences.
+    pub fn body_string(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// A response served by a [`MockHttpServer`].
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::net::Response;
+///
+/// let response = Response::new(200)
+///     .header("Content-Type", "application/json")
+///     .body(r#"{"items":[]}"#)
+///     .delay(Duration::from_millis(50))
+///     .chunked(4);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    delay: Duration,
+    chunk_size: Option<usize>,
+}
+
+impl Response {
+    /// Creates an empty response of the given status code.
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+            delay: Duration::ZERO,
+            chunk_size: None,
+        }
+    }
+
+    /// Appends a header to the response.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the body of the response.
+    pub fn body(mut self, body: impl AsRef<[u8]>) -> Self {
+        self.body = body.as_ref().to_vec();
+        self
+    }
+
+    /// Delays the response by the given duration, e.g., to exercise client timeouts.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sends the body with the chunked transfer encoding, in chunks of the given size.
+    ///
+    /// # Panics
+    /// - This method will panic if `chunk_size == 0`.
+    pub fn chunked(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "cannot send empty chunks");
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Writes the response to a connection.
+    ///
+    /// The body is omitted when answering a request of the `HEAD` method, while the headers
+    /// still describe it, e.g., with its `Content-Length`.
+    pub(super) fn write_to(&self, conn: &mut Connection, method: &str) -> std::io::Result<()> {
+        thread::sleep(self.delay);
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        match self.chunk_size {
+            Some(_) => head.push_str("Transfer-Encoding: chunked\r\n"),
+            None => head.push_str(&format!("Content-Length: {}\r\n", self.body.len())),
+        }
+        head.push_str("Connection: close\r\n\r\n");
+        conn.write_all(head.as_bytes())?;
+        if method == Method::Head.as_str() {
+            return conn.flush();
+        }
+        match self.chunk_size {
+            Some(chunk_size) => {
+                for chunk in self.body.chunks(chunk_size) {
+                    conn.write_all(format!("{:x}\r\n", chunk.len()).as_bytes())?;
+                    conn.write_all(chunk)?;
+                    conn.write_all(b"\r\n")?;
+                    conn.flush()?;
+                }
+                conn.write_all(b"0\r\n\r\n")?;
+            }
+            None => conn.write_all(&self.body)?,
+        }
+        conn.flush()
+    }
+}
+
+/// Returns the reason phrase of common status codes.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Content Too Large",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "",
+    }
+}
+
+/// A registered route of a [`MockHttpServer`].
+#[derive(Debug)]
+struct Route {
+    method: Method,
+    path: String,
+    response: Response,
+    hits: usize,
+}
+
+/// The routes and requests shared between a [`MockHttpServer`] and its connections.
+#[derive(Debug, Default)]
+struct State {
+    routes: Vec<Route>,
+    requests: Vec<Request>,
+}
+
+/// Locks the state, ignoring the poisoning caused by a panicking connection.
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Reads a request from a connection, returning the status code to answer with if it is
+/// malformed, i.e., `400`, or if its body is longer than [`MAX_BODY`], i.e., `413`.
+///
+/// Only bodies delimited by `Content-Length` are supported.
+pub(super) fn read_request(conn: &mut Connection) -> Result<Request, u16> {
+    const MALFORMED: u16 = 400;
+
+    let mut reader = BufReader::new(conn);
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|_| MALFORMED)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or(MALFORMED)?.to_string();
+    let target = parts.next().ok_or(MALFORMED)?.to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line).map_err(|_| MALFORMED)?;
+        let header = line.trim_end_matches(['\r', '\n']);
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header.split_once(':').ok_or(MALFORMED)?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut request = Request {
+        method,
+        target,
+        headers,
+        body: Vec::new(),
+    };
+    let length: u64 = request
+        .header("Content-Length")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    if length > MAX_BODY as u64 {
+        return Err(413);
+    }
+    // Reads into a growing buffer, so that a client announcing more than it sends costs nothing.
+    reader
+        .take(length)
+        .read_to_end(&mut request.body)
+        .map_err(|_| MALFORMED)?;
+    if request.body.len() as u64 != length {
+        return Err(MALFORMED);
+    }
+    Ok(request)
+}
+
+/// A builder of a route of a [`MockHttpServer`], created through [`MockHttpServer::when`].
+#[derive(Debug)]
+#[must_use = "the route is only registered once a response is set"]
+pub struct When<'a> {
+    server: &'a MockHttpServer,
+    method: Method,
+    path: String,
+}
+
+impl When<'_> {
+    /// Registers the route, responding with the given status code and body.
+    pub fn respond(self, status: u16, body: impl AsRef<[u8]>) {
+        self.respond_with(Response::new(status).body(body));
+    }
+
+    /// Registers the route, responding with the given response.
+    pub fn respond_with(self, response: Response) {
+        lock(&self.server.state).routes.push(Route {
+            method: self.method,
+            path: self.path,
+            response,
+            hits: 0,
+        });
+    }
+}
+
+/// An HTTP/1.1 server listening on an ephemeral loopback port, which shuts down on drop.
+///
+/// Requests are matched against the registered routes by method and path, ignoring the query
+/// string, and the most recently registered route wins. Requests matching no route are answered
+/// with `404 Not Found`. Every connection serves a single request and is then closed. Responses
+/// to `HEAD` requests carry the headers of the registered response but no body. Requests whose
+/// body is longer than 16 MiB are answered with `413 Content Too Large` without being read.
+///
+/// # Examples
+/// ```
+/// use std::io::{Read, Write};
+/// use std::net::TcpStream;
+///
+/// use regd_testing::net::{GET, HEAD, MockHttpServer};
+///
+/// let server = MockHttpServer::new();
+/// server.when(GET, "/v1/items").respond(200, r#"{"items":[]}"#);
+/// server.when(HEAD, "/v1/items").respond(200, r#"{"items":[]}"#);
+///
+/// let mut client = TcpStream::connect(server.addr()).unwrap();
+/// client
+///     .write_all(b"GET /v1/items?page=2 HTTP/1.1\r\nHost: localhost\r\n\r\n")
+///     .unwrap();
+/// let mut response = String::new();
+/// client.read_to_string(&mut response).unwrap();
+///
+/// assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+/// assert!(response.ends_with(r#"{"items":[]}"#));
+/// assert_eq!(server.hits(GET, "/v1/items"), 1);
+/// assert_eq!(server.requests()[0].target, "/v1/items?page=2");
+///
+/// let mut client = TcpStream::connect(server.addr()).unwrap();
+/// client
+///     .write_all(b"HEAD /v1/items HTTP/1.1\r\nHost: localhost\r\n\r\n")
+///     .unwrap();
+/// let mut response = String::new();
+/// client.read_to_string(&mut response).unwrap();
+///
+/// assert!(response.contains("Content-Length: 12\r\n"));
+/// assert!(response.ends_with("\r\n\r\n"));
+///
+/// let mut client = TcpStream::connect(server.addr()).unwrap();
+/// client
+///     .write_all(b"POST /v1/items HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n")
+///     .unwrap();
+/// let mut response = String::new();
+/// client.read_to_string(&mut response).unwrap();
+///
+/// assert!(response.starts_with("HTTP/1.1 413 Content Too Large\r\n"));
+/// assert_eq!(server.requests().len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct MockHttpServer {
+    state: Arc<Mutex<State>>,
+    server: MockTcpServer,
+}
+
+impl MockHttpServer {
+    /// Starts a server without any route.
+    ///
+    /// # Panics
+    /// - This function will panic if the loopback interface cannot be bound.
+    pub fn new() -> Self {
+        let state = Arc::new(Mutex::new(State::default()));
+        let server = {
+            let state = Arc::clone(&state);
+            MockTcpServer::new(move |conn| {
+                let request = match read_request(conn) {
+                    Ok(request) => request,
+                    Err(status) => {
+                        let _ = Response::new(status).write_to(conn, "");
+                        return;
+                    }
+                };
+                let method = request.method.clone();
+                let response = {
+                    let mut state = lock(&state);
+                    let route = state.routes.iter_mut().rev().find(|route| {
+                        route.method.as_str() == request.method && route.path == request.path()
+                    });
+                    let response = match route {
+                        Some(route) => {
+                            route.hits += 1;
+                            route.response.clone()
+                        }
+                        None => Response::new(404),
+                    };
+                    state.requests.push(request);
+                    response
+                };
+                let _ = response.write_to(conn, &method);
+            })
+        };
+        Self { state, server }
+    }
+
+    /// Starts building a route of the given method and path.
+    ///
+    /// # Parameters
+    /// - `method`: The method of the route, e.g., [`GET`].
+    /// - `path`: The path of the route, without the query string, e.g., `/v1/items`.
+    ///
+    /// # Returns
+    /// - A [`When`] builder, which registers the route once a response is set.
+    pub fn when(&self, method: Method, path: impl Into<String>) -> When<'_> {
+        When {
+            server: self,
+            method,
+            path: path.into(),
+        }
+    }
+
+    /// Returns the address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.server.addr()
+    }
+
+    /// Returns the base URL of the server, e.g., `http://127.0.0.1:49152`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr())
+    }
+
+    /// Returns every request received so far, in the order received.
+    pub fn requests(&self) -> Vec<Request> {
+        lock(&self.state).requests.clone()
+    }
+
+    /// Returns the number of requests served by the routes of the given method and path.
+    pub fn hits(&self, method: Method, path: &str) -> usize {
+        lock(&self.state)
+            .routes
+            .iter()
+            .filter(|route| route.method == method && route.path == path)
+            .map(|route| route.hits)
+            .sum()
+    }
+}
+
+impl Default for MockHttpServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,432 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of mock TCP servers.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+
+/// A stream accepted by a mock server.
+pub(super) trait Stream: Read + Write + Send + Sized + 'static {
+    /// Creates a new handle to the same stream.
+    fn try_clone(&self) -> io::Result<Self>;
+
+    /// Shuts down both halves of the stream.
+    fn shutdown(&self) -> io::Result<()>;
+}
+
+impl Stream for TcpStream {
+    fn try_clone(&self) -> io::Result<Self> {
+        TcpStream::try_clone(self)
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        TcpStream::shutdown(self, Shutdown::Both)
+    }
+}
+
+#[cfg(unix)]
+impl Stream for std::os::unix::net::UnixStream {
+    fn try_clone(&self) -> io::Result<Self> {
+        std::os::unix::net::UnixStream::try_clone(self)
+    }
+
+    fn shutdown(&self) -> io::Result<()> {
+        std::os::unix::net::UnixStream::shutdown(self, Shutdown::Both)
+    }
+}
+
+/// The traffic shared between a mock server and its connections.
+#[derive(Debug)]
+struct Traffic<S> {
+    /// The bytes received on each connection, in the order of acceptance.
+    received: Vec<Vec<u8>>,
+    /// The descriptions of every unmet expectation of a [`Script`].
+    errors: Vec<String>,
+    /// The clones of every accepted stream, which are shut down on drop.
+    streams: Vec<S>,
+}
+
+/// Locks the traffic, ignoring the poisoning caused by a panicking handler.
+fn lock<S>(traffic: &Mutex<Traffic<S>>) -> MutexGuard<'_, Traffic<S>> {
+    traffic.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A connection accepted by a mock server, e.g., a [`MockTcpServer`].
+///
+/// Every byte read from the connection is recorded, so that it can be inspected through
+/// [`MockTcpServer::received`].
+#[derive(Debug)]
+pub struct Connection<S = TcpStream> {
+    stream: S,
+    index: usize,
+    traffic: Arc<Mutex<Traffic<S>>>,
+}
+
+impl<S> Connection<S> {
+    /// Returns the underlying stream.
+    ///
+    /// Bytes read directly from the stream are not recorded.
+    pub fn stream(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl Connection<TcpStream> {
+    /// Returns the address of the client.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+}
+
+impl<S: Read> Read for Connection<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.stream.read(buf)?;
+        lock(&self.traffic).received[self.index].extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<S: Write> Write for Connection<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+/// A single step of a [`Script`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Step {
+    Expect(Vec<u8>),
+    Respond(Vec<u8>),
+    Close,
+}
+
+/// A script played by a mock server, e.g., a [`MockTcpServer`], on every connection.
+///
+/// # Examples
+/// ```
+/// use regd_testing::net::Script;
+///
+/// let script = Script::new().expect(b"PING\n").respond(b"PONG\n").close();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Script {
+    steps: Vec<Step>,
+}
+
+impl Script {
+    /// Creates an empty script.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a step reading exactly the given bytes from the client.
+    ///
+    /// If different bytes are received, or the client closes the connection first, the mismatch
+    /// is recorded in [`MockTcpServer::errors`] and the connection is closed.
+    pub fn expect(mut self, bytes: impl AsRef<[u8]>) -> Self {
+        self.steps.push(Step::Expect(bytes.as_ref().to_vec()));
+        self
+    }
+
+    /// Appends a step writing the given bytes to the client.
+    pub fn respond(mut self, bytes: impl AsRef<[u8]>) -> Self {
+        self.steps.push(Step::Respond(bytes.as_ref().to_vec()));
+        self
+    }
+
+    /// Appends a step closing the connection.
+    ///
+    /// The connection is also closed once every step has been played.
+    pub fn close(mut self) -> Self {
+        self.steps.push(Step::Close);
+        self
+    }
+
+    /// Plays the script on a connection.
+    pub(super) fn play<S: Read + Write>(&self, conn: &mut Connection<S>) {
+        for step in &self.steps {
+            match step {
+                Step::Expect(expected) => {
+                    let mut actual = vec![0; expected.len()];
+                    let error = match conn.read_exact(&mut actual) {
+                        Ok(()) if actual == *expected => continue,
+                        Ok(()) => format!(
+                            "connection {}: expected {:?}, received {:?}",
+                            conn.index,
+                            String::from_utf8_lossy(expected),
+                            String::from_utf8_lossy(&actual)
+                        ),
+                        Err(e) => format!(
+                            "connection {}: expected {:?}, {e}",
+                            conn.index,
+                            String::from_utf8_lossy(expected)
+                        ),
+                    };
+                    lock(&conn.traffic).errors.push(error);
+                    return;
+                }
+                Step::Respond(bytes) => {
+                    if conn.write_all(bytes).is_err() {
+                        return;
+                    }
+                }
+                Step::Close => return,
+            }
+        }
+    }
+}
+
+/// The accept loop shared by the mock servers, which serves each connection on its own thread
+/// and shuts down on drop.
+pub(super) struct Server<S: Stream> {
+    shutdown: Arc<AtomicBool>,
+    traffic: Arc<Mutex<Traffic<S>>>,
+    acceptor: Option<JoinHandle<Vec<JoinHandle<()>>>>,
+    wake: Box<dyn Fn() + Send + Sync>,
+}
+
+impl<S: Stream> Server<S> {
+    /// Starts accepting connections.
+    ///
+    /// `accept` blocks until the next connection, and `wake` unblocks it by connecting to the
+    /// server once it is shutting down.
+    pub(super) fn spawn<A, W, F>(mut accept: A, wake: W, handler: F) -> Self
+    where
+        A: FnMut() -> io::Result<S> + Send + 'static,
+        W: Fn() + Send + Sync + 'static,
+        F: Fn(&mut Connection<S>) + Send + Sync + 'static,
+    {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let traffic = Arc::new(Mutex::new(Traffic {
+            received: Vec::new(),
+            errors: Vec::new(),
+            streams: Vec::new(),
+        }));
+        let handler = Arc::new(handler);
+
+        let acceptor = {
+            let shutdown = Arc::clone(&shutdown);
+            let traffic = Arc::clone(&traffic);
+            thread::spawn(move || {
+                let mut workers = Vec::new();
+                loop {
+                    let stream = accept();
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    let index = {
+                        let mut traffic = lock(&traffic);
+                        if let Ok(clone) = stream.try_clone() {
+                            traffic.streams.push(clone);
+                        }
+                        traffic.received.push(Vec::new());
+                        traffic.received.len() - 1
+                    };
+                    let mut conn = Connection {
+                        stream,
+                        index,
+                        traffic: Arc::clone(&traffic),
+                    };
+                    let handler = Arc::clone(&handler);
+                    workers.push(thread::spawn(move || {
+                        handler(&mut conn);
+                        let _ = conn.stream.shutdown();
+                    }));
+                }
+                workers
+            })
+        };
+
+        Self {
+            shutdown,
+            traffic,
+            acceptor: Some(acceptor),
+            wake: Box::new(wake),
+        }
+    }
+
+    /// Returns the number of connections accepted so far.
+    pub(super) fn connections(&self) -> usize {
+        lock(&self.traffic).received.len()
+    }
+
+    /// Returns the bytes received on each connection so far, in the order of acceptance.
+    pub(super) fn received(&self) -> Vec<Vec<u8>> {
+        lock(&self.traffic).received.clone()
+    }
+
+    /// Returns the descriptions of every unmet expectation of the script so far.
+    pub(super) fn errors(&self) -> Vec<String> {
+        lock(&self.traffic).errors.clone()
+    }
+}
+
+impl<S: Stream> fmt::Debug for Server<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Server")
+            .field("connections", &self.connections())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Stream> Drop for Server<S> {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        (self.wake)();
+        let workers = self
+            .acceptor
+            .take()
+            .map(|acceptor| acceptor.join().unwrap_or_default())
+            .unwrap_or_default();
+        for stream in lock(&self.traffic).streams.drain(..) {
+            let _ = stream.shutdown();
+        }
+        for worker in workers {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// A TCP server listening on an ephemeral loopback port, which shuts down on drop.
+///
+/// Each connection is served on its own thread, either by a user-supplied handler or by a
+/// [`Script`]. Every byte received is recorded for assertions.
+///
+/// # Examples
+/// ```
+/// use std::io::{Read, Write};
+/// use std::net::TcpStream;
+///
+/// use regd_testing::net::{MockTcpServer, Script};
+///
+/// let server = MockTcpServer::with_script(Script::new().expect(b"PING").respond(b"PONG"));
+///
+/// let mut client = TcpStream::connect(server.addr()).unwrap();
+/// client.write_all(b"PING").unwrap();
+/// let mut reply = String::new();
+/// client.read_to_string(&mut reply).unwrap();
+///
+/// assert_eq!(reply, "PONG");
+/// assert_eq!(server.received(), vec![b"PING".to_vec()]);
+/// assert!(server.errors().is_empty());
+/// ```
+#[derive(Debug)]
+pub struct MockTcpServer {
+    addr: SocketAddr,
+    server: Server<TcpStream>,
+}
+
+impl MockTcpServer {
+    /// Starts a server which calls the given handler on every connection.
+    ///
+    /// The connection is closed when the handler returns.
+    ///
+    /// # Parameters
+    /// - `handler`: The function serving a connection.
+    ///
+    /// # Returns
+    /// - A running [`MockTcpServer`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::io::{BufRead, BufReader, Write};
+    /// use std::net::TcpStream;
+    ///
+    /// use regd_testing::net::MockTcpServer;
+    ///
+    /// let server = MockTcpServer::new(|conn| {
+    ///     let mut line = String::new();
+    ///     BufReader::new(&mut *conn).read_line(&mut line).unwrap();
+    ///     conn.write_all(line.to_uppercase().as_bytes()).unwrap();
+    /// });
+    ///
+    /// let mut client = TcpStream::connect(server.addr()).unwrap();
+    /// client.write_all(b"hello\n").unwrap();
+    /// let mut reply = String::new();
+    /// BufReader::new(client).read_line(&mut reply).unwrap();
+    /// assert_eq!(reply, "HELLO\n");
+    /// ```
+    ///
+    /// # Panics
+    /// - This function will panic if the loopback interface cannot be bound.
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&mut Connection) + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap_or_else(|e| panic!("cannot bind TCP listener: {e}"));
+        let addr = listener
+            .local_addr()
+            .unwrap_or_else(|e| panic!("cannot read local address: {e}"));
+        let server = Server::spawn(
+            move || listener.accept().map(|(stream, _)| stream),
+            move || {
+                let _ = TcpStream::connect(addr);
+            },
+            handler,
+        );
+        Self { addr, server }
+    }
+
+    /// Starts a server which plays the given script on every connection.
+    ///
+    /// # Parameters
+    /// - `script`: The script to play.
+    ///
+    /// # Returns
+    /// - A running [`MockTcpServer`].
+    ///
+    /// # Panics
+    /// - This function will panic if the loopback interface cannot be bound.
+    pub fn with_script(script: Script) -> Self {
+        Self::new(move |conn| script.play(conn))
+    }
+
+    /// Returns the address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the port the server listens on.
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// Returns the number of connections accepted so far.
+    pub fn connections(&self) -> usize {
+        self.server.connections()
+    }
+
+    /// Returns the bytes received on each connection so far, in the order of acceptance.
+    pub fn received(&self) -> Vec<Vec<u8>> {
+        self.server.received()
+    }
+
+    /// Returns the descriptions of every unmet expectation of the script so far.
+    pub fn errors(&self) -> Vec<String> {
+        self.server.errors()
+    }
+}
@@ -0,0 +1,242 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of mock UDP servers.
+
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::error::{Error, Result};
+use crate::poll;
+
+/// The maximum size of a datagram received by a [`MockUdpServer`].
+const MAX_DATAGRAM_LEN: usize = 65_535;
+
+/// A datagram received by a [`MockUdpServer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Datagram {
+    /// The address of the sender.
+    pub from: SocketAddr,
+    /// The payload of the datagram.
+    pub payload: Vec<u8>,
+}
+
+/// Options of [`MockUdpServer::with_opts`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::net::UdpOpts;
+///
+/// let opts = UdpOpts {
+///     reply: Some(b"ACK".to_vec()),
+///     drop_probability: 0.1,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UdpOpts {
+    /// The payload sent back to the sender of every datagram, if any.
+    pub reply: Option<Vec<u8>>,
+    /// The probability of silently dropping a received datagram, within `[0.0, 1.0]`.
+    pub drop_probability: f64,
+    /// The probability of handling a received datagram twice, within `[0.0, 1.0]`.
+    pub duplicate_probability: f64,
+}
+
+/// Locks the received datagrams, ignoring the poisoning caused by a panicking thread.
+fn lock(received: &Mutex<Vec<Datagram>>) -> MutexGuard<'_, Vec<Datagram>> {
+    received.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A UDP server bound to an ephemeral loopback port, which shuts down on drop.
+///
+/// The server records every datagram it receives and, optionally, replies to it. It can simulate
+/// an unreliable network by dropping or duplicating datagrams at configured probabilities; a
+/// dropped datagram is neither recorded nor replied to, while a duplicated one is recorded and
+/// replied to twice. These decisions are drawn from a generator seeded from the random number
+/// generator of the thread starting the server, so that they follow [`set_seed`].
+///
+/// [`set_seed`]: crate::rand::set_seed
+///
+/// # Examples
+/// ```
+/// use std::net::UdpSocket;
+/// use std::time::Duration;
+///
+/// use regd_testing::net::{MockUdpServer, UdpOpts};
+///
+/// let opts = UdpOpts {
+///     reply: Some(b"ACK".to_vec()),
+///     ..Default::default()
+/// };
+/// let server = MockUdpServer::with_opts(&opts);
+///
+/// let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+/// client.send_to(b"heartbeat", server.addr()).unwrap();
+///
+/// let mut buf = [0; 16];
+/// let (n, _) = client.recv_from(&mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"ACK");
+///
+/// let received = server.wait_for_datagrams(1, Duration::from_secs(5)).unwrap();
+/// assert_eq!(received[0].payload, b"heartbeat");
+/// ```
+#[derive(Debug)]
+pub struct MockUdpServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    received: Arc<Mutex<Vec<Datagram>>>,
+    receiver: Option<JoinHandle<()>>,
+}
+
+impl MockUdpServer {
+    /// Starts a server which records every datagram without replying.
+    ///
+    /// # Panics
+    /// - This function will panic if the loopback interface cannot be bound.
+    pub fn new() -> Self {
+        Self::with_opts(&UdpOpts::default())
+    }
+
+    /// Starts a server with the given options.
+    ///
+    /// # Parameters
+    /// - `opts`: The reply and the drop and duplicate probabilities.
+    ///
+    /// # Returns
+    /// - A running [`MockUdpServer`].
+    ///
+    /// # Panics
+    /// - This function will panic if a probability of `opts` is not within `[0.0, 1.0]`, or if the
+    ///   loopback interface cannot be bound.
+    pub fn with_opts(opts: &UdpOpts) -> Self {
+        for p in [opts.drop_probability, opts.duplicate_probability] {
+            assert!((0.0..=1.0).contains(&p), "cannot use probability {p}");
+        }
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap_or_else(|e| panic!("cannot bind UDP socket: {e}"));
+        let addr = socket
+            .local_addr()
+            .unwrap_or_else(|e| panic!("cannot read local address: {e}"));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let receiver = {
+            let opts = opts.clone();
+            let shutdown = Arc::clone(&shutdown);
+            let received = Arc::clone(&received);
+            // The receiver draws from its own generator, seeded from the caller's one so that the
+            // dropped and duplicated datagrams follow the seed of the test.
+            let seed: u64 = crate::rand::generate();
+            thread::spawn(move || {
+                crate::rand::set_seed(seed);
+                let mut buf = vec![0; MAX_DATAGRAM_LEN];
+                loop {
+                    let (n, from) = match socket.recv_from(&mut buf) {
+                        Ok(received) => received,
+                        // Windows reports an ICMP port unreachable caused by a reply as an error
+                        // of the next receive.
+                        Err(e) if e.kind() == io::ErrorKind::ConnectionReset => continue,
+                        Err(_) => break,
+                    };
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if crate::rand::generate_bool(opts.drop_probability) {
+                        continue;
+                    }
+                    let copies = if crate::rand::generate_bool(opts.duplicate_probability) {
+                        2
+                    } else {
+                        1
+                    };
+                    for _ in 0..copies {
+                        lock(&received).push(Datagram {
+                            from,
+                            payload: buf[..n].to_vec(),
+                        });
+                        if let Some(reply) = &opts.reply {
+                            let _ = socket.send_to(reply, from);
+                        }
+                    }
+                }
+            })
+        };
+
+        Self {
+            addr,
+            shutdown,
+            received,
+            receiver: Some(receiver),
+        }
+    }
+
+    /// Returns the address the server is bound to.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the port the server is bound to.
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// Returns every datagram recorded so far, in the order received.
+    pub fn received(&self) -> Vec<Datagram> {
+        lock(&self.received).clone()
+    }
+
+    /// Waits until at least `count` datagrams have been recorded, polling with exponential
+    /// backoff.
+    ///
+    /// # Parameters
+    /// - `count`: The number of datagrams to wait for.
+    /// - `timeout`: The maximum time to wait.
+    ///
+    /// # Returns
+    /// - Every datagram recorded so far, or [`Error::Timeout`] if `timeout` elapses first.
+    pub fn wait_for_datagrams(&self, count: usize, timeout: Duration) -> Result<Vec<Datagram>> {
+        poll::poll(timeout, || lock(&self.received).len() >= count)
+            .map(|()| self.received())
+            .map_err(|(elapsed, attempts)| Error::Timeout {
+                condition: format!("{count} datagrams on {}", self.addr),
+                elapsed,
+                attempts,
+                last_observed: format!("{} datagrams", lock(&self.received).len()),
+            })
+    }
+}
+
+impl Default for MockUdpServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MockUdpServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wakes the receiver blocked on `recv_from`.
+        if let Ok(socket) = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)) {
+            let _ = socket.send_to(&[], self.addr);
+        }
+        if let Some(receiver) = self.receiver.take() {
+            let _ = receiver.join();
+        }
+    }
+}
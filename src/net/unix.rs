@@ -0,0 +1,128 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of mock Unix domain socket servers.
+
+use std::fs;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+use super::tcp::Server;
+use super::{Connection, Script};
+
+/// A guard of a socket file, which is deleted on drop.
+#[derive(Debug)]
+struct SocketFile(PathBuf);
+
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// A Unix domain socket server listening on a fresh path under the temporary directory, which
+/// shuts down and deletes its socket file on drop.
+///
+/// Each connection is served on its own thread, either by a user-supplied handler or by a
+/// [`Script`]. Every byte received is recorded for assertions.
+///
+/// # Examples
+/// ```
+/// use std::io::{Read, Write};
+/// use std::os::unix::net::UnixStream;
+///
+/// use regd_testing::net::{MockUnixServer, Script};
+///
+/// let server = MockUnixServer::with_script(Script::new().expect(b"PING").respond(b"PONG"));
+///
+/// let mut client = UnixStream::connect(server.path()).unwrap();
+/// client.write_all(b"PING").unwrap();
+/// let mut reply = String::new();
+/// client.read_to_string(&mut reply).unwrap();
+///
+/// assert_eq!(reply, "PONG");
+/// assert_eq!(server.received(), vec![b"PING".to_vec()]);
+/// ```
+#[derive(Debug)]
+pub struct MockUnixServer {
+    // Declared before `socket`, so that the server shuts down before the file is deleted.
+    server: Server<UnixStream>,
+    socket: SocketFile,
+}
+
+impl MockUnixServer {
+    /// Starts a server which calls the given handler on every connection.
+    ///
+    /// The connection is closed when the handler returns.
+    ///
+    /// # Parameters
+    /// - `handler`: The function serving a connection.
+    ///
+    /// # Returns
+    /// - A running [`MockUnixServer`].
+    ///
+    /// # Panics
+    /// - This function will panic if the socket cannot be bound.
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&mut Connection<UnixStream>) + Send + Sync + 'static,
+    {
+        let path = crate::rand::generate_socket_path();
+        let listener = UnixListener::bind(&path)
+            .unwrap_or_else(|e| panic!("cannot bind {}: {e}", path.display()));
+        let socket = SocketFile(path.clone());
+        let server = Server::spawn(
+            move || listener.accept().map(|(stream, _)| stream),
+            move || {
+                let _ = UnixStream::connect(&path);
+            },
+            handler,
+        );
+        Self { server, socket }
+    }
+
+    /// Starts a server which plays the given script on every connection.
+    ///
+    /// # Parameters
+    /// - `script`: The script to play.
+    ///
+    /// # Returns
+    /// - A running [`MockUnixServer`].
+    ///
+    /// # Panics
+    /// - This function will panic if the socket cannot be bound.
+    pub fn with_script(script: Script) -> Self {
+        Self::new(move |conn| script.play(conn))
+    }
+
+    /// Returns the path of the socket the server listens on.
+    pub fn path(&self) -> &Path {
+        &self.socket.0
+    }
+
+    /// Returns the number of connections accepted so far.
+    pub fn connections(&self) -> usize {
+        self.server.connections()
+    }
+
+    /// Returns the bytes received on each connection so far, in the order of acceptance.
+    pub fn received(&self) -> Vec<Vec<u8>> {
+        self.server.received()
+    }
+
+    /// Returns the descriptions of every unmet expectation of the script so far.
+    pub fn errors(&self) -> Vec<String> {
+        self.server.errors()
+    }
+}
@@ -0,0 +1,600 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of recorded HTTP interactions.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use serde_json::{Map, Value, json};
+
+use super::http::read_request;
+use super::{MockTcpServer, Request, Response};
+
+/// The time given to the upstream server to answer a request while recording.
+const UPSTREAM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The headers describing a single connection, which are never forwarded nor recorded.
+const HOP_BY_HOP: [&str; 5] = [
+    "Connection",
+    "Content-Length",
+    "Keep-Alive",
+    "Transfer-Encoding",
+    "Upgrade",
+];
+
+/// A rule of [`VcrServer::replay_with`], which requires a part of a request to equal the one of a
+/// recorded request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MatchRule {
+    /// The method, e.g., `GET`.
+    Method,
+    /// The path of the request target, without the query string.
+    Path,
+    /// The query string of the request target.
+    Query,
+    /// The value of the header of the given name, compared case-insensitively by name.
+    Header(String),
+    /// The body.
+    Body,
+}
+
+impl MatchRule {
+    /// Returns whether `request` matches `recorded` under this rule.
+    fn matches(&self, recorded: &Request, request: &Request) -> bool {
+        match self {
+            Self::Method => recorded.method == request.method,
+            Self::Path => recorded.path() == request.path(),
+            Self::Query => query(recorded) == query(request),
+            Self::Header(name) => recorded.header(name) == request.header(name),
+            Self::Body => recorded.body == request.body,
+        }
+    }
+}
+
+/// Returns the query string of the request target, if any.
+fn query(request: &Request) -> Option<&str> {
+    request.target.split_once('?').map(|(_, query)| query)
+}
+
+/// Returns whether the header of the given name describes a single connection.
+fn is_hop_by_hop(name: &str) -> bool {
+    HOP_BY_HOP.iter().any(|hop| hop.eq_ignore_ascii_case(name))
+}
+
+/// A response recorded from the upstream server, without its hop-by-hop headers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Recorded {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Recorded {
+    /// Returns the response replaying this one.
+    fn to_response(&self) -> Response {
+        self.headers
+            .iter()
+            .fold(Response::new(self.status), |response, (name, value)| {
+                response.header(name, value)
+            })
+            .body(&self.body)
+    }
+}
+
+/// A request together with the response it was answered with.
+#[derive(Clone, Debug)]
+struct Interaction {
+    request: Request,
+    response: Recorded,
+    replayed: bool,
+}
+
+/// The server recording the interactions, as parsed from the URL given to [`VcrServer::record`].
+#[derive(Clone, Debug)]
+struct Upstream {
+    /// The address to connect to, e.g., `example.com:80`.
+    addr: String,
+    /// The value of the `Host` header, e.g., `example.com`.
+    host: String,
+    /// The path prefixed to every request target, without the trailing slash.
+    base: String,
+}
+
+impl Upstream {
+    /// Parses an `http://` URL.
+    fn parse(url: &str) -> Option<Self> {
+        let rest = url.strip_prefix("http://")?;
+        let (host, base) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        if host.is_empty() {
+            return None;
+        }
+        let addr = if host
+            .rsplit_once(':')
+            .is_some_and(|(_, port)| !port.contains(']'))
+        {
+            host.to_string()
+        } else {
+            format!("{host}:80")
+        };
+        Some(Self {
+            addr,
+            host: host.to_string(),
+            base: base.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Forwards a request, returning the response of the upstream server.
+    fn forward(&self, request: &Request) -> io::Result<Recorded> {
+        let mut stream = TcpStream::connect(&self.addr)?;
+        stream.set_read_timeout(Some(UPSTREAM_TIMEOUT))?;
+        let mut head = format!(
+            "{} {}{} HTTP/1.1\r\nHost: {}\r\n",
+            request.method, self.base, request.target, self.host
+        );
+        for (name, value) in &request.headers {
+            if !is_hop_by_hop(name) && !name.eq_ignore_ascii_case("Host") {
+                let _ = write!(head, "{name}: {value}\r\n");
+            }
+        }
+        if !request.body.is_empty() {
+            let _ = write!(head, "Content-Length: {}\r\n", request.body.len());
+        }
+        head.push_str("Connection: close\r\n\r\n");
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&request.body)?;
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw)?;
+        parse_response(&raw)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed HTTP response"))
+    }
+}
+
+/// Parses a response read until the upstream server closed the connection.
+fn parse_response(raw: &[u8]) -> Option<Recorded> {
+    let end = raw.windows(4).position(|window| window == b"\r\n\r\n")?;
+    let head = std::str::from_utf8(&raw[..end]).ok()?;
+    let mut body = raw[end + 4..].to_vec();
+    let mut lines = head.split("\r\n");
+    let status = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+    let mut headers = Vec::new();
+    let mut chunked = false;
+    let mut length = None;
+    for line in lines {
+        let (name, value) = line.split_once(':')?;
+        let (name, value) = (name.trim(), value.trim());
+        if name.eq_ignore_ascii_case("Transfer-Encoding") {
+            chunked = value.eq_ignore_ascii_case("chunked");
+        } else if name.eq_ignore_ascii_case("Content-Length") {
+            length = value.parse().ok();
+        }
+        if !is_hop_by_hop(name) {
+            headers.push((name.to_string(), value.to_string()));
+        }
+    }
+    if chunked {
+        body = decode_chunked(&body)?;
+    } else if let Some(length) = length {
+        body.truncate(length);
+    }
+    Some(Recorded {
+        status,
+        headers,
+        body,
+    })
+}
+
+/// Decodes a body sent with the chunked transfer encoding.
+fn decode_chunked(mut raw: &[u8]) -> Option<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let end = raw.windows(2).position(|window| window == b"\r\n")?;
+        let size = std::str::from_utf8(&raw[..end]).ok()?;
+        let size = size.split(';').next()?.trim();
+        let size = usize::from_str_radix(size, 16).ok()?;
+        raw = &raw[end + 2..];
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(raw.get(..size)?);
+        raw = raw.get(size + 2..)?;
+    }
+}
+
+/// Returns the JSON representation of a body, as text if it is valid UTF-8 or as hex otherwise.
+fn body_to_json(body: &[u8], object: &mut Map<String, Value>) {
+    match std::str::from_utf8(body) {
+        Ok(text) => object.insert("body".into(), text.into()),
+        Err(_) => object.insert(
+            "body_hex".into(),
+            body.iter()
+                .fold(String::new(), |mut hex, b| {
+                    let _ = write!(hex, "{b:02x}");
+                    hex
+                })
+                .into(),
+        ),
+    };
+}
+
+/// Returns the body of the JSON representation returned by [`body_to_json`].
+fn body_from_json(object: &Value) -> Option<Vec<u8>> {
+    if let Some(hex) = object.get("body_hex") {
+        let hex = hex.as_str()?;
+        return (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect();
+    }
+    match object.get("body") {
+        Some(body) => Some(body.as_str()?.as_bytes().to_vec()),
+        None => Some(Vec::new()),
+    }
+}
+
+/// Returns the JSON representation of headers.
+fn headers_to_json(headers: &[(String, String)]) -> Value {
+    headers
+        .iter()
+        .map(|(name, value)| json!([name, value]))
+        .collect()
+}
+
+/// Returns the headers of the JSON representation returned by [`headers_to_json`].
+fn headers_from_json(headers: Option<&Value>) -> Option<Vec<(String, String)>> {
+    let Some(headers) = headers else {
+        return Some(Vec::new());
+    };
+    headers
+        .as_array()?
+        .iter()
+        .map(|header| {
+            let name = header.get(0)?.as_str()?;
+            let value = header.get(1)?.as_str()?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Returns the JSON representation of a cassette.
+fn cassette_to_json(interactions: &[Interaction]) -> Value {
+    let interactions: Vec<Value> = interactions
+        .iter()
+        .map(|interaction| {
+            let request = &interaction.request;
+            let mut req = Map::new();
+            req.insert("method".into(), request.method.as_str().into());
+            req.insert("target".into(), request.target.as_str().into());
+            req.insert("headers".into(), headers_to_json(&request.headers));
+            body_to_json(&request.body, &mut req);
+            let response = &interaction.response;
+            let mut res = Map::new();
+            res.insert("status".into(), response.status.into());
+            res.insert("headers".into(), headers_to_json(&response.headers));
+            body_to_json(&response.body, &mut res);
+            json!({ "request": req, "response": res })
+        })
+        .collect();
+    json!({ "interactions": interactions })
+}
+
+/// Returns the interactions of the JSON representation returned by [`cassette_to_json`].
+fn cassette_from_json(cassette: &Value) -> Option<Vec<Interaction>> {
+    cassette
+        .get("interactions")?
+        .as_array()?
+        .iter()
+        .map(|interaction| {
+            let req = interaction.get("request")?;
+            let res = interaction.get("response")?;
+            Some(Interaction {
+                request: Request {
+                    method: req.get("method")?.as_str()?.to_string(),
+                    target: req.get("target")?.as_str()?.to_string(),
+                    headers: headers_from_json(req.get("headers"))?,
+                    body: body_from_json(req)?,
+                },
+                response: Recorded {
+                    status: u16::try_from(res.get("status")?.as_u64()?).ok()?,
+                    headers: headers_from_json(res.get("headers"))?,
+                    body: body_from_json(res)?,
+                },
+                replayed: false,
+            })
+        })
+        .collect()
+}
+
+/// Writes a cassette to the given path.
+fn save(path: &Path, interactions: &[Interaction]) -> io::Result<()> {
+    let json = serde_json::to_vec_pretty(&cassette_to_json(interactions))?;
+    fs::write(path, json)
+}
+
+/// The interactions and requests shared between a [`VcrServer`] and its connections.
+#[derive(Debug, Default)]
+struct State {
+    interactions: Vec<Interaction>,
+    requests: Vec<Request>,
+    unmatched: Vec<Request>,
+}
+
+/// Locks the state, ignoring the poisoning caused by a panicking connection.
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// An HTTP/1.1 server recording the interactions with a real server into a cassette file, or
+/// replaying them from it, which shuts down on drop.
+///
+/// A server created through [`record`] forwards every request to the upstream server and writes
+/// each request together with its response to the cassette as soon as it is answered. A server
+/// created through [`replay`] never connects to the network: each request is answered with the
+/// response of the first recorded request it matches which has not been replayed yet, or of the
+/// last one it matches once all have been replayed, so that tests run hermetically and
+/// deterministically against the recorded traffic. Requests matching no recorded request are
+/// answered with `404 Not Found`, and are available through [`unmatched`].
+///
+/// The cassette is a JSON file, which may be reviewed and edited by hand, e.g., to remove
+/// credentials. Bodies are stored as text, or as hex if they are not valid UTF-8.
+///
+/// Only plaintext HTTP is supported: the upstream server must be reachable through an `http://`
+/// URL, since the crate has no TLS client. Interactions with an `https://` server may be recorded
+/// through a local plaintext proxy, e.g., a TLS-terminating reverse proxy, in front of it.
+///
+/// [`record`]: Self::record
+/// [`replay`]: Self::replay
+/// [`unmatched`]: Self::unmatched
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "json")]
+/// # {
+/// use std::io::{Read, Write};
+/// use std::net::{SocketAddr, TcpStream};
+///
+/// use regd_testing::fs::TempDir;
+/// use regd_testing::net::{GET, MockHttpServer, VcrServer};
+///
+/// fn get(addr: SocketAddr, target: &str) -> String {
+///     let mut client = TcpStream::connect(addr).unwrap();
+///     write!(client, "GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+///     let mut response = String::new();
+///     client.read_to_string(&mut response).unwrap();
+///     response
+/// }
+///
+/// let dir = TempDir::new();
+/// let cassette = dir.path().join("registry.json");
+/// {
+///     let registry = MockHttpServer::new();
+///     registry.when(GET, "/v1/crates/foo").respond(200, r#"{"version":"1.0.0"}"#);
+///     let vcr = VcrServer::record(&registry.url(), &cassette);
+///     assert!(get(vcr.addr(), "/v1/crates/foo").ends_with(r#"{"version":"1.0.0"}"#));
+/// }
+///
+/// let vcr = VcrServer::replay(&cassette);
+/// let response = get(vcr.addr(), "/v1/crates/foo");
+/// assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+/// assert!(response.ends_with(r#"{"version":"1.0.0"}"#));
+/// assert!(get(vcr.addr(), "/v1/crates/bar").starts_with("HTTP/1.1 404"));
+/// assert_eq!(vcr.unmatched()[0].target, "/v1/crates/bar");
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct VcrServer {
+    state: Arc<Mutex<State>>,
+    server: MockTcpServer,
+}
+
+impl VcrServer {
+    /// Starts a server recording the interactions with the given upstream server.
+    ///
+    /// # Parameters
+    /// - `upstream`: The URL of the upstream server, e.g., `http://127.0.0.1:8080/api`, whose
+    ///   path is prefixed to the target of every forwarded request.
+    /// - `cassette`: The path of the cassette, which is overwritten.
+    ///
+    /// # Returns
+    /// - A running [`VcrServer`] in recording mode.
+    ///
+    /// # Panics
+    /// - This function will panic if `upstream` is not an `http://` URL, e.g., an `https://` URL
+    ///   since TLS is not supported, if the cassette cannot be written, or if the loopback
+    ///   interface cannot be bound.
+    ///
+    /// # Notes
+    /// - Requests which cannot be forwarded are answered with `502 Bad Gateway` and are not
+    ///   recorded. Requests whose interaction cannot be written to the cassette are answered
+    ///   with `500 Internal Server Error`.
+    /// - The headers are recorded verbatim, including credentials such as `Authorization`.
+    pub fn record(upstream: &str, cassette: impl AsRef<Path>) -> Self {
+        let upstream = Upstream::parse(upstream)
+            .unwrap_or_else(|| panic!("cannot record from {upstream:?}: expected an http:// URL"));
+        let cassette: PathBuf = cassette.as_ref().to_path_buf();
+        save(&cassette, &[])
+            .unwrap_or_else(|e| panic!("cannot write cassette {}: {e}", cassette.display()));
+        let state = Arc::new(Mutex::new(State::default()));
+        let server = {
+            let state = Arc::clone(&state);
+            MockTcpServer::new(move |conn| {
+                let request = match read_request(conn) {
+                    Ok(request) => request,
+                    Err(status) => {
+                        let _ = Response::new(status).write_to(conn, "");
+                        return;
+                    }
+                };
+                let method = request.method.clone();
+                let response = match upstream.forward(&request) {
+                    Ok(recorded) => {
+                        let mut state = lock(&state);
+                        state.interactions.push(Interaction {
+                            request: request.clone(),
+                            response: recorded.clone(),
+                            replayed: false,
+                        });
+                        match save(&cassette, &state.interactions) {
+                            Ok(()) => recorded.to_response(),
+                            Err(e) => Response::new(500)
+                                .body(format!("cannot write cassette {}: {e}", cassette.display())),
+                        }
+                    }
+                    Err(e) => Response::new(502).body(format!("cannot forward request: {e}")),
+                };
+                lock(&state).requests.push(request);
+                let _ = response.write_to(conn, &method);
+            })
+        };
+        Self { state, server }
+    }
+
+    /// Starts a server replaying the interactions of the given cassette, matching the requests
+    /// by method, path and query string.
+    ///
+    /// This function is equivalent to [`replay_with`](Self::replay_with) with
+    /// [`MatchRule::Method`], [`MatchRule::Path`] and [`MatchRule::Query`].
+    ///
+    /// # Panics
+    /// - This function will panic if the cassette cannot be read or parsed, or if the loopback
+    ///   interface cannot be bound.
+    pub fn replay(cassette: impl AsRef<Path>) -> Self {
+        Self::replay_with(
+            cassette,
+            &[MatchRule::Method, MatchRule::Path, MatchRule::Query],
+        )
+    }
+
+    /// Starts a server replaying the interactions of the given cassette, matching the requests
+    /// by the given rules.
+    ///
+    /// # Parameters
+    /// - `cassette`: The path of a cassette written by a server created through
+    ///   [`record`](Self::record).
+    /// - `rules`: The rules a request must satisfy to match a recorded request. A request matches
+    ///   every recorded request if `rules` is empty.
+    ///
+    /// # Returns
+    /// - A running [`VcrServer`] in replaying mode.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "json")]
+    /// # {
+    /// use regd_testing::fs::TempFile;
+    /// use regd_testing::net::{MatchRule, VcrServer};
+    ///
+    /// let cassette = TempFile::with_contents(r#"{"interactions": []}"#);
+    /// let rules = [MatchRule::Method, MatchRule::Path, MatchRule::Header("Accept".into())];
+    /// let vcr = VcrServer::replay_with(cassette.path(), &rules);
+    /// assert!(vcr.requests().is_empty());
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// - This function will panic if the cassette cannot be read or parsed, or if the loopback
+    ///   interface cannot be bound.
+    pub fn replay_with(cassette: impl AsRef<Path>, rules: &[MatchRule]) -> Self {
+        let cassette = cassette.as_ref();
+        let json = fs::read(cassette)
+            .unwrap_or_else(|e| panic!("cannot read cassette {}: {e}", cassette.display()));
+        let interactions = serde_json::from_slice(&json)
+            .ok()
+            .as_ref()
+            .and_then(cassette_from_json)
+            .unwrap_or_else(|| panic!("cannot parse cassette {}", cassette.display()));
+        let state = Arc::new(Mutex::new(State {
+            interactions,
+            ..State::default()
+        }));
+        let rules = rules.to_vec();
+        let server = {
+            let state = Arc::clone(&state);
+            MockTcpServer::new(move |conn| {
+                let request = match read_request(conn) {
+                    Ok(request) => request,
+                    Err(status) => {
+                        let _ = Response::new(status).write_to(conn, "");
+                        return;
+                    }
+                };
+                let method = request.method.clone();
+                let response = {
+                    let mut state = lock(&state);
+                    let matching: Vec<usize> = state
+                        .interactions
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, interaction)| {
+                            rules
+                                .iter()
+                                .all(|rule| rule.matches(&interaction.request, &request))
+                        })
+                        .map(|(i, _)| i)
+                        .collect();
+                    let chosen = matching
+                        .iter()
+                        .copied()
+                        .find(|&i| !state.interactions[i].replayed)
+                        .or(matching.last().copied());
+                    let response = match chosen {
+                        Some(i) => {
+                            state.interactions[i].replayed = true;
+                            state.interactions[i].response.to_response()
+                        }
+                        None => {
+                            state.unmatched.push(request.clone());
+                            Response::new(404).body(format!(
+                                "no recorded interaction matches {} {}",
+                                request.method, request.target
+                            ))
+                        }
+                    };
+                    state.requests.push(request);
+                    response
+                };
+                let _ = response.write_to(conn, &method);
+            })
+        };
+        Self { state, server }
+    }
+
+    /// Returns the address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.server.addr()
+    }
+
+    /// Returns the base URL of the server, e.g., `http://127.0.0.1:49152`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr())
+    }
+
+    /// Returns every request received so far, in the order received.
+    pub fn requests(&self) -> Vec<Request> {
+        lock(&self.state).requests.clone()
+    }
+
+    /// Returns every request which matched no recorded request so far, in the order received.
+    ///
+    /// This is always empty for a server created through [`record`](Self::record).
+    pub fn unmatched(&self) -> Vec<Request> {
+        lock(&self.state).unmatched.clone()
+    }
+}
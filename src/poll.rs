@@ -0,0 +1,73 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains the polling loop shared by the waiting utilities.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The delay before the second attempt; it doubles after every failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(1);
+
+/// The maximum delay between two attempts.
+const MAX_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Polls `done` with exponential backoff until it returns `true` or `timeout` elapses.
+///
+/// `done` is always called at least once, and once more right at the deadline. On timeout, the
+/// elapsed time and the number of attempts are returned.
+pub(crate) fn poll(
+    timeout: Duration,
+    mut done: impl FnMut() -> bool,
+) -> Result<(), (Duration, u32)> {
+    let start = Instant::now();
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        if done() {
+            return Ok(());
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Err((elapsed, attempts));
+        }
+        thread::sleep(backoff.min(timeout - elapsed));
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Polls `done` every `interval` until it returns `true` or `timeout` elapses.
+///
+/// `done` is always called at least once, and once more right at the deadline. On timeout, the
+/// elapsed time and the number of attempts are returned.
+pub(crate) fn poll_every(
+    timeout: Duration,
+    interval: Duration,
+    mut done: impl FnMut() -> bool,
+) -> Result<(), (Duration, u32)> {
+    let start = Instant::now();
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        if done() {
+            return Ok(());
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Err((elapsed, attempts));
+        }
+        thread::sleep(interval.min(timeout - elapsed));
+    }
+}
@@ -0,0 +1,804 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of child processes.
+//!
+//! The utilities are meant for black-box testing of binaries: a process is run to completion
+//! with its standard streams captured, and killed together with its descendants if it does not
+//! finish in time. Long-running processes may also be spawned and signaled, e.g., to test
+//! graceful shutdowns. On Unix, fake executables may be put onto `PATH`, so that code shelling out
+//! to system tools can be tested without depending on them.
+
+use std::ffi::OsStr;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+use crate::env::{self, EnvGuard};
+use crate::error::{Error, Result};
+#[cfg(unix)]
+use crate::fs::TempDir;
+
+/// A builder of a child process, which is created through [`run`].
+pub struct Run {
+    command: Command,
+    stdin: Option<Vec<u8>>,
+    timeout: Option<Duration>,
+}
+
+impl Run {
+    /// Adds an argument.
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.command.arg(arg);
+        self
+    }
+
+    /// Adds several arguments.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.command.args(args);
+        self
+    }
+
+    /// Sets an environment variable of the process.
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.command.env(key, value);
+        self
+    }
+
+    /// Removes an environment variable inherited by the process.
+    pub fn env_remove(mut self, key: impl AsRef<OsStr>) -> Self {
+        self.command.env_remove(key);
+        self
+    }
+
+    /// Clears every environment variable inherited by the process.
+    pub fn env_clear(mut self) -> Self {
+        self.command.env_clear();
+        self
+    }
+
+    /// Sets the working directory of the process.
+    pub fn current_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.command.current_dir(dir);
+        self
+    }
+
+    /// Sets the bytes written to the standard input of the process, which is closed afterwards.
+    ///
+    /// The standard input is empty by default.
+    pub fn stdin(mut self, bytes: impl Into<Vec<u8>>) -> Self {
+        self.stdin = Some(bytes.into());
+        self
+    }
+
+    /// Sets the time after which the process and its descendants are killed.
+    ///
+    /// There is no timeout by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Runs the process to completion, or until the timeout elapses.
+    ///
+    /// # Returns
+    /// - The [`ProcOutput`] of the process.
+    ///
+    /// # Panics
+    /// - This method will panic if the process cannot be spawned or waited for.
+    pub fn output(self) -> ProcOutput {
+        self.spawn().wait()
+    }
+
+    /// Spawns the process without waiting for it, e.g., to signal a long-running daemon.
+    ///
+    /// # Returns
+    /// - A [`Running`] process whose standard streams are captured in the background.
+    ///
+    /// # Panics
+    /// - This method will panic if the process cannot be spawned.
+    pub fn spawn(mut self) -> Running {
+        self.command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut self.command, 0);
+        let program = self.command.get_program().to_string_lossy().into_owned();
+        let start = Instant::now();
+        let mut child = self
+            .command
+            .spawn()
+            .unwrap_or_else(|e| panic!("cannot spawn {program}: {e}"));
+        let stdin = child.stdin.take().map(|mut pipe| {
+            let bytes = self.stdin.unwrap_or_default();
+            thread::spawn(move || {
+                let _ = pipe.write_all(&bytes);
+            })
+        });
+        let stdout = Pipe::drain(child.stdout.take());
+        let stderr = Pipe::drain(child.stderr.take());
+        Running {
+            child,
+            program,
+            start,
+            timeout: self.timeout,
+            stdin,
+            stdout,
+            stderr,
+            finished: false,
+        }
+    }
+}
+
+/// A captured output stream of a child process, which is read to its end on a new thread.
+struct Pipe {
+    bytes: Arc<Mutex<Vec<u8>>>,
+    reader: Option<JoinHandle<()>>,
+}
+
+impl Pipe {
+    fn drain(pipe: Option<impl Read + Send + 'static>) -> Self {
+        let bytes = Arc::new(Mutex::new(Vec::new()));
+        let reader = pipe.map(|mut pipe| {
+            let bytes = Arc::clone(&bytes);
+            thread::spawn(move || {
+                let mut buf = [0; 4096];
+                while let Ok(n @ 1..) = pipe.read(&mut buf) {
+                    bytes
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .extend_from_slice(&buf[..n]);
+                }
+            })
+        });
+        Self { bytes, reader }
+    }
+
+    /// Returns the bytes read so far.
+    fn snapshot(&self) -> Vec<u8> {
+        self.bytes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Waits for the end of the stream and returns every byte read.
+    fn join(&mut self) -> Vec<u8> {
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        self.snapshot()
+    }
+}
+
+/// A child process spawned through [`Run::spawn`].
+///
+/// The process and its descendants are killed when dropped without being waited for.
+///
+/// # Examples
+/// ```
+/// # #[cfg(unix)]
+/// # {
+/// use std::time::Duration;
+///
+/// use regd_testing::proc;
+///
+/// let mut process = proc::run("sh")
+///     .args(["-c", "echo ready; read line; echo \"got $line\""])
+///     .stdin("input\n")
+///     .spawn();
+/// process
+///     .wait_for_stdout("ready", Duration::from_secs(10))
+///     .unwrap();
+/// process.wait().assert_success().assert_stdout_contains("got input");
+/// # }
+/// ```
+pub struct Running {
+    child: Child,
+    program: String,
+    start: Instant,
+    timeout: Option<Duration>,
+    stdin: Option<JoinHandle<()>>,
+    stdout: Pipe,
+    stderr: Pipe,
+    finished: bool,
+}
+
+impl Running {
+    /// Returns the OS-assigned identifier of the process.
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Returns the bytes written to the standard output so far.
+    pub fn stdout(&self) -> Vec<u8> {
+        self.stdout.snapshot()
+    }
+
+    /// Returns the bytes written to the standard error so far.
+    pub fn stderr(&self) -> Vec<u8> {
+        self.stderr.snapshot()
+    }
+
+    /// Sends a signal to the process.
+    ///
+    /// # Errors
+    /// - An I/O error if the process cannot be signaled; see [`send_signal`].
+    pub fn signal(&mut self, signal: Signal) -> io::Result<()> {
+        send_signal(&mut self.child, signal)
+    }
+
+    /// Waits until the standard output contains the given substring, e.g., a readiness message.
+    ///
+    /// # Parameters
+    /// - `substring`: The substring to wait for.
+    /// - `timeout`: The maximum time to wait.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the substring is written.
+    ///
+    /// # Errors
+    /// - [`Error::Timeout`] if the substring is not written before `timeout` elapses, carrying the
+    ///   standard output so far.
+    pub fn wait_for_stdout(&mut self, substring: &str, timeout: Duration) -> Result<()> {
+        crate::poll::poll(timeout, || {
+            String::from_utf8_lossy(&self.stdout()).contains(substring)
+        })
+        .map_err(|(elapsed, attempts)| Error::Timeout {
+            condition: format!("stdout of {} to contain {substring:?}", self.program),
+            elapsed,
+            attempts,
+            last_observed: format!("{:?}", String::from_utf8_lossy(&self.stdout())),
+        })
+    }
+
+    /// Waits for the process to exit, killing it together with its descendants once the timeout
+    /// of the [`Run`], counted from the spawn, elapses.
+    ///
+    /// # Returns
+    /// - The [`ProcOutput`] of the process.
+    ///
+    /// # Panics
+    /// - This method will panic if the process cannot be waited for.
+    pub fn wait(mut self) -> ProcOutput {
+        let timeout = self
+            .timeout
+            .map(|timeout| timeout.saturating_sub(self.start.elapsed()));
+        self.finish(timeout)
+    }
+
+    /// Waits for the process to exit for at most `timeout`, then collects its output.
+    fn finish(&mut self, timeout: Option<Duration>) -> ProcOutput {
+        self.finished = true;
+        let (status, timed_out) = wait(&mut self.child, timeout)
+            .unwrap_or_else(|e| panic!("cannot wait for {}: {e}", self.program));
+        let duration = self.start.elapsed();
+        if let Some(stdin) = self.stdin.take() {
+            let _ = stdin.join();
+        }
+        ProcOutput {
+            status,
+            stdout: self.stdout.join(),
+            stderr: self.stderr.join(),
+            duration,
+            timed_out,
+        }
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        if !self.finished {
+            kill_tree(&mut self.child);
+            let _ = self.child.wait();
+        }
+    }
+}
+
+/// Waits for a child process, killing it together with its descendants once `timeout` elapses.
+///
+/// Returns the exit status and whether the process was killed.
+fn wait(child: &mut Child, timeout: Option<Duration>) -> std::io::Result<(ExitStatus, bool)> {
+    let Some(timeout) = timeout else {
+        return child.wait().map(|status| (status, false));
+    };
+    let mut status = None;
+    let mut error = None;
+    let _ = crate::poll::poll(timeout, || match child.try_wait() {
+        Ok(exited) => {
+            status = exited;
+            status.is_some()
+        }
+        Err(e) => {
+            error = Some(e);
+            true
+        }
+    });
+    if let Some(e) = error {
+        return Err(e);
+    }
+    if let Some(status) = status {
+        return Ok((status, false));
+    }
+    kill_tree(child);
+    child.wait().map(|status| (status, true))
+}
+
+/// Kills a child process and, on Unix, every process of its process group.
+fn kill_tree(child: &mut Child) {
+    #[cfg(unix)]
+    if let Ok(pid) = libc::pid_t::try_from(child.id()) {
+        // SAFETY: The child was spawned as the leader of its own process group, which is not
+        // reaped yet, so that the group cannot have been reused.
+        unsafe {
+            libc::kill(-pid, libc::SIGKILL);
+        }
+    }
+    let _ = child.kill();
+}
+
+/// The outcome of a child process run through [`run`].
+#[derive(Clone, Debug)]
+pub struct ProcOutput {
+    /// The exit status of the process.
+    pub status: ExitStatus,
+    /// The bytes written to the standard output.
+    pub stdout: Vec<u8>,
+    /// The bytes written to the standard error.
+    pub stderr: Vec<u8>,
+    /// The time elapsed between spawning the process and its exit.
+    pub duration: Duration,
+    /// Whether the process was killed because the timeout elapsed.
+    pub timed_out: bool,
+}
+
+impl ProcOutput {
+    /// Returns the standard output as a string, replacing invalid UTF-8 sequences.
+    pub fn stdout_string(&self) -> String {
+        String::from_utf8_lossy(&self.stdout).into_owned()
+    }
+
+    /// Returns the standard error as a string, replacing invalid UTF-8 sequences.
+    pub fn stderr_string(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).into_owned()
+    }
+
+    /// Asserts that the process exited successfully.
+    ///
+    /// # Panics
+    /// - This method will panic with the captured output if the process failed or timed out.
+    #[track_caller]
+    pub fn assert_success(&self) -> &Self {
+        if self.timed_out || !self.status.success() {
+            panic!("process did not succeed\n{self}");
+        }
+        self
+    }
+
+    /// Asserts that the process exited with a failure.
+    ///
+    /// # Panics
+    /// - This method will panic with the captured output if the process succeeded or timed out.
+    #[track_caller]
+    pub fn assert_failure(&self) -> &Self {
+        if self.timed_out || self.status.success() {
+            panic!("process did not fail\n{self}");
+        }
+        self
+    }
+
+    /// Asserts that the process exited with the given code.
+    ///
+    /// # Panics
+    /// - This method will panic with the captured output if the process exited with another code,
+    ///   was killed by a signal, or timed out.
+    #[track_caller]
+    pub fn assert_code(&self, code: i32) -> &Self {
+        if self.timed_out || self.status.code() != Some(code) {
+            panic!("process did not exit with code {code}\n{self}");
+        }
+        self
+    }
+
+    /// Asserts that the process was killed because the timeout elapsed.
+    ///
+    /// # Panics
+    /// - This method will panic with the captured output if the process exited in time.
+    #[track_caller]
+    pub fn assert_timed_out(&self) -> &Self {
+        if !self.timed_out {
+            panic!("process did not time out\n{self}");
+        }
+        self
+    }
+
+    /// Asserts that the standard output contains the given substring.
+    ///
+    /// # Panics
+    /// - This method will panic with the captured output if the substring is not found.
+    #[track_caller]
+    pub fn assert_stdout_contains(&self, substring: &str) -> &Self {
+        if !self.stdout_string().contains(substring) {
+            panic!("stdout does not contain {substring:?}\n{self}");
+        }
+        self
+    }
+
+    /// Asserts that the standard error contains the given substring.
+    ///
+    /// # Panics
+    /// - This method will panic with the captured output if the substring is not found.
+    #[track_caller]
+    pub fn assert_stderr_contains(&self, substring: &str) -> &Self {
+        if !self.stderr_string().contains(substring) {
+            panic!("stderr does not contain {substring:?}\n{self}");
+        }
+        self
+    }
+}
+
+impl fmt::Display for ProcOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.timed_out {
+            writeln!(f, "status: timed out after {:?}", self.duration)?;
+        } else {
+            writeln!(f, "status: {} after {:?}", self.status, self.duration)?;
+        }
+        writeln!(f, "stdout:\n{}", self.stdout_string())?;
+        write!(f, "stderr:\n{}", self.stderr_string())
+    }
+}
+
+/// Creates a builder running the given program.
+///
+/// The program is resolved through `PATH` like [`Command::new`], and inherits the environment
+/// and the working directory of the current process unless overridden.
+///
+/// # Parameters
+/// - `program`: The name or the path of the program.
+///
+/// # Returns
+/// - A [`Run`] whose [`output`](Run::output) runs the process.
+///
+/// # Examples
+/// ```
+/// # #[cfg(unix)]
+/// # {
+/// use std::time::Duration;
+///
+/// use regd_testing::proc;
+///
+/// let output = proc::run("sh")
+///     .args(["-c", "read line; echo \"got $line\"; echo \"$GREETING\" >&2"])
+///     .env("GREETING", "hello")
+///     .stdin("input\n")
+///     .timeout(Duration::from_secs(10))
+///     .output();
+/// output
+///     .assert_success()
+///     .assert_stdout_contains("got input")
+///     .assert_stderr_contains("hello");
+///
+/// let output = proc::run("sh")
+///     .args(["-c", "sleep 30 & sleep 30"])
+///     .timeout(Duration::from_millis(100))
+///     .output();
+/// output.assert_timed_out();
+/// assert!(output.duration < Duration::from_secs(10));
+/// # }
+/// ```
+pub fn run(program: impl AsRef<OsStr>) -> Run {
+    Run {
+        command: Command::new(program),
+        stdin: None,
+        timeout: None,
+    }
+}
+
+/// A fake executable put onto `PATH`, which is created through [`fake_bin`].
+///
+/// Every invocation of the executable records its arguments, writes the configured standard
+/// output, and exits with the configured code. `PATH` is restored and the executable is removed
+/// when the fake is dropped.
+///
+/// # Notes
+/// - `PATH` is shared by the whole process, so that the fake is also found by the other threads,
+///   including concurrently running tests. The fake therefore holds the process-wide environment
+///   lock of [`env`] until dropped, which makes every other change of the environment made
+///   through [`env`] or another fake wait, and several fakes of the same thread must be dropped in
+///   the reverse order of their creation. Code reading `PATH` directly is not serialized.
+#[cfg(unix)]
+#[must_use = "the executable is removed from `PATH` as soon as the fake is dropped"]
+pub struct FakeBin {
+    _path: EnvGuard,
+    dir: TempDir,
+    name: String,
+}
+
+#[cfg(unix)]
+impl FakeBin {
+    /// Sets the exit code and the standard output of every later invocation.
+    ///
+    /// # Panics
+    /// - This method will panic if the executable cannot be rewritten.
+    pub fn responds_with(self, exit_code: i32, stdout: impl AsRef<[u8]>) -> Self {
+        let out = self
+            .dir
+            .create_file(format!("{}.stdout", self.name), stdout);
+        let script = format!(
+            "#!/bin/sh\nprintf '%s\\0' \"$#\" \"$@\" >> {}\ncat {}\nexit {exit_code}\n",
+            quote(&self.calls_path()),
+            quote(&out),
+        );
+        let path = self.dir.create_file(&self.name, script);
+        let mut permissions = std::fs::metadata(&path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {e}", path.display()))
+            .permissions();
+        std::os::unix::fs::PermissionsExt::set_mode(&mut permissions, 0o755);
+        std::fs::set_permissions(&path, permissions)
+            .unwrap_or_else(|e| panic!("cannot make {} executable: {e}", path.display()));
+        self
+    }
+
+    /// Returns the path of the executable.
+    pub fn path(&self) -> PathBuf {
+        self.dir.path().join(&self.name)
+    }
+
+    /// Returns the arguments of every invocation so far, excluding the name of the executable.
+    pub fn calls(&self) -> Vec<Vec<String>> {
+        let recorded = std::fs::read(self.calls_path()).unwrap_or_default();
+        let mut tokens = recorded
+            .split(|&b| b == 0)
+            .map(|token| String::from_utf8_lossy(token).into_owned());
+        let mut calls = Vec::new();
+        while let Some(count) = tokens.next().and_then(|count| count.parse().ok()) {
+            calls.push(tokens.by_ref().take(count).collect());
+        }
+        calls
+    }
+
+    /// Asserts that some invocation had exactly the given arguments.
+    ///
+    /// # Panics
+    /// - This method will panic with every recorded invocation if none matches.
+    #[track_caller]
+    pub fn assert_called_with(&self, args: &[&str]) {
+        let calls = self.calls();
+        if !calls.iter().any(|call| call == args) {
+            panic!(
+                "{} was not called with {args:?}; calls: {calls:?}",
+                self.name
+            );
+        }
+    }
+
+    fn calls_path(&self) -> PathBuf {
+        self.dir.path().join(format!("{}.calls", self.name))
+    }
+}
+
+/// Quotes a path for a POSIX shell.
+#[cfg(unix)]
+fn quote(path: &Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', "'\\''"))
+}
+
+/// Puts a fake executable of the given name in front of `PATH`.
+///
+/// The executable succeeds without output until configured through
+/// [`responds_with`](FakeBin::responds_with). `PATH` is changed for the whole process, and
+/// restored once the returned [`FakeBin`] is dropped; see its notes.
+///
+/// # Parameters
+/// - `name`: The name of the executable, e.g., `git`.
+///
+/// # Returns
+/// - A [`FakeBin`] recording the arguments of every invocation.
+///
+/// # Examples
+/// ```
+/// # #[cfg(unix)]
+/// # {
+/// use regd_testing::proc;
+///
+/// let git = proc::fake_bin("git").responds_with(0, "main\n");
+/// let output = proc::run("git").args(["branch", "--show-current"]).output();
+/// output.assert_success();
+/// assert_eq!(output.stdout_string(), "main\n");
+/// git.assert_called_with(&["branch", "--show-current"]);
+///
+/// let git = git.responds_with(128, "");
+/// proc::run("git").arg("push").output().assert_code(128);
+/// assert_eq!(git.calls().len(), 2);
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the executable cannot be written or `PATH` cannot be built.
+#[cfg(unix)]
+pub fn fake_bin(name: &str) -> FakeBin {
+    let dir = TempDir::new();
+    // Holds the lock while reading `PATH`, so that no other change is lost before it is replaced.
+    let _lock = env::ENV_LOCK.lock();
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let path = std::env::join_paths(
+        std::iter::once(dir.path().to_path_buf()).chain(std::env::split_paths(&path)),
+    )
+    .unwrap_or_else(|e| panic!("cannot prepend {} to PATH: {e}", dir.path().display()));
+    FakeBin {
+        _path: env::set_scoped("PATH", path),
+        dir,
+        name: name.to_string(),
+    }
+    .responds_with(0, "")
+}
+
+/// A signal which may be sent to a child process.
+///
+/// On Windows, only [`Signal::Int`], [`Signal::Term`] and [`Signal::Kill`] are supported, and all
+/// of them terminate the process immediately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Signal {
+    /// `SIGHUP`, often used to reload a configuration.
+    Hup,
+    /// `SIGINT`, sent by `Ctrl-C`.
+    Int,
+    /// `SIGQUIT`.
+    Quit,
+    /// `SIGKILL`, which cannot be handled.
+    Kill,
+    /// `SIGTERM`, the conventional request for a graceful shutdown.
+    Term,
+    /// `SIGUSR1`.
+    Usr1,
+    /// `SIGUSR2`.
+    Usr2,
+}
+
+#[cfg(unix)]
+impl Signal {
+    fn as_raw(self) -> libc::c_int {
+        match self {
+            Self::Hup => libc::SIGHUP,
+            Self::Int => libc::SIGINT,
+            Self::Quit => libc::SIGQUIT,
+            Self::Kill => libc::SIGKILL,
+            Self::Term => libc::SIGTERM,
+            Self::Usr1 => libc::SIGUSR1,
+            Self::Usr2 => libc::SIGUSR2,
+        }
+    }
+}
+
+/// Sends a signal to a child process.
+///
+/// # Parameters
+/// - `child`: The process to signal.
+/// - `signal`: The signal to send.
+///
+/// # Returns
+/// - `Ok(())` if the signal was sent.
+///
+/// # Examples
+/// ```
+/// # #[cfg(unix)]
+/// # {
+/// use std::process::Command;
+///
+/// use regd_testing::proc::{self, Signal};
+///
+/// let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+/// proc::send_signal(&mut child, Signal::Term).unwrap();
+/// assert!(!child.wait().unwrap().success());
+/// # }
+/// ```
+///
+/// # Errors
+/// - An I/O error if the process cannot be signaled, or, on Windows, if the signal is not
+///   supported.
+pub fn send_signal(child: &mut Child, signal: Signal) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        let pid = libc::pid_t::try_from(child.id()).map_err(io::Error::other)?;
+        // SAFETY: The child is not reaped while borrowed, so that its identifier cannot have been
+        // reused.
+        if unsafe { libc::kill(pid, signal.as_raw()) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    match signal {
+        Signal::Int | Signal::Term | Signal::Kill => child.kill(),
+        _ => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("cannot send {signal:?} on this platform"),
+        )),
+    }
+}
+
+/// Asserts that a process exits within the given timeout after receiving a signal.
+///
+/// This is meant for testing graceful shutdowns: the process is usually spawned through
+/// [`Run::spawn`] and awaited through [`Running::wait_for_stdout`] until it is ready, so that
+/// its signal handlers are installed. The returned output may then be checked for the shutdown,
+/// e.g., through [`ProcOutput::assert_success`].
+///
+/// # Parameters
+/// - `process`: The running process.
+/// - `signal`: The signal to send.
+/// - `timeout`: The maximum time the process may take to exit.
+///
+/// # Returns
+/// - The [`ProcOutput`] of the process.
+///
+/// # Examples
+/// ```
+/// # #[cfg(unix)]
+/// # {
+/// use std::time::Duration;
+///
+/// use regd_testing::proc::{self, Signal};
+///
+/// let mut process = proc::run("sh")
+///     .args(["-c", "trap 'echo bye; exit 0' TERM; echo ready; while :; do sleep 0.01; done"])
+///     .spawn();
+/// process
+///     .wait_for_stdout("ready", Duration::from_secs(10))
+///     .unwrap();
+/// proc::assert_exits_on_signal(process, Signal::Term, Duration::from_secs(10))
+///     .assert_success()
+///     .assert_stdout_contains("bye");
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic with the captured output if the signal cannot be sent, or if the
+///   process does not exit in time, in which case it is killed.
+#[track_caller]
+pub fn assert_exits_on_signal(
+    mut process: Running,
+    signal: Signal,
+    timeout: Duration,
+) -> ProcOutput {
+    if let Err(e) = process.signal(signal) {
+        let output = process.finish(Some(Duration::ZERO));
+        panic!(
+            "cannot send {signal:?} to {}: {e}\n{output}",
+            process.program
+        );
+    }
+    let output = process.finish(Some(timeout));
+    if output.timed_out {
+        panic!(
+            "{} did not exit within {timeout:?} after {signal:?}\n{output}",
+            process.program
+        );
+    }
+    output
+}
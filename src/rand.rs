@@ -13,13 +13,133 @@
 // limitations under the License.
 
 //! This module contains a set of testing utilities of random value generators.
+//!
+//! Every generator draws from a thread-local random number generator which is seeded from the
+//! OS entropy by default, and which can be reseeded with [`set_seed`] to reproduce a test run.
 
 use std::fs;
 
-use rand::Rng;
 use rand::distr::uniform::{SampleRange, SampleUniform};
 use rand::distr::{Alphanumeric, StandardUniform};
 use rand::prelude::Distribution;
+use rand::{Rng, RngCore};
+
+use crate::error::{Error, Result};
+
+pub mod fake;
+pub mod generator;
+pub mod net;
+
+mod badfile;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod calendar;
+mod charset;
+mod check;
+mod corpus;
+mod csv;
+#[cfg(feature = "serde")]
+mod deserialize;
+mod distribution;
+mod edge;
+mod encoding;
+mod frame;
+mod generate;
+mod graph;
+mod http;
+mod id;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+mod interop;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "regex")]
+mod matching;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod path;
+mod rng;
+mod semver;
+mod shrink;
+mod text;
+mod time;
+#[cfg(feature = "chrono-tz")]
+mod timezone;
+mod unicode;
+mod utf8;
+mod web;
+mod weighted;
+mod wordlist;
+
+pub use self::badfile::{
+    BadFileOpts, ReservedPath, generate_badfile_in, generate_badpath, generate_socket_path,
+    reserve_badfile, reserve_badfile_in,
+};
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use self::calendar::{
+    CalendarDate, CalendarDateTime, CalendarTime, DateOpts, generate_date, generate_date_with,
+    generate_datetime, generate_datetime_with, generate_naive_time,
+};
+pub use self::charset::{Charset, generate_string};
+pub(crate) use self::check::derive_seed;
+pub use self::check::{CheckOpts, SEED_ENV, Testable, check, check_with, repeat};
+pub use self::corpus::{CORPUS_ENV, Corpus};
+pub use self::csv::{ColumnSpec, CsvOpts, generate_csv, generate_csv_with, write_csv};
+#[cfg(feature = "serde")]
+pub use self::deserialize::generate_deserialize;
+pub use self::distribution::{
+    generate_exponential, generate_normal, generate_poisson, generate_zipf,
+};
+pub use self::edge::{
+    Adjacent, AnyFloat, Bias, Edge, generate_edge, generate_float_any, generate_float_any_finite,
+    generate_range_biased,
+};
+pub use self::encoding::{generate_base64, generate_base64url, generate_hex};
+pub use self::frame::{Frame, FrameSpec, generate_corrupted_frames, generate_frames};
+pub use self::generate::{Generate, GenerateLen};
+pub use self::graph::{Graph, GraphKind, Tree, generate_graph, generate_tree};
+pub use self::http::{
+    HttpOpts, generate_http_request, generate_http_request_with, generate_http_response,
+    generate_http_response_with,
+};
+#[cfg(feature = "uuid")]
+pub use self::id::generate_uuid_v4;
+pub use self::id::{generate_uuid, generate_uuid_like};
+#[cfg(feature = "quickcheck")]
+pub use self::interop::generate_arbitrary;
+#[cfg(feature = "proptest")]
+pub use self::interop::{GenStrategy, GenValueTree};
+#[cfg(feature = "json")]
+pub use self::json::{JsonWeights, generate_json, generate_json_string, generate_json_with};
+#[cfg(feature = "regex")]
+pub use self::matching::generate_matching;
+pub use self::net::{generate_hostname, generate_mac};
+#[cfg(feature = "rayon")]
+pub use self::parallel::{generate_bytes_parallel, generate_vec_parallel};
+pub use self::path::{
+    PathOpts, TargetOs, generate_hostile_filename, generate_hostile_filename_for, generate_path,
+    generate_path_with,
+};
+pub use self::rng::{TestRng, install_failure_hook, rng, seed, set_seed};
+pub use self::semver::{
+    SemverOpts, generate_semver, generate_semver_sequence, generate_semver_with,
+};
+pub use self::shrink::Shrink;
+pub use self::text::{Markov, generate_paragraphs, generate_sentence};
+pub use self::time::{
+    generate_duration, generate_future_system_time, generate_past_system_time, generate_system_time,
+};
+#[cfg(feature = "chrono-tz")]
+pub use self::timezone::{
+    DateTimeEdge, DateTimeEdgeKind, generate_datetime_edge, generate_datetime_edge_of,
+};
+pub use self::unicode::{UnicodeProfile, generate_unicode, generate_unicode_with};
+pub use self::utf8::{generate_invalid_utf8, generate_invalid_utf8_within};
+pub use self::web::{
+    EmailOpts, UrlOpts, generate_email, generate_email_with, generate_url, generate_url_with,
+};
+pub use self::weighted::{Weighted, WeightedBuilder, WeightedSampler, choose_weighted};
+pub use self::wordlist::{DEFAULT_WORDLIST, generate_from_wordlist, generate_readable_id};
+#[cfg(feature = "macros")]
+pub use regd_testing_macros::Generate;
 
 /// Generates a random value of type `T`.
 ///
@@ -43,7 +163,7 @@ pub fn generate<T>() -> T
 where
     StandardUniform: Distribution<T>,
 {
-    let mut rng = rand::rng();
+    let mut rng = rng();
     rng.random::<T>()
 }
 
@@ -76,15 +196,166 @@ where
     T: SampleUniform,
     R: SampleRange<T>,
 {
-    assert!(!range.is_empty(), "cannot sample empty range");
-    let mut rng = rand::rng();
-    rng.random_range(range)
+    try_generate_range(range).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Generates a random value of type `T` within the specified range without panicking.
+///
+/// This function is the non-panicking variant of [`generate_range`], which is useful when the
+/// range itself comes from other generated data.
+///
+/// # Parameters
+/// - `range`: The range from which to generate a random value.
+///
+/// # Returns
+/// - An `Result` containing a randomly generated value of type `T` within the specified range,
+///   or [`Error::EmptyRange`] if the provided range is empty.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x: i32 = regd_testing::rand::try_generate_range(10..20).unwrap();
+/// assert!((10..20).contains(&x));
+///
+/// let y = regd_testing::rand::try_generate_range(20..10);
+/// assert_eq!(y, Err(regd_testing::Error::EmptyRange));
+/// ```
+pub fn try_generate_range<T, R>(range: R) -> Result<T>
+where
+    T: SampleUniform,
+    R: SampleRange<T>,
+{
+    if range.is_empty() {
+        return Err(Error::EmptyRange);
+    }
+    let mut rng = rng();
+    Ok(rng.random_range(range))
+}
+
+/// Creates an infinite iterator of random values of type `T`.
+///
+/// This function lazily generates values in the same way as [`generate`], so that streams of
+/// random values can be combined with the iterator adapters, e.g., `take`, `zip` and `filter`.
+///
+/// # Returns
+/// - An infinite iterator of randomly generated values of type `T`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let xs: Vec<u32> = regd_testing::rand::iter().take(8).collect();
+/// assert_eq!(xs.len(), 8);
+///
+/// let evens = regd_testing::rand::iter::<u8>().filter(|x| x % 2 == 0).take(4);
+/// assert!(evens.into_iter().all(|x| x % 2 == 0));
+/// ```
+pub fn iter<T>() -> impl Iterator<Item = T>
+where
+    StandardUniform: Distribution<T>,
+{
+    rng().sample_iter(StandardUniform)
+}
+
+/// Creates an infinite iterator of random values of type `T` within the specified range.
+///
+/// This function lazily generates values in the same way as [`generate_range`].
+///
+/// # Parameters
+/// - `range`: The range from which to generate random values.
+///
+/// # Returns
+/// - An infinite iterator of randomly generated values of type `T` within the specified range.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let cases = regd_testing::rand::iter_range(1..=6).zip(regd_testing::rand::iter_range(1..=6));
+/// for (x, y) in cases.take(16) {
+///     assert!((2..=12).contains(&(x + y)));
+/// }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the provided range is empty.
+pub fn iter_range<T, R>(range: R) -> impl Iterator<Item = T>
+where
+    T: SampleUniform,
+    R: SampleRange<T> + Clone,
+{
+    assert!(!range.is_empty(), "{}", Error::EmptyRange);
+    std::iter::repeat_with(move || generate_range(range.clone()))
+}
+
+/// Generates a random boolean which is `true` with the specified probability.
+///
+/// # Parameters
+/// - `p`: The probability of returning `true`. Must be in the range `[0.0, 1.0]`.
+///
+/// # Returns
+/// - `true` with probability `p`, `false` otherwise.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_bool(0.3);
+/// println!("Generated boolean: {}", x);
+///
+/// assert!(regd_testing::rand::generate_bool(1.0));
+/// assert!(!regd_testing::rand::generate_bool(0.0));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `p` is not in the range `[0.0, 1.0]`.
+pub fn generate_bool(p: f64) -> bool {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "cannot sample invalid probability"
+    );
+    let mut rng = rng();
+    rng.random_bool(p)
+}
+
+/// Generates a random `Option<T>` which is `Some` with the specified probability.
+///
+/// This function is useful for populating optional fields, e.g., "this field is present 30% of
+/// the time". The contained value is generated in the same way as [`generate`].
+///
+/// # Parameters
+/// - `p_some`: The probability of returning `Some`. Must be in the range `[0.0, 1.0]`.
+///
+/// # Returns
+/// - `Some` containing a randomly generated value of type `T` with probability `p_some`,
+///   `None` otherwise.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x: Option<u32> = regd_testing::rand::generate_option(0.3);
+/// println!("Generated option: {:?}", x);
+///
+/// let y: Option<u8> = regd_testing::rand::generate_option(0.0);
+/// assert!(y.is_none());
+/// ```
+///
+/// # Panics
+/// - This function will panic if `p_some` is not in the range `[0.0, 1.0]`.
+pub fn generate_option<T>(p_some: f64) -> Option<T>
+where
+    StandardUniform: Distribution<T>,
+{
+    generate_bool(p_some).then(generate)
 }
 
 /// Generates a vector of random bytes of the specified length.
 ///
 /// This function returns a `Vec<u8>` filled with random byte values (`u8`)
-/// generated using the thread-local random number generator.
+/// generated using the thread-local random number generator. The bytes are filled in bulk
+/// with [`fill_bytes`], which is considerably faster than generating them one by one.
 ///
 /// # Parameters
 /// - `length`: The number of random bytes to generate.
@@ -101,8 +372,30 @@ where
 /// println!("Random bytes: {:?}", x);
 /// ```
 pub fn generate_bytes(length: usize) -> Vec<u8> {
-    let mut rng = rand::rng();
-    (0..length).map(|_| rng.random::<u8>()).collect()
+    let mut bytes = vec![0; length];
+    fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Fills the given buffer with random bytes.
+///
+/// This function overwrites every byte of `buffer` in bulk using the thread-local random number
+/// generator, which makes it suitable for multi-megabyte buffers.
+///
+/// # Parameters
+/// - `buffer`: The buffer to fill with random bytes.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let mut x = [0u8; 1024];
+/// regd_testing::rand::fill_bytes(&mut x);
+/// assert!(x.iter().any(|b| *b != 0));
+/// ```
+pub fn fill_bytes(buffer: &mut [u8]) {
+    let mut rng = rng();
+    rng.fill_bytes(buffer);
 }
 
 /// Generates a random alphanumeric string of the specified length.
@@ -125,7 +418,7 @@ pub fn generate_bytes(length: usize) -> Vec<u8> {
 /// assert_eq!(x.len(), 12);
 /// ```
 pub fn generate_alphanumeric(length: usize) -> String {
-    let rng = rand::rng();
+    let rng = rng();
     rng.sample_iter(&Alphanumeric)
         .take(length)
         .map(char::from)
@@ -161,17 +454,94 @@ pub fn generate_alphanumeric(length: usize) -> String {
 /// - The function uses a loop and may retry multiple times if name collisions occur,
 ///   although with a reasonable `length` (e.g., ≥8), collisions are very unlikely.
 /// - The check is limited to the **current working directory**.
+/// - The name is only checked, never created, so that two tests may obtain the same name; see
+///   [`reserve_badfile`] for a race-free alternative.
 pub fn generate_badfile(length: usize) -> String {
-    assert!(length > 0, "cannot sample empty file name");
+    try_generate_badfile(length).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Generates a random alphanumeric filename that does not exist in the current directory
+/// without panicking.
+///
+/// This function is the non-panicking variant of [`generate_badfile`], which is useful when the
+/// length itself comes from other generated data.
+///
+/// # Parameters
+/// - `length`: The length of the generated filename.
+///
+/// # Returns
+/// - An `Result` containing a randomly generated, non-existent filename, or
+///   [`Error::EmptyFileName`] if `length == 0`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::try_generate_badfile(12).unwrap();
+/// assert!(std::fs::metadata(&x).is_err());
+///
+/// let y = regd_testing::rand::try_generate_badfile(0);
+/// assert_eq!(y, Err(regd_testing::Error::EmptyFileName));
+/// ```
+pub fn try_generate_badfile(length: usize) -> Result<String> {
+    if length == 0 {
+        return Err(Error::EmptyFileName);
+    }
     loop {
-        let rng = rand::rng();
+        let rng = rng();
         let filename: String = rng
             .sample_iter(&Alphanumeric)
             .take(length)
             .map(char::from)
             .collect();
         if fs::metadata(&filename).is_err() {
-            return filename;
+            return Ok(filename);
+        }
+    }
+}
+
+/// Generates a random uppercase environment variable name that is not set in the process
+/// environment.
+///
+/// The name starts with an uppercase ASCII letter followed by uppercase ASCII letters, digits and
+/// underscores, so that it is a valid identifier on every platform. This is useful for testing
+/// the code paths of missing configuration without clobbering real settings.
+///
+/// # Parameters
+/// - `length`: The length of the generated name. Must be greater than 0.
+///
+/// # Returns
+/// - A `String` representing a randomly generated name of an unset environment variable.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_badenv(16);
+/// assert_eq!(x.len(), 16);
+/// assert!(x.starts_with(|c: char| c.is_ascii_uppercase()));
+/// assert!(std::env::var_os(&x).is_none());
+/// ```
+///
+/// # Panics
+/// - This function will panic if `length == 0`.
+///
+/// # Notes
+/// - As with [`generate_badfile`], the name is only checked, never set, so that it may be set by
+///   other code afterwards.
+pub fn generate_badenv(length: usize) -> String {
+    const HEAD: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    const TAIL: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_";
+    assert!(length > 0, "cannot sample empty environment variable name");
+    loop {
+        let mut rng = rng();
+        let mut name = String::with_capacity(length);
+        name.push(char::from(HEAD[rng.random_range(0..HEAD.len())]));
+        for _ in 1..length {
+            name.push(char::from(TAIL[rng.random_range(0..TAIL.len())]));
+        }
+        if std::env::var_os(&name).is_none() {
+            return name;
         }
     }
 }
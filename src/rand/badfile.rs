@@ -0,0 +1,286 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of nonexistent file name generators.
+
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::mem::{self, ManuallyDrop};
+use std::path::{Path, PathBuf};
+
+/// Options of [`generate_badfile_in`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::BadFileOpts;
+///
+/// let opts = BadFileOpts {
+///     prefix: "report-".to_string(),
+///     suffix: ".json".to_string(),
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_badfile_in(&std::env::temp_dir(), &opts);
+/// let name = x.file_name().unwrap().to_str().unwrap();
+/// assert!(name.starts_with("report-") && name.ends_with(".json"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BadFileOpts {
+    /// The length of the random alphanumeric part of the file name. Defaults to 12.
+    pub length: usize,
+    /// The string prepended to the random part of the file name.
+    pub prefix: String,
+    /// The string appended to the random part of the file name, typically an extension such as
+    /// `.txt`.
+    pub suffix: String,
+}
+
+impl Default for BadFileOpts {
+    fn default() -> Self {
+        Self {
+            length: 12,
+            prefix: String::new(),
+            suffix: String::new(),
+        }
+    }
+}
+
+/// Generates a random path to a file that does not exist under the given directory.
+///
+/// This function repeatedly builds a file name from the prefix, a random alphanumeric part and
+/// the suffix of `opts`, and returns it joined to `dir` as soon as nothing, not even a dangling
+/// symbolic link, exists at that path. `dir` itself does not need to exist.
+///
+/// # Parameters
+/// - `dir`: The base directory of the generated path.
+/// - `opts`: The length of the random part, and the prefix and suffix of the file name.
+///
+/// # Returns
+/// - A `PathBuf` of the form `dir/{prefix}{random}{suffix}` that does not exist.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::BadFileOpts;
+///
+/// let dir = std::env::temp_dir();
+/// let x = regd_testing::rand::generate_badfile_in(&dir, &BadFileOpts::default());
+/// assert_eq!(x.parent(), Some(dir.as_path()));
+/// assert!(!x.exists());
+/// ```
+///
+/// # Panics
+/// - This function will panic if `opts.length == 0`.
+///
+/// # Notes
+/// - The path is only checked, never created, so that another test may obtain the same path
+///   before the caller creates it; see [`reserve_badfile_in`] for a race-free alternative.
+pub fn generate_badfile_in(dir: &Path, opts: &BadFileOpts) -> PathBuf {
+    assert!(opts.length > 0, "{}", crate::Error::EmptyFileName);
+    loop {
+        let name = format!(
+            "{}{}{}",
+            opts.prefix,
+            super::generate_alphanumeric(opts.length),
+            opts.suffix
+        );
+        let path = dir.join(name);
+        if fs::symlink_metadata(&path).is_err() {
+            return path;
+        }
+    }
+}
+
+/// Generates a random relative path of nested directories that does not exist.
+///
+/// The first component of the path does not exist in the current working directory, so that
+/// neither the path nor any of its parent directories exist. This is useful for testing error
+/// paths where the parent directory is missing, which a flat file name cannot reach.
+///
+/// # Parameters
+/// - `depth`: The number of components of the generated path. Must be greater than 0.
+/// - `length`: The length of each component. Must be greater than 0.
+///
+/// # Returns
+/// - A `PathBuf` of `depth` random alphanumeric components, e.g., `xK2f/9dQw/ZzP1`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_badpath(3, 4);
+/// assert_eq!(x.components().count(), 3);
+/// assert!(!x.exists());
+/// assert!(!x.parent().unwrap().exists());
+/// ```
+///
+/// # Panics
+/// - This function will panic if `depth == 0` or `length == 0`.
+pub fn generate_badpath(depth: usize, length: usize) -> PathBuf {
+    assert!(depth > 0, "cannot sample empty path");
+    let mut path = PathBuf::from(super::generate_badfile(length));
+    for _ in 1..depth {
+        path.push(super::generate_alphanumeric(length));
+    }
+    path
+}
+
+/// The maximum length of a socket path, including the terminating nul byte, on every supported
+/// platform; Linux allows 108 bytes while macOS and the BSDs only allow 104.
+const MAX_SOCKET_PATH_LEN: usize = 104;
+
+/// Generates a short path for a Unix domain socket that does not exist under the temporary
+/// directory.
+///
+/// The path is short enough to fit into `sun_path` on every supported platform. If the temporary
+/// directory is too deep for that, e.g., a long `TMPDIR`, `/tmp` is used instead.
+///
+/// # Returns
+/// - A `PathBuf` of the form `{temp_dir}/regd-{random}.sock` that does not exist.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_socket_path();
+/// assert!(x.as_os_str().len() < 104);
+/// assert!(!x.exists());
+/// ```
+pub fn generate_socket_path() -> PathBuf {
+    let opts = BadFileOpts {
+        length: 8,
+        prefix: "regd-".to_string(),
+        suffix: ".sock".to_string(),
+    };
+    let path = generate_badfile_in(&std::env::temp_dir(), &opts);
+    if path.as_os_str().len() < MAX_SOCKET_PATH_LEN {
+        return path;
+    }
+    generate_badfile_in(Path::new("/tmp"), &opts)
+}
+
+/// A guard of a file name claimed by creating an empty file, which is deleted on drop.
+///
+/// This guard is created through [`reserve_badfile`] or [`reserve_badfile_in`].
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let reserved = regd_testing::rand::reserve_badfile(12);
+/// let path = reserved.path().to_path_buf();
+/// assert!(path.exists());
+///
+/// drop(reserved);
+/// assert!(!path.exists());
+/// ```
+#[derive(Debug)]
+pub struct ReservedPath {
+    path: PathBuf,
+}
+
+impl ReservedPath {
+    /// Returns the path of the reserved file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Consumes the guard without deleting the reserved file and returns its path.
+    pub fn into_path(self) -> PathBuf {
+        let mut this = ManuallyDrop::new(self);
+        mem::take(&mut this.path)
+    }
+}
+
+impl AsRef<Path> for ReservedPath {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ReservedPath {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Reserves a random alphanumeric file name in the current directory.
+///
+/// Unlike [`generate_badfile`](super::generate_badfile), which only checks that a name is unused,
+/// this function atomically creates an empty file with that name, i.e., with `O_EXCL` semantics,
+/// so that two tests running concurrently never obtain the same name.
+///
+/// # Parameters
+/// - `length`: The length of the file name. Must be greater than 0.
+///
+/// # Returns
+/// - A [`ReservedPath`] guard which deletes the file when dropped.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::reserve_badfile(12);
+/// let y = regd_testing::rand::reserve_badfile(12);
+/// assert_ne!(x.path(), y.path());
+/// ```
+///
+/// # Panics
+/// - This function will panic if `length == 0`, or if the file cannot be created for any reason
+///   other than the name being taken, e.g., a permission error.
+pub fn reserve_badfile(length: usize) -> ReservedPath {
+    let opts = BadFileOpts {
+        length,
+        ..Default::default()
+    };
+    reserve_badfile_in(Path::new(""), &opts)
+}
+
+/// Reserves a random file name under the given directory.
+///
+/// This function is the race-free variant of [`generate_badfile_in`]: it atomically creates an
+/// empty file at the generated path, i.e., with `O_EXCL` semantics, and retries with another
+/// name if the path has been taken in the meantime.
+///
+/// # Parameters
+/// - `dir`: The directory in which to reserve the file. Must exist.
+/// - `opts`: The length of the random part, and the prefix and suffix of the file name.
+///
+/// # Returns
+/// - A [`ReservedPath`] guard which deletes the file when dropped.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::BadFileOpts;
+///
+/// let opts = BadFileOpts {
+///     suffix: ".lock".to_string(),
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::reserve_badfile_in(&std::env::temp_dir(), &opts);
+/// assert!(x.path().is_file());
+/// assert_eq!(x.path().extension().unwrap(), "lock");
+/// ```
+///
+/// # Panics
+/// - This function will panic if `opts.length == 0`, or if the file cannot be created for any
+///   reason other than the name being taken, e.g., if `dir` does not exist.
+pub fn reserve_badfile_in(dir: &Path, opts: &BadFileOpts) -> ReservedPath {
+    loop {
+        let path = generate_badfile_in(dir, opts);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => return ReservedPath { path },
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => panic!("cannot reserve file {}: {e}", path.display()),
+        }
+    }
+}
@@ -0,0 +1,476 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random calendar date and time generators.
+
+use std::ops::{Bound, RangeBounds};
+
+use rand::Rng;
+
+/// The probability with which an enabled calendar boundary is picked instead of a uniform date.
+const BOUNDARY_PROBABILITY: f64 = 0.25;
+
+/// The number of nanoseconds in a day.
+const NANOS_PER_DAY: i128 = 86_400_000_000_000;
+
+/// A trait for calendar date types which can be randomly generated.
+///
+/// This trait is implemented for `chrono::NaiveDate` with the `chrono` feature and for
+/// `time::Date` with the `time` feature.
+pub trait CalendarDate: Copy {
+    /// Creates a date from its year, month and day, returning `None` if it does not exist.
+    fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self>;
+
+    /// Returns the year, month and day of the date.
+    fn to_ymd(self) -> (i32, u32, u32);
+
+    /// Returns the number of days between the date and a fixed, type-specific epoch.
+    fn to_days(self) -> i64;
+
+    /// Creates a date from the number of days since the epoch used by [`to_days`].
+    ///
+    /// [`to_days`]: Self::to_days
+    fn from_days(days: i64) -> Self;
+}
+
+/// A trait for time-of-day types which can be randomly generated.
+///
+/// This trait is implemented for `chrono::NaiveTime` with the `chrono` feature and for
+/// `time::Time` with the `time` feature.
+pub trait CalendarTime: Copy {
+    /// Creates a time of day from the number of nanoseconds since midnight.
+    fn from_nanos_of_day(nanos: u64) -> Self;
+
+    /// Returns the number of nanoseconds since midnight.
+    fn to_nanos_of_day(self) -> u64;
+}
+
+/// A trait for date-time types which can be randomly generated.
+///
+/// This trait is implemented for `chrono::NaiveDateTime` with the `chrono` feature and for
+/// `time::PrimitiveDateTime` with the `time` feature.
+pub trait CalendarDateTime: Copy {
+    /// The date part of the date-time.
+    type Date: CalendarDate;
+    /// The time-of-day part of the date-time.
+    type Time: CalendarTime;
+
+    /// Creates a date-time from its date and time-of-day parts.
+    fn from_parts(date: Self::Date, time: Self::Time) -> Self;
+
+    /// Returns the date and time-of-day parts of the date-time.
+    fn to_parts(self) -> (Self::Date, Self::Time);
+}
+
+/// Options of [`generate_date_with`] and [`generate_datetime_with`].
+///
+/// Calendar arithmetic bugs usually live on a few special days, which a uniform date almost
+/// never hits. Each enabled kind of boundary is picked a quarter of the time, as long as one
+/// exists within the requested range.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use chrono::{Datelike, NaiveDate};
+/// use regd_testing::rand::DateOpts;
+///
+/// let opts = DateOpts {
+///     month_ends: true,
+///     ..Default::default()
+/// };
+/// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+/// let x: NaiveDate = regd_testing::rand::generate_date_with(start..end, &opts);
+/// assert_eq!(x.year(), 2024);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DateOpts {
+    /// Whether to favor February 29th of leap years.
+    pub leap_days: bool,
+    /// Whether to favor the last day of months.
+    pub month_ends: bool,
+}
+
+/// Returns the inclusive bounds of `range` mapped through `key`.
+fn bounds<T, R>(range: &R, key: impl Fn(&T) -> i128) -> (i128, i128)
+where
+    R: RangeBounds<T>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(start) => key(start),
+        Bound::Excluded(start) => key(start) + 1,
+        Bound::Unbounded => panic!("cannot sample unbounded range"),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => key(end),
+        Bound::Excluded(end) => key(end) - 1,
+        Bound::Unbounded => panic!("cannot sample unbounded range"),
+    };
+    assert!(start <= end, "cannot sample empty range");
+    (start, end)
+}
+
+/// Returns the last day of the month of the given year.
+fn month_end<D: CalendarDate>(year: i32, month: u32) -> Option<D> {
+    (28..=31)
+        .rev()
+        .find_map(|day| D::from_ymd(year, month, day))
+}
+
+/// Returns the first February 29th on or after the given year, looking a few years ahead.
+fn leap_day<D: CalendarDate>(year: i32) -> Option<D> {
+    (year..year.saturating_add(8)).find_map(|year| D::from_ymd(year, 2, 29))
+}
+
+/// Returns a boundary of `opts` near the given day number within `[start, end]`, if any is picked.
+///
+/// The boundaries are searched around `days`, i.e., the end of its month or the next leap day,
+/// so that favored dates remain spread over the whole range.
+fn favor_days<D: CalendarDate>(
+    rng: &mut impl Rng,
+    days: i64,
+    (start, end): (i64, i64),
+    opts: &DateOpts,
+) -> i64 {
+    let (year, month, _) = D::from_days(days).to_ymd();
+    let mut candidates = Vec::with_capacity(2);
+    if opts.leap_days && rng.random_bool(BOUNDARY_PROBABILITY) {
+        candidates.extend(leap_day::<D>(year));
+    }
+    if opts.month_ends && rng.random_bool(BOUNDARY_PROBABILITY) {
+        candidates.extend(month_end::<D>(year, month));
+    }
+    candidates
+        .into_iter()
+        .map(D::to_days)
+        .find(|candidate| (start..=end).contains(candidate))
+        .unwrap_or(days)
+}
+
+/// Generates a random calendar date within the specified range.
+///
+/// # Parameters
+/// - `range`: The range from which to generate a random date. Both ends must be bounded, e.g.,
+///   `start..end` or `start..=end`.
+///
+/// # Returns
+/// - A uniformly distributed date within the specified range.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2100, 1, 1).unwrap();
+/// let x: NaiveDate = regd_testing::rand::generate_date(start..end);
+/// assert!(start <= x && x < end);
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the provided range is empty or unbounded.
+pub fn generate_date<D, R>(range: R) -> D
+where
+    D: CalendarDate,
+    R: RangeBounds<D>,
+{
+    generate_date_with(range, &DateOpts::default())
+}
+
+/// Generates a random calendar date within the specified range, favoring the boundaries of `opts`.
+///
+/// # Parameters
+/// - `range`: The range from which to generate a random date. Both ends must be bounded, e.g.,
+///   `start..end` or `start..=end`.
+/// - `opts`: The calendar boundaries to favor.
+///
+/// # Returns
+/// - A randomly generated date within the specified range.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "time")]
+/// # {
+/// use regd_testing::rand::DateOpts;
+/// use time::{Date, Month};
+///
+/// let opts = DateOpts {
+///     leap_days: true,
+///     month_ends: true,
+/// };
+/// let start = Date::from_calendar_date(2024, Month::February, 1).unwrap();
+/// let end = Date::from_calendar_date(2024, Month::February, 29).unwrap();
+/// let x: Date = regd_testing::rand::generate_date_with(start..=end, &opts);
+/// assert_eq!(x.month(), Month::February);
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the provided range is empty or unbounded.
+pub fn generate_date_with<D, R>(range: R, opts: &DateOpts) -> D
+where
+    D: CalendarDate,
+    R: RangeBounds<D>,
+{
+    let (start, end) = bounds(&range, |date: &D| i128::from(date.to_days()));
+    let (start, end) = (start as i64, end as i64);
+    let mut rng = super::rng();
+    let days = rng.random_range(start..=end);
+    D::from_days(favor_days::<D>(&mut rng, days, (start, end), opts))
+}
+
+/// Generates a random date-time within the specified range.
+///
+/// # Parameters
+/// - `range`: The range from which to generate a random date-time. Both ends must be bounded,
+///   e.g., `start..end` or `start..=end`.
+///
+/// # Returns
+/// - A uniformly distributed date-time within the specified range, with nanosecond precision.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use chrono::{NaiveDate, NaiveDateTime};
+///
+/// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// let x: NaiveDateTime = regd_testing::rand::generate_datetime(start..end);
+/// assert!(start <= x && x < end);
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the provided range is empty or unbounded.
+pub fn generate_datetime<T, R>(range: R) -> T
+where
+    T: CalendarDateTime,
+    R: RangeBounds<T>,
+{
+    generate_datetime_with(range, &DateOpts::default())
+}
+
+/// Generates a random date-time within the specified range, favoring the boundaries of `opts`.
+///
+/// The boundaries of `opts` only apply to the date part; a favored date keeps the randomly
+/// generated time of day, unless that would fall outside of the range.
+///
+/// # Parameters
+/// - `range`: The range from which to generate a random date-time. Both ends must be bounded,
+///   e.g., `start..end` or `start..=end`.
+/// - `opts`: The calendar boundaries to favor.
+///
+/// # Returns
+/// - A randomly generated date-time within the specified range, with nanosecond precision.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "time")]
+/// # {
+/// use regd_testing::rand::DateOpts;
+/// use time::{Date, Month, PrimitiveDateTime, Time};
+///
+/// let opts = DateOpts {
+///     leap_days: true,
+///     ..Default::default()
+/// };
+/// let date = |year| Date::from_calendar_date(year, Month::January, 1).unwrap();
+/// let start = PrimitiveDateTime::new(date(2020), Time::MIDNIGHT);
+/// let end = PrimitiveDateTime::new(date(2030), Time::MIDNIGHT);
+/// let x: PrimitiveDateTime = regd_testing::rand::generate_datetime_with(start..end, &opts);
+/// assert!(start <= x && x < end);
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the provided range is empty or unbounded.
+pub fn generate_datetime_with<T, R>(range: R, opts: &DateOpts) -> T
+where
+    T: CalendarDateTime,
+    R: RangeBounds<T>,
+{
+    let to_nanos = |x: &T| {
+        let (date, time) = x.to_parts();
+        i128::from(date.to_days()) * NANOS_PER_DAY + i128::from(time.to_nanos_of_day())
+    };
+    let (start, end) = bounds(&range, to_nanos);
+    let mut rng = super::rng();
+    let nanos = rng.random_range(start..=end);
+    let (days, time) = (
+        nanos.div_euclid(NANOS_PER_DAY) as i64,
+        nanos.rem_euclid(NANOS_PER_DAY),
+    );
+    let range = (
+        start.div_euclid(NANOS_PER_DAY) as i64,
+        end.div_euclid(NANOS_PER_DAY) as i64,
+    );
+    let favored =
+        i128::from(favor_days::<T::Date>(&mut rng, days, range, opts)) * NANOS_PER_DAY + time;
+    let nanos = if (start..=end).contains(&favored) {
+        favored
+    } else {
+        nanos
+    };
+    T::from_parts(
+        T::Date::from_days(nanos.div_euclid(NANOS_PER_DAY) as i64),
+        T::Time::from_nanos_of_day(nanos.rem_euclid(NANOS_PER_DAY) as u64),
+    )
+}
+
+/// Generates a random time of day.
+///
+/// # Returns
+/// - A uniformly distributed time of day between midnight and the last nanosecond of the day.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use chrono::{NaiveTime, Timelike};
+///
+/// let x: NaiveTime = regd_testing::rand::generate_naive_time();
+/// assert!(x.hour() < 24);
+/// # }
+/// ```
+pub fn generate_naive_time<T>() -> T
+where
+    T: CalendarTime,
+{
+    T::from_nanos_of_day(super::rng().random_range(0..NANOS_PER_DAY as u64))
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+    use super::{CalendarDate, CalendarDateTime, CalendarTime};
+
+    impl CalendarDate for NaiveDate {
+        fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+            NaiveDate::from_ymd_opt(year, month, day)
+        }
+
+        fn to_ymd(self) -> (i32, u32, u32) {
+            (self.year(), self.month(), self.day())
+        }
+
+        fn to_days(self) -> i64 {
+            i64::from(self.num_days_from_ce())
+        }
+
+        fn from_days(days: i64) -> Self {
+            i32::try_from(days)
+                .ok()
+                .and_then(NaiveDate::from_num_days_from_ce_opt)
+                .unwrap_or_else(|| panic!("cannot sample out-of-range date"))
+        }
+    }
+
+    impl CalendarTime for NaiveTime {
+        fn from_nanos_of_day(nanos: u64) -> Self {
+            let (secs, nanos) = (nanos / 1_000_000_000, nanos % 1_000_000_000);
+            NaiveTime::from_num_seconds_from_midnight_opt(secs as u32, nanos as u32)
+                .unwrap_or_else(|| panic!("cannot sample out-of-range time"))
+        }
+
+        fn to_nanos_of_day(self) -> u64 {
+            // Leap seconds are represented by nanoseconds beyond one second; clamp them.
+            u64::from(self.num_seconds_from_midnight()) * 1_000_000_000
+                + u64::from(self.nanosecond().min(999_999_999))
+        }
+    }
+
+    impl CalendarDateTime for NaiveDateTime {
+        type Date = NaiveDate;
+        type Time = NaiveTime;
+
+        fn from_parts(date: NaiveDate, time: NaiveTime) -> Self {
+            NaiveDateTime::new(date, time)
+        }
+
+        fn to_parts(self) -> (NaiveDate, NaiveTime) {
+            (self.date(), self.time())
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{Date, Month, PrimitiveDateTime, Time};
+
+    use super::{CalendarDate, CalendarDateTime, CalendarTime};
+
+    impl CalendarDate for Date {
+        fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+            let month = Month::try_from(u8::try_from(month).ok()?).ok()?;
+            Date::from_calendar_date(year, month, u8::try_from(day).ok()?).ok()
+        }
+
+        fn to_ymd(self) -> (i32, u32, u32) {
+            (
+                self.year(),
+                u32::from(u8::from(self.month())),
+                u32::from(self.day()),
+            )
+        }
+
+        fn to_days(self) -> i64 {
+            i64::from(self.to_julian_day())
+        }
+
+        fn from_days(days: i64) -> Self {
+            i32::try_from(days)
+                .ok()
+                .and_then(|days| Date::from_julian_day(days).ok())
+                .unwrap_or_else(|| panic!("cannot sample out-of-range date"))
+        }
+    }
+
+    impl CalendarTime for Time {
+        fn from_nanos_of_day(nanos: u64) -> Self {
+            let (secs, nanos) = (nanos / 1_000_000_000, nanos % 1_000_000_000);
+            Time::from_hms_nano(
+                (secs / 3600) as u8,
+                (secs / 60 % 60) as u8,
+                (secs % 60) as u8,
+                nanos as u32,
+            )
+            .unwrap_or_else(|e| panic!("cannot sample out-of-range time: {e}"))
+        }
+
+        fn to_nanos_of_day(self) -> u64 {
+            let (hour, minute, second, nanos) = self.as_hms_nano();
+            (u64::from(hour) * 3600 + u64::from(minute) * 60 + u64::from(second)) * 1_000_000_000
+                + u64::from(nanos)
+        }
+    }
+
+    impl CalendarDateTime for PrimitiveDateTime {
+        type Date = Date;
+        type Time = Time;
+
+        fn from_parts(date: Date, time: Time) -> Self {
+            PrimitiveDateTime::new(date, time)
+        }
+
+        fn to_parts(self) -> (Date, Time) {
+            (self.date(), self.time())
+        }
+    }
+}
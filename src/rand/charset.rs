@@ -0,0 +1,137 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random string generators over character sets.
+
+use rand::Rng;
+use rand::prelude::Distribution;
+
+/// A set of characters from which random strings are generated.
+///
+/// The predefined variants cover the alphabets most commonly used by identifiers and tokens,
+/// while [`Charset::Custom`] accepts an arbitrary set of characters.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::Charset;
+///
+/// assert!(Charset::Hex.contains('f'));
+/// assert!(!Charset::Base58.contains('0'));
+/// assert!(Charset::Custom(&['α', 'β']).contains('β'));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Charset<'a> {
+    /// Lowercase hexadecimal digits (`0-9`, `a-f`).
+    Hex,
+    /// The Bitcoin base58 alphabet, i.e., alphanumerics without `0`, `O`, `I` and `l`.
+    Base58,
+    /// Lowercase ASCII letters (`a-z`).
+    Lowercase,
+    /// ASCII digits (`0-9`).
+    Digits,
+    /// Printable ASCII characters, from space (`0x20`) to tilde (`0x7E`).
+    PrintableAscii,
+    /// Unreserved URL characters as defined by RFC 3986 (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_`, `~`).
+    UrlSafe,
+    /// An arbitrary set of characters.
+    Custom(&'a [char]),
+}
+
+const HEX: &str = "0123456789abcdef";
+const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const DIGITS: &str = "0123456789";
+const PRINTABLE_ASCII: &str = " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+const URL_SAFE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
+
+impl Charset<'_> {
+    /// Returns the ASCII alphabet of a predefined charset, or `None` for [`Charset::Custom`].
+    fn ascii(&self) -> Option<&'static str> {
+        match self {
+            Self::Hex => Some(HEX),
+            Self::Base58 => Some(BASE58),
+            Self::Lowercase => Some(LOWERCASE),
+            Self::Digits => Some(DIGITS),
+            Self::PrintableAscii => Some(PRINTABLE_ASCII),
+            Self::UrlSafe => Some(URL_SAFE),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Returns the number of characters in the charset.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Custom(chars) => chars.len(),
+            _ => self.ascii().map_or(0, str::len),
+        }
+    }
+
+    /// Returns `true` if the charset contains no characters.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the charset contains the given character.
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            Self::Custom(chars) => chars.contains(&c),
+            _ => self.ascii().is_some_and(|s| s.contains(c)),
+        }
+    }
+}
+
+impl Distribution<char> for Charset<'_> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        assert!(!self.is_empty(), "cannot sample empty charset");
+        let index = rng.random_range(0..self.len());
+        match self {
+            Self::Custom(chars) => chars[index],
+            _ => self
+                .ascii()
+                .map_or('\0', |s| char::from(s.as_bytes()[index])),
+        }
+    }
+}
+
+/// Generates a random string of the specified length over the given charset.
+///
+/// This function creates a string consisting of characters selected uniformly at random
+/// from `charset` using the thread-local random number generator.
+///
+/// # Parameters
+/// - `length`: The number of characters of the generated string.
+/// - `charset`: The set of characters to draw from.
+///
+/// # Returns
+/// - A `String` containing `length` randomly chosen characters of `charset`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::Charset;
+///
+/// let x = regd_testing::rand::generate_string(32, &Charset::Hex);
+/// assert_eq!(x.len(), 32);
+/// assert!(x.chars().all(|c| c.is_ascii_hexdigit()));
+///
+/// let y = regd_testing::rand::generate_string(8, &Charset::Custom(&['x', 'y', 'z']));
+/// assert!(y.chars().all(|c| "xyz".contains(c)));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `charset` is empty and `length > 0`.
+pub fn generate_string(length: usize, charset: &Charset) -> String {
+    let rng = super::rng();
+    rng.sample_iter(charset).take(length).collect()
+}
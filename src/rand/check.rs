@@ -0,0 +1,359 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of property checking with shrinking.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use rand::Rng;
+
+use super::corpus::Corpus;
+use super::generator::Gen;
+use super::rng;
+
+thread_local! {
+    /// Whether panics raised on the current thread are silenced by the panic hook.
+    pub(super) static SILENT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Ensures the silencing panic hook is installed once.
+static HOOK: Once = Once::new();
+
+/// Installs a panic hook which delegates to the previous one unless the current thread is silenced.
+fn install_hook() {
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENT.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Returns the message of a panic payload.
+fn message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// A trait for the results of properties checked by [`check`].
+///
+/// A property fails if it panics, e.g., with an `assert!`, or if it returns `false` or `Err`.
+pub trait Testable {
+    /// Converts the result into `Ok(())` on success, or into the cause of the failure.
+    fn result(self) -> Result<(), String>;
+}
+
+impl Testable for () {
+    fn result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl Testable for bool {
+    fn result(self) -> Result<(), String> {
+        if self {
+            Ok(())
+        } else {
+            Err("property returned false".to_string())
+        }
+    }
+}
+
+impl<E: Debug> Testable for Result<(), E> {
+    fn result(self) -> Result<(), String> {
+        self.map_err(|e| format!("property returned error: {e:?}"))
+    }
+}
+
+/// Options of [`check_with`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckOpts {
+    /// The number of random inputs to check.
+    pub cases: usize,
+    /// The maximum number of successful shrink steps applied to a failing input.
+    pub max_shrinks: usize,
+}
+
+impl Default for CheckOpts {
+    fn default() -> Self {
+        Self {
+            cases: 100,
+            max_shrinks: 1000,
+        }
+    }
+}
+
+/// Runs `property` against `input` with panics silenced, returning the cause of a failure.
+fn run<T, F, R>(property: &F, input: T) -> Result<(), String>
+where
+    F: Fn(T) -> R,
+    R: Testable,
+{
+    install_hook();
+    let silent = SILENT.with(|silent| silent.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| property(input)));
+    SILENT.with(|s| s.set(silent));
+    match result {
+        Ok(result) => result.result(),
+        Err(payload) => Err(message(payload.as_ref())),
+    }
+}
+
+/// Checks that `property` holds for 100 random inputs generated by `generator`.
+///
+/// This function is a shorthand of [`check_with`] called with the default [`CheckOpts`].
+///
+/// # Parameters
+/// - `generator`: The generator of the inputs.
+/// - `property`: The property to check, which fails by panicking or by returning `false` or `Err`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::generator::{self as g, Gen};
+///
+/// regd_testing::rand::check(g::range(0..100u32), |x| x < 100);
+/// regd_testing::rand::check(g::alphanumeric(8).vec_of(4), |xs| {
+///     assert_eq!(xs.len(), 4);
+/// });
+/// ```
+///
+/// ```should_panic
+/// use regd_testing::rand::generator::{self as g, Gen};
+///
+/// // Panics with a minimal counterexample of eight zeros but a single 10, e.g.,
+/// // `[0, 0, 0, 0, 0, 0, 0, 10]`.
+/// regd_testing::rand::check(g::range(0..100u32).vec_of(8), |xs| {
+///     xs.iter().all(|x| *x < 10)
+/// });
+/// ```
+///
+/// # Panics
+/// - This function will panic with the minimal counterexample if the property fails.
+pub fn check<T, G, F, R>(generator: G, property: F)
+where
+    T: Clone + Debug,
+    G: Gen<T>,
+    F: Fn(T) -> R,
+    R: Testable,
+{
+    check_with(&CheckOpts::default(), generator, property)
+}
+
+/// Checks that `property` holds for random inputs generated by `generator`.
+///
+/// Each input is generated from its own seed, derived from a single value drawn from the random
+/// number generator of the current thread, whose state is otherwise restored once the check
+/// completes. The seeds of the failing inputs are recorded in the [`Corpus`] of the test and
+/// replayed before the new inputs on subsequent runs.
+///
+/// When the property fails, the failing input is iteratively simplified with [`Gen::shrink`],
+/// keeping the first simpler candidate which still fails, until no candidate fails or the
+/// maximum number of shrink steps is reached. The resulting minimal counterexample is then
+/// reported together with the original one and the cause of the failure.
+///
+/// Panics raised by the property while checking are caught and not printed, so that only the
+/// final report appears in the test output.
+///
+/// # Parameters
+/// - `opts`: The options of the check.
+/// - `generator`: The generator of the inputs.
+/// - `property`: The property to check, which fails by panicking or by returning `false` or `Err`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::CheckOpts;
+/// use regd_testing::rand::generator::{self as g, Gen};
+///
+/// let opts = CheckOpts {
+///     cases: 1000,
+///     ..Default::default()
+/// };
+/// regd_testing::rand::check_with(&opts, g::any::<u8>(), |x| u16::from(x) < 256);
+/// ```
+///
+/// # Panics
+/// - This function will panic with the minimal counterexample if the property fails.
+pub fn check_with<T, G, F, R>(opts: &CheckOpts, generator: G, property: F)
+where
+    T: Clone + Debug,
+    G: Gen<T>,
+    F: Fn(T) -> R,
+    R: Testable,
+{
+    let corpus = Corpus::current();
+    let seeds = seeds(corpus.as_ref(), opts.cases);
+    // The seeds are installed temporarily, so that the caller keeps generating from its own seed.
+    let _rng = rng::save();
+    for (case, (seed, replayed)) in seeds.enumerate() {
+        super::set_seed(seed);
+        let input = generator.generate();
+        let Err(cause) = run(&property, input.clone()) else {
+            continue;
+        };
+        let (minimal, cause, shrinks) = shrink(opts, &generator, &property, input.clone(), cause);
+        if let (Some(corpus), false) = (&corpus, replayed) {
+            corpus.record(seed, &format!("{input:?}"), &cause);
+        }
+        panic!(
+            "property failed after {case} successful cases\n\
+             minimal counterexample (after {shrinks} shrink steps): {minimal:?}\n\
+             original counterexample: {input:?} (seed {seed})\n\
+             cause: {cause}"
+        );
+    }
+}
+
+/// Simplifies the failing `input`, returning the minimal counterexample, its cause of the
+/// failure, and the number of shrink steps applied.
+fn shrink<T, G, F, R>(
+    opts: &CheckOpts,
+    generator: &G,
+    property: &F,
+    mut input: T,
+    mut cause: String,
+) -> (T, String, usize)
+where
+    T: Clone,
+    G: Gen<T>,
+    F: Fn(T) -> R,
+    R: Testable,
+{
+    let mut shrinks = 0;
+    'outer: while shrinks < opts.max_shrinks {
+        for candidate in generator.shrink(&input) {
+            if let Err(c) = run(property, candidate.clone()) {
+                input = candidate;
+                cause = c;
+                shrinks += 1;
+                continue 'outer;
+            }
+        }
+        break;
+    }
+    (input, cause, shrinks)
+}
+
+/// The environment variable which, when set to a seed, makes [`repeat`] run only that seed.
+pub const SEED_ENV: &str = "REGD_TESTING_SEED";
+
+/// Derives the seed of the `index`-th repetition from `base` with the SplitMix64 finalizer.
+pub(crate) fn derive_seed(base: u64, index: u64) -> u64 {
+    let mut z = base.wrapping_add(index.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Returns the seeds recorded in `corpus`, followed by `count` new seeds, each paired with
+/// whether it is replayed from the corpus.
+fn seeds(corpus: Option<&Corpus>, count: usize) -> impl Iterator<Item = (u64, bool)> {
+    let replayed = corpus.map(Corpus::seeds).unwrap_or_default();
+    let base: u64 = super::rng().random();
+    replayed
+        .into_iter()
+        .map(|seed| (seed, true))
+        .chain((0..count as u64).map(move |i| (derive_seed(base, i), false)))
+}
+
+/// Runs `test` the given number of times, each with a distinct seed installed with
+/// [`set_seed`].
+///
+/// The seeds are derived from a base seed drawn from the random number generator of the current
+/// thread, so that every run explores different values unless the thread was reseeded, and the
+/// state of that generator is restored once every repetition has passed. When a repetition
+/// fails, the seed it ran with is reported and recorded in the [`Corpus`] of the test, whose
+/// seeds are replayed before the new ones on subsequent runs. Setting the [`SEED_ENV`]
+/// environment variable to a seed runs a single repetition with it, which reproduces the values
+/// generated through this module.
+///
+/// This function is what `#[regd_test(repeat = N)]` expands to.
+///
+/// [`set_seed`]: super::set_seed
+///
+/// # Parameters
+/// - `times`: The number of repetitions.
+/// - `test`: The test to repeat, which fails by panicking or by returning `false` or `Err`.
+///
+/// # Examples
+/// ```
+/// let mut runs = 0;
+/// regd_testing::rand::repeat(10, || {
+///     runs += 1;
+///     let x: u8 = regd_testing::rand::generate();
+///     assert_eq!(u16::from(x) * 2 / 2, u16::from(x));
+/// });
+/// assert!(runs == 10 || std::env::var_os(regd_testing::rand::SEED_ENV).is_some());
+/// ```
+///
+/// ```should_panic
+/// // Panics with, e.g., "repetition 3 of 100 failed with seed 1234; rerun with
+/// // REGD_TESTING_SEED=1234".
+/// regd_testing::rand::repeat(100, || regd_testing::rand::generate::<u8>() < 200);
+/// ```
+///
+/// # Panics
+/// - This function will panic with the seed and the cause of the failure if a repetition fails,
+///   or if [`SEED_ENV`] is set to a value which is not a seed.
+pub fn repeat<F, R>(times: usize, mut test: F)
+where
+    F: FnMut() -> R,
+    R: Testable,
+{
+    let (seeds, corpus): (Vec<_>, _) = match std::env::var(SEED_ENV) {
+        Ok(seed) => {
+            let seed = seed
+                .trim()
+                .parse::<u64>()
+                .unwrap_or_else(|e| panic!("cannot parse {SEED_ENV}={seed:?}: {e}"));
+            (vec![(seed, true)], None)
+        }
+        Err(_) => {
+            let corpus = Corpus::current();
+            (seeds(corpus.as_ref(), times).collect(), corpus)
+        }
+    };
+    let count = seeds.len();
+    let _rng = rng::save();
+    for (i, (seed, replayed)) in seeds.into_iter().enumerate() {
+        super::set_seed(seed);
+        install_hook();
+        let silent = SILENT.with(|silent| silent.replace(true));
+        let result = panic::catch_unwind(AssertUnwindSafe(&mut test));
+        SILENT.with(|s| s.set(silent));
+        let cause = match result {
+            Ok(result) => result.result(),
+            Err(payload) => Err(message(payload.as_ref())),
+        };
+        if let Err(cause) = cause {
+            if let (Some(corpus), false) = (&corpus, replayed) {
+                corpus.record(seed, "()", &cause);
+            }
+            panic!(
+                "repetition {i} of {count} failed with seed {seed}; rerun with {SEED_ENV}={seed}\n\
+                 cause: {cause}"
+            );
+        }
+    }
+}
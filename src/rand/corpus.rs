@@ -0,0 +1,163 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains the persistence of the seeds of failing inputs across test runs.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The environment variable which, when set to `0` or `false`, disables the failure corpus.
+pub const CORPUS_ENV: &str = "REGD_TESTING_CORPUS";
+
+/// Returns the target directory of the build which produced the running executable.
+fn target_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let exe = std::env::current_exe().ok()?;
+    if let Some(dir) = exe
+        .ancestors()
+        .find(|dir| dir.join("CACHEDIR.TAG").is_file())
+    {
+        return Some(dir.to_path_buf());
+    }
+    std::env::var_os("CARGO_MANIFEST_DIR").map(|dir| PathBuf::from(dir).join("target"))
+}
+
+/// Returns the name of the running test binary, i.e., the stem of the executable without the
+/// hash appended by Cargo, e.g., `integration` for `integration-0123456789abcdef`.
+fn binary_name() -> Option<String> {
+    let exe = std::env::current_exe().ok()?;
+    let stem = exe.file_stem()?.to_str()?;
+    let name = match stem.rsplit_once('-') {
+        Some((name, hash)) if hash.len() == 16 && hash.bytes().all(|b| b.is_ascii_hexdigit()) => {
+            name
+        }
+        _ => stem,
+    };
+    Some(name.to_string())
+}
+
+/// Replaces the characters of a name which are not safe in a file name.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// The failure corpus of a single test, i.e., the seeds of the inputs which made it fail.
+///
+/// The entries of a test are stored in `target/regd-corpus/<binary>/<test name>/`, where
+/// `<binary>` is the name of the test binary, so that identically named tests of different
+/// binaries or crates keep distinct corpora. There is one file per seed, each containing the
+/// seed, the failing input, and the cause of the failure. [`check`] and [`repeat`] record a new
+/// entry when the test fails, and replay the recorded entries before generating new inputs on
+/// subsequent runs, so that a failure once found keeps being checked after it is fixed.
+///
+/// Since the inputs are regenerated from their seeds, an entry only reproduces its input as long
+/// as the generator is deterministic and left unchanged. The entries can be removed at any time,
+/// e.g., by `cargo clean`, and are ignored when [`CORPUS_ENV`] is set to `0` or `false`.
+///
+/// [`check`]: super::check
+/// [`repeat`]: super::repeat
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::Corpus;
+/// use regd_testing::fs::TempDir;
+///
+/// let dir = TempDir::new();
+/// let corpus = Corpus::in_dir(dir.path().join("my_test"));
+/// corpus.record(42, "[10]", "property returned false");
+/// assert_eq!(corpus.seeds(), vec![42]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Corpus {
+    dir: PathBuf,
+}
+
+impl Corpus {
+    /// Returns the corpus stored in the given directory.
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the corpus of the test running on the current thread.
+    ///
+    /// # Returns
+    /// - The corpus of the test, named after the running test binary and the current thread as
+    ///   the test harness does, or `None` if the current thread is not a named test thread, if the
+    ///   target directory cannot be found, or if the corpus is disabled by [`CORPUS_ENV`].
+    pub fn current() -> Option<Self> {
+        if std::env::var(CORPUS_ENV).is_ok_and(|v| v == "0" || v.eq_ignore_ascii_case("false")) {
+            return None;
+        }
+        let thread = std::thread::current();
+        let name = thread.name().filter(|name| *name != "main")?;
+        Some(Self::in_dir(
+            target_dir()?
+                .join("regd-corpus")
+                .join(sanitize(&binary_name()?))
+                .join(sanitize(name)),
+        ))
+    }
+
+    /// Returns the directory of the corpus.
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the recorded seeds, in ascending order.
+    pub fn seeds(&self) -> Vec<u64> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut seeds: Vec<u64> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "txt" {
+                    return None;
+                }
+                path.file_stem()?.to_str()?.parse().ok()
+            })
+            .collect();
+        seeds.sort_unstable();
+        seeds
+    }
+
+    /// Records the seed of a failing input.
+    ///
+    /// # Parameters
+    /// - `seed`: The seed which generated the input.
+    /// - `input`: A description of the input, e.g., its `Debug` representation.
+    /// - `cause`: The cause of the failure.
+    ///
+    /// # Notes
+    /// - Failing to write the entry is silently ignored, so that the corpus never hides the
+    ///   failure of the test itself.
+    pub fn record(&self, seed: u64, input: &str, cause: &str) {
+        let _ = fs::create_dir_all(&self.dir);
+        let _ = fs::write(
+            self.dir.join(format!("{seed}.txt")),
+            format!("seed: {seed}\ninput: {input}\ncause: {cause}\n"),
+        );
+    }
+}
@@ -0,0 +1,327 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random CSV data.
+
+use std::fmt;
+use std::io::{self, Write};
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use super::generator::Gen;
+
+/// Field values which are notoriously mishandled by CSV readers and writers.
+const EDGE_CASES: &[&str] = &[
+    "",
+    " ",
+    " padded ",
+    "a,b",
+    "a;b",
+    "a\tb",
+    "say \"hi\"",
+    "\"",
+    "\"\"",
+    "line\nbreak",
+    "line\r\nbreak",
+    "trailing\n",
+    "=1+1",
+    "NULL",
+    "\u{feff}bom",
+    "caf\u{e9} \u{2603}",
+];
+
+/// A column of the CSV data generated by [`generate_csv`], whose fields are drawn from a
+/// generator.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::ColumnSpec;
+/// use regd_testing::rand::generator as g;
+///
+/// let columns = [
+///     ColumnSpec::new("id", g::range(1..=1000u32)),
+///     ColumnSpec::new("name", g::alphanumeric(8)),
+/// ];
+/// assert_eq!(columns[1].name(), "name");
+/// ```
+pub struct ColumnSpec {
+    name: String,
+    generator: Box<dyn Fn() -> String>,
+}
+
+impl fmt::Debug for ColumnSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColumnSpec")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ColumnSpec {
+    /// Creates a column whose fields are the values of `generator` formatted with `Display`.
+    pub fn new<T, G>(name: &str, generator: G) -> Self
+    where
+        T: ToString,
+        G: Gen<T> + 'static,
+    {
+        Self {
+            name: name.to_string(),
+            generator: Box::new(move || generator.generate().to_string()),
+        }
+    }
+
+    /// Returns the name of the column, written in the header row.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Options of [`generate_csv_with`] and [`write_csv`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::CsvOpts;
+///
+/// let opts = CsvOpts {
+///     delimiter: ';',
+///     edge_case_rate: 0.1,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvOpts {
+    /// The field delimiter. Defaults to `,`.
+    pub delimiter: char,
+    /// The line terminator. Defaults to `"\r\n"`, as in RFC 4180.
+    pub terminator: &'static str,
+    /// Whether to write a header row of the column names. Defaults to `true`.
+    pub header: bool,
+    /// Whether to quote every field, rather than only the fields which require quoting. Defaults
+    /// to `false`.
+    pub quote_all: bool,
+    /// The probability of replacing a field with a value which is hard to quote or to read back,
+    /// e.g., one containing a delimiter, a quote, or a line break. Defaults to `0.0`.
+    pub edge_case_rate: f64,
+    /// The probability of making a row malformed, i.e., giving it a missing or an extra field,
+    /// or a stray quote in an unquoted field. Defaults to `0.0`.
+    pub malformed_rate: f64,
+}
+
+impl Default for CsvOpts {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            terminator: "\r\n",
+            header: true,
+            quote_all: false,
+            edge_case_rate: 0.0,
+            malformed_rate: 0.0,
+        }
+    }
+}
+
+impl CsvOpts {
+    /// Writes a field, quoting it if required or requested.
+    fn write_field(&self, line: &mut String, field: &str) {
+        let quoted = self.quote_all
+            || field.contains([self.delimiter, '"', '\r', '\n'])
+            || field.starts_with(' ')
+            || field.ends_with(' ');
+        if quoted {
+            line.push('"');
+            line.push_str(&field.replace('"', "\"\""));
+            line.push('"');
+        } else {
+            line.push_str(field);
+        }
+    }
+
+    /// Writes a row of fields followed by the line terminator, replacing the field at index
+    /// `stray`, if any, with an unquoted field containing a quote.
+    fn write_row(&self, line: &mut String, fields: &[String], stray: Option<usize>) {
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                line.push(self.delimiter);
+            }
+            if stray == Some(i) {
+                line.push_str("stray\"quote");
+            } else {
+                self.write_field(line, field);
+            }
+        }
+        line.push_str(self.terminator);
+    }
+}
+
+/// Generates random CSV data with the default [`CsvOpts`].
+///
+/// # Parameters
+/// - `rows`: The number of data rows, not counting the header row.
+/// - `columns`: The columns of the data.
+///
+/// # Returns
+/// - The CSV text, with a header row of the column names.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::ColumnSpec;
+/// use regd_testing::rand::generator as g;
+///
+/// let csv = regd_testing::rand::generate_csv(
+///     3,
+///     &[
+///         ColumnSpec::new("id", g::range(1..=9u8)),
+///         ColumnSpec::new("ok", g::just(true)),
+///     ],
+/// );
+/// let lines: Vec<&str> = csv.lines().collect();
+/// assert_eq!(lines.len(), 4);
+/// assert_eq!(lines[0], "id,ok");
+/// assert!(lines[1].ends_with(",true"));
+/// ```
+pub fn generate_csv(rows: usize, columns: &[ColumnSpec]) -> String {
+    generate_csv_with(&CsvOpts::default(), rows, columns)
+}
+
+/// Generates random CSV data with the given options.
+///
+/// # Parameters
+/// - `opts`: The options of the data.
+/// - `rows`: The number of data rows, not counting the header row.
+/// - `columns`: The columns of the data.
+///
+/// # Returns
+/// - The CSV text.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::generator as g;
+/// use regd_testing::rand::{ColumnSpec, CsvOpts};
+///
+/// let opts = CsvOpts {
+///     header: false,
+///     quote_all: true,
+///     terminator: "\n",
+///     ..Default::default()
+/// };
+/// let csv = regd_testing::rand::generate_csv_with(
+///     &opts,
+///     2,
+///     &[ColumnSpec::new("quote", g::just("say \"hi\""))],
+/// );
+/// assert_eq!(csv, "\"say \"\"hi\"\"\"\n\"say \"\"hi\"\"\"\n");
+/// ```
+///
+/// # Panics
+/// - This function will panic if a rate of `opts` is not in the range `[0.0, 1.0]`.
+pub fn generate_csv_with(opts: &CsvOpts, rows: usize, columns: &[ColumnSpec]) -> String {
+    let mut buffer = Vec::new();
+    write_csv(&mut buffer, opts, rows, columns).unwrap_or_else(|e| panic!("cannot write CSV: {e}"));
+    String::from_utf8(buffer).unwrap_or_else(|e| panic!("cannot write CSV: {e}"))
+}
+
+/// Writes random CSV data to a writer, one row at a time.
+///
+/// This is the streaming variant of [`generate_csv_with`], suited to writing large files without
+/// holding the whole data in memory.
+///
+/// # Parameters
+/// - `writer`: The writer to write the data to, e.g., a `File` or a `BufWriter`.
+/// - `opts`: The options of the data.
+/// - `rows`: The number of data rows, not counting the header row.
+/// - `columns`: The columns of the data.
+///
+/// # Returns
+/// - The indices of the data rows which were made malformed, starting at `0` for the first row
+///   after the header row, so that a test may check which rows an import rejected.
+///
+/// # Examples
+/// ```
+/// use std::io::BufWriter;
+///
+/// use regd_testing::fs::TempFile;
+/// use regd_testing::rand::generator as g;
+/// use regd_testing::rand::{ColumnSpec, CsvOpts};
+///
+/// let file = TempFile::with_contents("");
+/// let opts = CsvOpts {
+///     malformed_rate: 0.1,
+///     ..Default::default()
+/// };
+/// let writer = BufWriter::new(std::fs::File::create(file.path()).unwrap());
+/// let columns = [
+///     ColumnSpec::new("id", g::range(1..=1000u32)),
+///     ColumnSpec::new("name", g::alphanumeric(8)),
+/// ];
+/// let malformed = regd_testing::rand::write_csv(writer, &opts, 1000, &columns).unwrap();
+/// assert!(!malformed.is_empty());
+/// ```
+///
+/// # Errors
+/// - This function returns an error if writing to `writer` fails.
+///
+/// # Panics
+/// - This function will panic if a rate of `opts` is not in the range `[0.0, 1.0]`.
+pub fn write_csv(
+    mut writer: impl Write,
+    opts: &CsvOpts,
+    rows: usize,
+    columns: &[ColumnSpec],
+) -> io::Result<Vec<usize>> {
+    assert!(
+        (0.0..=1.0).contains(&opts.edge_case_rate) && (0.0..=1.0).contains(&opts.malformed_rate),
+        "cannot sample invalid probability"
+    );
+    let mut rng = super::rng();
+    let mut line = String::new();
+    if opts.header {
+        let names: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
+        opts.write_row(&mut line, &names, None);
+        writer.write_all(line.as_bytes())?;
+    }
+    let mut malformed = Vec::new();
+    for row in 0..rows {
+        let mut fields: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                if rng.random_bool(opts.edge_case_rate) {
+                    EDGE_CASES
+                        .choose(&mut rng)
+                        .copied()
+                        .unwrap_or_default()
+                        .to_string()
+                } else {
+                    (column.generator)()
+                }
+            })
+            .collect();
+        line.clear();
+        let mut stray = None;
+        if !fields.is_empty() && rng.random_bool(opts.malformed_rate) {
+            malformed.push(row);
+            match rng.random_range(0..3) {
+                0 if fields.len() > 1 => {
+                    fields.pop();
+                }
+                0 | 1 => fields.push("extra".to_string()),
+                _ => stray = Some(rng.random_range(0..fields.len())),
+            }
+        }
+        opts.write_row(&mut line, &fields, stray);
+        writer.write_all(line.as_bytes())?;
+    }
+    writer.flush()?;
+    Ok(malformed)
+}
@@ -0,0 +1,385 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random values of `serde` types.
+
+use rand::Rng;
+use serde::de::value::{Error, StrDeserializer};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+use super::{generate_alphanumeric, generate_bytes};
+
+/// The depth beyond which options are `None` and collections are empty, so that recursive types
+/// remain finite.
+const MAX_DEPTH: usize = 4;
+
+/// The depth beyond which a draw is rejected, so that enums whose variants recurse, which keep
+/// taking random variants beyond [`MAX_DEPTH`], are retried rather than overflowing the stack.
+const MAX_RECURSION: usize = 64;
+
+/// The maximum length of generated collections, strings and byte buffers.
+const MAX_LEN: usize = 8;
+
+/// The number of attempts before giving up on types whose `Deserialize` rejects values.
+const MAX_ATTEMPTS: usize = 100;
+
+/// A deserializer feeding random primitives to the visitor of any type.
+#[derive(Clone, Copy)]
+struct RandomDeserializer {
+    depth: usize,
+}
+
+impl RandomDeserializer {
+    fn nested(self) -> Self {
+        Self {
+            depth: self.depth + 1,
+        }
+    }
+
+    fn exhausted(self) -> bool {
+        self.depth >= MAX_DEPTH
+    }
+
+    /// Rejects the draw once it recursed beyond [`MAX_RECURSION`].
+    fn check_recursion(self) -> Result<Self, Error> {
+        if self.depth >= MAX_RECURSION {
+            Err(de::Error::custom(
+                "cannot generate beyond the maximum depth",
+            ))
+        } else {
+            Ok(self)
+        }
+    }
+
+    fn len(self) -> usize {
+        if self.exhausted() {
+            0
+        } else {
+            super::rng().random_range(0..=MAX_LEN / 2)
+        }
+    }
+}
+
+/// Implements the deserialization of primitive types by visiting a random value.
+macro_rules! deserialize_primitives {
+    ($($method:ident => $visit:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                visitor.$visit(super::rng().random::<$ty>())
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for RandomDeserializer {
+    type Error = Error;
+
+    deserialize_primitives! {
+        deserialize_bool => visit_bool(bool),
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+        deserialize_char => visit_char(char),
+    }
+
+    /// Visits a random scalar, since the type does not describe what it expects.
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match super::rng().random_range(0..5) {
+            0 => visitor.visit_unit(),
+            1 => self.deserialize_bool(visitor),
+            2 => self.deserialize_i64(visitor),
+            3 => self.deserialize_f64(visitor),
+            _ => self.deserialize_string(visitor),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_f32(super::rng().random_range(-1e6..1e6))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_f64(super::rng().random_range(-1e9..1e9))
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let len = super::rng().random_range(0..=MAX_LEN);
+        visitor.visit_string(generate_alphanumeric(len))
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let len = super::rng().random_range(0..=MAX_LEN);
+        visitor.visit_byte_buf(generate_bytes(len))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.exhausted() || super::rng().random() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self.nested())
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Elements::new(self, self.len()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Elements::new(self, len))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_seq(Elements::new(self, len))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(Elements::new(self, self.len()))
+    }
+
+    /// Visits the fields in order as a sequence, which derived implementations accept.
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_seq(Elements::new(self, fields.len()))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let variant = if variants.is_empty() {
+            0
+        } else {
+            super::rng().random_range(0..variants.len())
+        };
+        visitor.visit_enum(Variant {
+            deserializer: self,
+            name: variants.get(variant).copied().unwrap_or_default(),
+        })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+}
+
+/// The elements of a random sequence or map.
+struct Elements {
+    deserializer: RandomDeserializer,
+    remaining: usize,
+}
+
+impl Elements {
+    fn new(deserializer: RandomDeserializer, len: usize) -> Self {
+        Self {
+            deserializer: deserializer.nested(),
+            remaining: len,
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for Elements {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(self.deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> MapAccess<'de> for Elements {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        self.next_element_seed(seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// A randomly chosen variant of an enum.
+struct Variant {
+    deserializer: RandomDeserializer,
+    name: &'static str,
+}
+
+impl<'de> EnumAccess<'de> for Variant {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let name: StrDeserializer<'_, Error> = self.name.into_deserializer();
+        Ok((seed.deserialize(name)?, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Variant {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.deserializer.check_recursion()?.nested())
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Elements::new(self.deserializer.check_recursion()?, len))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_seq(Elements::new(
+            self.deserializer.check_recursion()?,
+            fields.len(),
+        ))
+    }
+}
+
+/// Generates a random value of any type implementing `serde::Deserialize`.
+///
+/// The value is built by a deserializer which feeds random primitives to the `Deserialize`
+/// implementation of `T`, so that no derive of [`Generate`](super::Generate) is required. Structs
+/// are visited as sequences of their fields, and enums take a random variant. Options and
+/// collections are cut off beyond a fixed depth, and values whose enum variants keep recursing
+/// beyond a larger depth are drawn again, so that recursive types remain finite.
+///
+/// # Returns
+/// - A randomly generated value of type `T`.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use std::collections::HashMap;
+///
+/// let x: (u8, Option<String>, Vec<bool>) = regd_testing::rand::generate_deserialize();
+/// assert!(x.2.len() <= 4);
+///
+/// let y: HashMap<String, Result<u32, char>> = regd_testing::rand::generate_deserialize();
+/// assert!(y.len() <= 4);
+/// assert!(y.keys().all(|k| k.len() <= 8 && k.chars().all(|c| c.is_ascii_alphanumeric())));
+///
+/// #[derive(serde::Deserialize)]
+/// enum List {
+///     Cons(u8, Box<List>),
+///     Nil,
+/// }
+///
+/// fn len(list: &List) -> usize {
+///     match list {
+///         List::Cons(_, tail) => 1 + len(tail),
+///         List::Nil => 0,
+///     }
+/// }
+///
+/// for _ in 0..1000 {
+///     let list: List = regd_testing::rand::generate_deserialize();
+///     assert!(len(&list) < 64);
+/// }
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the `Deserialize` implementation of `T` rejects 100 random
+///   values in a row, e.g., because it validates them, or because it requires a self-describing
+///   format through `deserialize_any`.
+pub fn generate_deserialize<T: DeserializeOwned>() -> T {
+    let mut last = None;
+    for _ in 0..MAX_ATTEMPTS {
+        match T::deserialize(RandomDeserializer { depth: 0 }) {
+            Ok(value) => return value,
+            Err(e) => last = Some(e),
+        }
+    }
+    panic!(
+        "cannot generate `{}`: {}",
+        std::any::type_name::<T>(),
+        last.map_or_else(String::new, |e| e.to_string())
+    );
+}
@@ -0,0 +1,129 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random value generators following
+//! non-uniform statistical distributions.
+
+use rand::prelude::Distribution;
+use rand_distr::{Exp, Normal, Poisson, Zipf};
+
+/// Generates a random value following the normal (Gaussian) distribution.
+///
+/// # Parameters
+/// - `mean`: The mean of the distribution.
+/// - `std_dev`: The standard deviation of the distribution. Must be finite and non-negative.
+///
+/// # Returns
+/// - A randomly generated `f64` value.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_normal(100.0, 15.0);
+/// println!("Generated latency: {}ms", x);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `std_dev` is negative or not finite.
+pub fn generate_normal(mean: f64, std_dev: f64) -> f64 {
+    let normal = Normal::new(mean, std_dev)
+        .unwrap_or_else(|e| panic!("cannot sample invalid normal distribution: {e}"));
+    normal.sample(&mut super::rng())
+}
+
+/// Generates a random value following the exponential distribution.
+///
+/// This distribution models the time between events occurring at a constant average rate,
+/// e.g., the inter-arrival time of requests.
+///
+/// # Parameters
+/// - `lambda`: The rate parameter of the distribution, i.e., the inverse of its mean. Must be
+///   positive.
+///
+/// # Returns
+/// - A randomly generated non-negative `f64` value.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_exponential(0.5);
+/// assert!(x >= 0.0);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `lambda` is not positive.
+pub fn generate_exponential(lambda: f64) -> f64 {
+    assert!(lambda > 0.0, "cannot sample non-positive lambda");
+    let exp = Exp::new(lambda)
+        .unwrap_or_else(|e| panic!("cannot sample invalid exponential distribution: {e}"));
+    exp.sample(&mut super::rng())
+}
+
+/// Generates a random value following the Poisson distribution.
+///
+/// This distribution models the number of events occurring in a fixed interval at a constant
+/// average rate, e.g., the number of requests per second.
+///
+/// # Parameters
+/// - `lambda`: The expected number of events. Must be positive and finite.
+///
+/// # Returns
+/// - A randomly generated number of events.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_poisson(4.0);
+/// println!("Generated number of events: {}", x);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `lambda` is not positive or not finite.
+pub fn generate_poisson(lambda: f64) -> u64 {
+    let poisson = Poisson::new(lambda)
+        .unwrap_or_else(|e| panic!("cannot sample invalid poisson distribution: {e}"));
+    poisson.sample(&mut super::rng()) as u64
+}
+
+/// Generates a random rank following the Zipf distribution.
+///
+/// This heavy-tailed distribution models the popularity of items, where the item of rank `k` is
+/// chosen with a probability proportional to `1 / k^s`, e.g., a few hot keys receiving most of the
+/// traffic of a cache.
+///
+/// # Parameters
+/// - `n`: The number of elements. Must be greater than 0.
+/// - `s`: The exponent characterizing the distribution. Must be non-negative.
+///
+/// # Returns
+/// - A randomly generated rank in the range `[1, n]`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_zipf(1000, 1.1);
+/// assert!((1..=1000).contains(&x));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `n == 0` or if `s` is negative or not a number.
+pub fn generate_zipf(n: u64, s: f64) -> u64 {
+    let zipf = Zipf::new(n as f64, s)
+        .unwrap_or_else(|e| panic!("cannot sample invalid zipf distribution: {e}"));
+    zipf.sample(&mut super::rng()) as u64
+}
@@ -0,0 +1,389 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of numeric edge-case generators.
+
+use std::ops::{Bound, RangeBounds};
+
+use rand::Rng;
+use rand::distr::StandardUniform;
+use rand::distr::uniform::{SampleRange, SampleUniform};
+use rand::prelude::Distribution;
+
+use crate::error::Error;
+
+/// The probability with which [`generate_edge`] returns an edge case rather than a uniform value.
+const EDGE_PROBABILITY: f64 = 0.75;
+
+/// A trait for numeric types which have interesting boundary values.
+///
+/// This trait is implemented for every primitive integer and floating-point type.
+pub trait Edge: Sized {
+    /// Randomly selects one of the boundary values of the type.
+    fn sample_edge<R: Rng + ?Sized>(rng: &mut R) -> Self;
+}
+
+macro_rules! impl_edge_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Edge for $t {
+                fn sample_edge<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                    match rng.random_range(0..6) {
+                        0 => <$t>::MIN,
+                        1 => <$t>::MAX,
+                        2 => 1,
+                        3 => <$t>::MAX - 1,
+                        4 => <$t>::MAX / 2,
+                        _ => {
+                            let power: $t = 1 << rng.random_range(1..<$t>::BITS);
+                            match rng.random_range(0..3) {
+                                0 => power - 1,
+                                1 => power,
+                                _ => power + 1,
+                            }
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_edge_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Edge for $t {
+                fn sample_edge<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                    match rng.random_range(0..8) {
+                        0 => <$t>::MIN,
+                        1 => <$t>::MAX,
+                        2 => 0,
+                        3 => 1,
+                        4 => -1,
+                        5 => <$t>::MIN + 1,
+                        6 => <$t>::MAX - 1,
+                        _ => {
+                            let power: $t = 1 << rng.random_range(1..<$t>::BITS - 1);
+                            let power = if rng.random_bool(0.5) { power } else { -power };
+                            match rng.random_range(0..3) {
+                                0 => power - 1,
+                                1 => power,
+                                _ => power + 1,
+                            }
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_edge_float {
+    ($($t:ty),*) => {
+        $(
+            impl Edge for $t {
+                fn sample_edge<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                    match rng.random_range(0..15) {
+                        0 => <$t>::MIN,
+                        1 => <$t>::MAX,
+                        2 => 0.0,
+                        3 => -0.0,
+                        4 => 1.0,
+                        5 => -1.0,
+                        6 => <$t>::NAN,
+                        7 => <$t>::INFINITY,
+                        8 => <$t>::NEG_INFINITY,
+                        9 => <$t>::MIN_POSITIVE,
+                        10 => -<$t>::MIN_POSITIVE,
+                        11 => <$t>::EPSILON,
+                        12 => {
+                            // Subnormal values have a zero exponent and a non-zero mantissa.
+                            let mantissa = rng.random_range(1..(1 << (<$t>::MANTISSA_DIGITS - 1)));
+                            let subnormal = <$t>::from_bits(mantissa);
+                            if rng.random_bool(0.5) { subnormal } else { -subnormal }
+                        }
+                        _ => {
+                            let power = (2.0 as $t).powi(rng.random_range(1..<$t>::MANTISSA_DIGITS as i32));
+                            let power = if rng.random_bool(0.5) { power } else { -power };
+                            match rng.random_range(0..3) {
+                                0 => power - 1.0,
+                                1 => power,
+                                _ => power + 1.0,
+                            }
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_edge_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_edge_signed!(i8, i16, i32, i64, i128, isize);
+impl_edge_float!(f32, f64);
+
+/// Generates a random value of type `T` biased toward interesting numeric boundaries.
+///
+/// Purely uniform values almost never hit the boundaries of a numeric type, where overflow and
+/// comparison bugs usually live. This function instead returns one of the boundary values of `T`
+/// most of the time, and a uniformly distributed value otherwise. The boundary values are:
+/// - For integers: `T::MIN`, `T::MAX`, `0`, `1`, `-1`, their neighbors, and powers of two ±1.
+/// - For floats: `T::MIN`, `T::MAX`, `0.0`, `-0.0`, `±1.0`, `NaN`, `±inf`, `±T::MIN_POSITIVE`,
+///   `T::EPSILON`, subnormals, and powers of two ±1.
+///
+/// # Returns
+/// - A randomly generated value of type `T`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x: i32 = regd_testing::rand::generate_edge();
+/// println!("Generated edge case: {}", x);
+///
+/// let y: f64 = regd_testing::rand::generate_edge();
+/// println!("Generated edge case: {}", y);
+/// ```
+pub fn generate_edge<T>() -> T
+where
+    T: Edge,
+    StandardUniform: Distribution<T>,
+{
+    let mut rng = super::rng();
+    if rng.random_bool(EDGE_PROBABILITY) {
+        T::sample_edge(&mut rng)
+    } else {
+        rng.random::<T>()
+    }
+}
+
+/// A trait for numeric types whose values have adjacent values, i.e., the next larger and the
+/// next smaller representable ones.
+///
+/// This trait is implemented for every primitive integer and floating-point type.
+pub trait Adjacent: Sized {
+    /// Returns the next larger value, or `None` if there is none.
+    fn next_up(self) -> Option<Self>;
+
+    /// Returns the next smaller value, or `None` if there is none.
+    fn next_down(self) -> Option<Self>;
+}
+
+macro_rules! impl_adjacent_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Adjacent for $t {
+                fn next_up(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn next_down(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_adjacent_float {
+    ($($t:ty),*) => {
+        $(
+            impl Adjacent for $t {
+                fn next_up(self) -> Option<Self> {
+                    if !self.is_finite() {
+                        return None;
+                    }
+                    let next = if self == 0.0 {
+                        <$t>::from_bits(1)
+                    } else if self > 0.0 {
+                        <$t>::from_bits(self.to_bits() + 1)
+                    } else {
+                        <$t>::from_bits(self.to_bits() - 1)
+                    };
+                    next.is_finite().then_some(next)
+                }
+
+                fn next_down(self) -> Option<Self> {
+                    Adjacent::next_up(-self).map(|x| -x)
+                }
+            }
+        )*
+    };
+}
+
+impl_adjacent_integer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+impl_adjacent_float!(f32, f64);
+
+/// The bias of [`generate_range_biased`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Bias {
+    /// No bias, i.e., a uniformly distributed value.
+    Uniform,
+    /// A value at or adjacent to an endpoint of the range with the given probability, and a
+    /// uniformly distributed value otherwise.
+    Edges(f64),
+}
+
+/// Generates a random value within the specified range, biased toward its endpoints.
+///
+/// Off-by-one bugs live at the boundaries of ranges, which uniform sampling almost never hits.
+/// With [`Bias::Edges`], this function instead returns, with the given probability, one of the
+/// lowest value of the range, the highest one, or the values adjacent to them within the range,
+/// e.g., `0`, `1`, `8` or `9` for `0..10`.
+///
+/// # Parameters
+/// - `range`: The range from which to generate a random value.
+/// - `bias`: The bias of the generated value.
+///
+/// # Returns
+/// - A randomly generated value of type `T` within the specified range.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::Bias;
+///
+/// let x: u32 = regd_testing::rand::generate_range_biased(0..10, Bias::Edges(1.0));
+/// assert!([0, 1, 8, 9].contains(&x));
+///
+/// let y: f64 = regd_testing::rand::generate_range_biased(0.0..=1.0, Bias::Edges(0.5));
+/// assert!((0.0..=1.0).contains(&y));
+/// ```
+///
+/// # Panics
+/// - This function will panic if the provided range is empty, or if the probability of
+///   [`Bias::Edges`] is not in the range `[0.0, 1.0]`.
+pub fn generate_range_biased<T, R>(range: R, bias: Bias) -> T
+where
+    T: SampleUniform + Adjacent + PartialOrd + Copy,
+    R: SampleRange<T> + RangeBounds<T>,
+{
+    assert!(!SampleRange::is_empty(&range), "{}", Error::EmptyRange);
+    let mut rng = super::rng();
+    let p = match bias {
+        Bias::Uniform => 0.0,
+        Bias::Edges(p) => p,
+    };
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "cannot sample invalid probability"
+    );
+    if !rng.random_bool(p) {
+        return rng.random_range(range);
+    }
+    let low = match range.start_bound() {
+        Bound::Included(start) => Some(*start),
+        Bound::Excluded(start) => start.next_up(),
+        Bound::Unbounded => None,
+    };
+    let high = match range.end_bound() {
+        Bound::Included(end) => Some(*end),
+        Bound::Excluded(end) => end.next_down(),
+        Bound::Unbounded => None,
+    };
+    let (Some(low), Some(high)) = (low, high) else {
+        return rng.random_range(range);
+    };
+    let candidates: Vec<T> = [Some(low), low.next_up(), high.next_down(), Some(high)]
+        .into_iter()
+        .flatten()
+        .filter(|x| low <= *x && *x <= high)
+        .collect();
+    candidates[rng.random_range(0..candidates.len())]
+}
+
+/// A trait for floating-point types whose values can be generated from their whole bit pattern
+/// space.
+///
+/// This trait is implemented for `f32` and `f64`.
+pub trait AnyFloat: Sized {
+    /// Randomly selects a value of any class, or of a finite class if `finite_only` is set.
+    fn sample_any<R: Rng + ?Sized>(rng: &mut R, finite_only: bool) -> Self;
+}
+
+macro_rules! impl_any_float {
+    ($($t:ty => $bits:ty),*) => {
+        $(
+            impl AnyFloat for $t {
+                fn sample_any<R: Rng + ?Sized>(rng: &mut R, finite_only: bool) -> Self {
+                    const MANTISSA_BITS: u32 = <$t>::MANTISSA_DIGITS - 1;
+                    const EXPONENT_BITS: u32 = <$bits>::BITS - 1 - MANTISSA_BITS;
+                    const MANTISSA_MASK: $bits = (1 << MANTISSA_BITS) - 1;
+                    const MAX_EXPONENT: $bits = (1 << EXPONENT_BITS) - 1;
+                    let sign = <$bits>::from(rng.random_bool(0.5)) << (<$bits>::BITS - 1);
+                    let mantissa = rng.random::<$bits>() & MANTISSA_MASK;
+                    let classes = if finite_only { 6 } else { 8 };
+                    let (exponent, mantissa) = match rng.random_range(0..classes) {
+                        // Zero.
+                        0 => (0, 0),
+                        // Subnormal.
+                        1 => (0, mantissa.max(1)),
+                        // Normal, with a uniformly distributed exponent.
+                        2..=5 => (rng.random_range(1..MAX_EXPONENT), mantissa),
+                        // Infinity.
+                        6 => (MAX_EXPONENT, 0),
+                        // NaN, quiet or signaling, with a random payload.
+                        _ => (MAX_EXPONENT, mantissa.max(1)),
+                    };
+                    <$t>::from_bits(sign | exponent << MANTISSA_BITS | mantissa)
+                }
+            }
+        )*
+    };
+}
+
+impl_any_float!(f32 => u32, f64 => u64);
+
+/// Generates a random float of type `T` across its whole bit pattern space.
+///
+/// `StandardUniform` only yields floats in `[0, 1)`, which never exercise the representation of
+/// floats. This function instead picks a sign and a class, then a random bit pattern of that
+/// class: `±0.0` and subnormals one eighth of the time each, normal values with a uniformly
+/// distributed exponent half of the time, `±inf` one eighth of the time, and NaNs with random
+/// payloads, quiet or signaling, one eighth of the time.
+///
+/// # Returns
+/// - A randomly generated value of type `T`.
+///
+/// # Examples
+/// ```
+/// let xs: Vec<f64> = (0..1000)
+///     .map(|_| regd_testing::rand::generate_float_any())
+///     .collect();
+/// assert!(xs.iter().any(|x| x.is_nan()));
+/// assert!(xs.iter().any(|x| x.is_subnormal()));
+/// assert!(xs.iter().any(|x| x.abs() > 1e100));
+/// ```
+pub fn generate_float_any<T: AnyFloat>() -> T {
+    T::sample_any(&mut super::rng(), false)
+}
+
+/// Generates a random finite float of type `T` across its whole bit pattern space.
+///
+/// This function is the variant of [`generate_float_any`] excluding NaNs and infinities, whose
+/// values are `±0.0` and subnormals one sixth of the time each, and normal values otherwise.
+///
+/// # Returns
+/// - A randomly generated finite value of type `T`.
+///
+/// # Examples
+/// ```
+/// let x: f32 = regd_testing::rand::generate_float_any_finite();
+/// assert!(x.is_finite());
+/// ```
+pub fn generate_float_any_finite<T: AnyFloat>() -> T {
+    T::sample_any(&mut super::rng(), true)
+}
@@ -0,0 +1,107 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random encoded string generators.
+
+use super::Charset;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encodes `bytes` with the given base64 alphabet, padding the output with `=`.
+fn encode_base64(bytes: &[u8], alphabet: &[u8; 64]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(
+                    alphabet[(group >> (18 - 6 * i)) as usize & 0x3F],
+                ));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Generates a random lowercase hexadecimal string of the specified length.
+///
+/// # Parameters
+/// - `length`: The number of hexadecimal digits of the generated string.
+///
+/// # Returns
+/// - A `String` of `length` characters from `0-9` and `a-f`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_hex(40);
+/// assert_eq!(x.len(), 40);
+/// assert!(x.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+/// ```
+pub fn generate_hex(length: usize) -> String {
+    super::generate_string(length, &Charset::Hex)
+}
+
+/// Generates the standard base64 encoding of the specified number of random bytes.
+///
+/// The encoding uses the alphabet of RFC 4648, i.e., `A-Z`, `a-z`, `0-9`, `+` and `/`, and is
+/// padded with `=` to a multiple of four characters.
+///
+/// # Parameters
+/// - `byte_len`: The number of random bytes to encode.
+///
+/// # Returns
+/// - A `String` of `4 * ceil(byte_len / 3)` characters.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_base64(32);
+/// assert_eq!(x.len(), 44);
+/// assert!(x.ends_with('='));
+/// ```
+pub fn generate_base64(byte_len: usize) -> String {
+    encode_base64(&super::generate_bytes(byte_len), BASE64)
+}
+
+/// Generates the URL-safe base64 encoding of the specified number of random bytes.
+///
+/// The encoding uses the URL and filename safe alphabet of RFC 4648, i.e., `A-Z`, `a-z`, `0-9`,
+/// `-` and `_`, and is padded with `=` to a multiple of four characters.
+///
+/// # Parameters
+/// - `byte_len`: The number of random bytes to encode.
+///
+/// # Returns
+/// - A `String` of `4 * ceil(byte_len / 3)` characters.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_base64url(16);
+/// assert_eq!(x.len(), 24);
+/// assert!(!x.contains(['+', '/']));
+/// ```
+pub fn generate_base64url(byte_len: usize) -> String {
+    encode_base64(&super::generate_bytes(byte_len), BASE64URL)
+}
@@ -0,0 +1,834 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of human-looking fake personal data generators.
+//!
+//! Every value is drawn from small built-in word lists, so that the generated data is
+//! deterministic under [`set_seed`](super::set_seed) and does not require any external
+//! dependency. The name, address, city, company and phone number generators each have an `*_in`
+//! variant taking a [`Locale`], which switches the word lists as well as the formats. The
+//! identifier generators, e.g., [`iban`], are locale-independent.
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+/// The locale of generated fake data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// English (United States).
+    #[default]
+    En,
+    /// Japanese (Japan).
+    Ja,
+    /// German (Germany).
+    De,
+}
+
+/// The word lists of a locale.
+struct Words {
+    first_names: &'static [&'static str],
+    last_names: &'static [&'static str],
+    streets: &'static [&'static str],
+    street_suffixes: &'static [&'static str],
+    cities: &'static [&'static str],
+    company_words: &'static [&'static str],
+    company_suffixes: &'static [&'static str],
+}
+
+const EN: Words = Words {
+    first_names: &[
+        "Alice",
+        "Benjamin",
+        "Chloe",
+        "Daniel",
+        "Emily",
+        "Felix",
+        "Grace",
+        "Henry",
+        "Isabel",
+        "Jack",
+        "Katherine",
+        "Liam",
+        "Maya",
+        "Noah",
+        "Olivia",
+        "Peter",
+        "Quinn",
+        "Rachel",
+        "Samuel",
+        "Tessa",
+        "Victor",
+        "William",
+        "Yasmin",
+        "Zachary",
+    ],
+    last_names: &[
+        "Anderson", "Brown", "Carter", "Davis", "Evans", "Fisher", "Garcia", "Harris", "Jackson",
+        "Kennedy", "Lopez", "Miller", "Nelson", "Owens", "Parker", "Reed", "Smith", "Taylor",
+        "Walker", "Young",
+    ],
+    streets: &[
+        "Maple", "Oak", "Pine", "Cedar", "Elm", "Willow", "Lake", "Hill", "River", "Park",
+        "Sunset", "Highland", "Meadow", "Forest", "Church", "Mill",
+    ],
+    street_suffixes: &["Street", "Avenue", "Road", "Lane", "Drive", "Court", "Way"],
+    cities: &[
+        "Springfield",
+        "Riverside",
+        "Fairview",
+        "Greenville",
+        "Franklin",
+        "Clinton",
+        "Madison",
+        "Georgetown",
+        "Salem",
+        "Ashland",
+        "Oakdale",
+        "Lakewood",
+    ],
+    company_words: &[
+        "Acme",
+        "Apex",
+        "Blue",
+        "Bright",
+        "Cobalt",
+        "Summit",
+        "Nimbus",
+        "Northwind",
+        "Pioneer",
+        "Quantum",
+        "Silver",
+        "Vertex",
+    ],
+    company_suffixes: &[
+        "Inc.",
+        "LLC",
+        "Corp.",
+        "Labs",
+        "Systems",
+        "Solutions",
+        "Group",
+    ],
+};
+
+const JA: Words = Words {
+    first_names: &[
+        "太郎",
+        "花子",
+        "翔太",
+        "陽菜",
+        "大輔",
+        "美咲",
+        "健一",
+        "さくら",
+        "拓海",
+        "結衣",
+        "直樹",
+        "愛子",
+        "悠人",
+        "七海",
+        "誠",
+        "由美",
+    ],
+    last_names: &[
+        "佐藤", "鈴木", "高橋", "田中", "伊藤", "渡辺", "山本", "中村", "小林", "加藤", "吉田",
+        "山田", "松本", "井上", "木村", "斎藤",
+    ],
+    streets: &[
+        "西新宿",
+        "本町",
+        "中央",
+        "栄町",
+        "緑町",
+        "桜台",
+        "旭町",
+        "若葉",
+        "東雲",
+        "南青山",
+    ],
+    street_suffixes: &[],
+    cities: &[
+        "東京都新宿区",
+        "東京都渋谷区",
+        "横浜市",
+        "大阪市",
+        "名古屋市",
+        "札幌市",
+        "福岡市",
+        "京都市",
+        "神戸市",
+        "仙台市",
+    ],
+    company_words: &[
+        "山田", "日本", "東洋", "未来", "大和", "富士", "青空", "光", "桜", "北斗",
+    ],
+    company_suffixes: &[
+        "商事",
+        "工業",
+        "電機",
+        "システム",
+        "製作所",
+        "ホールディングス",
+    ],
+};
+
+const DE: Words = Words {
+    first_names: &[
+        "Jürgen",
+        "Jörg",
+        "Anna",
+        "Lukas",
+        "Sophie",
+        "Maximilian",
+        "Lena",
+        "Felix",
+        "Marie",
+        "Björn",
+        "Hannah",
+        "Paul",
+        "Käthe",
+        "Tobias",
+        "Greta",
+        "Sören",
+    ],
+    last_names: &[
+        "Müller",
+        "Schmidt",
+        "Schneider",
+        "Fischer",
+        "Weber",
+        "Meyer",
+        "Wagner",
+        "Becker",
+        "Schulz",
+        "Hoffmann",
+        "Schröder",
+        "Koch",
+        "Bauer",
+        "Richter",
+        "Klein",
+        "Wolf",
+    ],
+    streets: &[
+        "Haupt", "Bahnhof", "Garten", "Schul", "Berg", "Linden", "Kirch", "Wald", "Ring", "Mühlen",
+        "Rosen", "Goethe",
+    ],
+    street_suffixes: &["straße", "weg", "allee", "gasse", "platz"],
+    cities: &[
+        "Berlin",
+        "München",
+        "Köln",
+        "Hamburg",
+        "Düsseldorf",
+        "Nürnberg",
+        "Frankfurt am Main",
+        "Stuttgart",
+        "Leipzig",
+        "Göttingen",
+        "Lübeck",
+        "Würzburg",
+    ],
+    company_words: &[
+        "Nord", "Süd", "Alpen", "Rhein", "Elb", "Stern", "Blitz", "Kraft", "Licht", "Werk",
+    ],
+    company_suffixes: &["GmbH", "AG", "GmbH & Co. KG", "KG", "e.K."],
+};
+
+impl Locale {
+    /// Returns the word lists of the locale.
+    fn words(self) -> &'static Words {
+        match self {
+            Self::En => &EN,
+            Self::Ja => &JA,
+            Self::De => &DE,
+        }
+    }
+}
+
+/// Randomly selects one of the given words.
+fn pick(rng: &mut impl Rng, words: &[&'static str]) -> &'static str {
+    words.choose(rng).copied().unwrap_or_default()
+}
+
+/// Generates a random full name in English.
+///
+/// This function is a shorthand of [`full_name_in`] called with [`Locale::En`].
+///
+/// # Returns
+/// - A `String` of the form `First Last`, e.g., `Grace Parker`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// let x = fake::full_name();
+/// assert_eq!(x.split(' ').count(), 2);
+/// ```
+pub fn full_name() -> String {
+    full_name_in(Locale::En)
+}
+
+/// Generates a random full name following the conventions of the given locale.
+///
+/// # Parameters
+/// - `locale`: The locale of the generated name.
+///
+/// # Returns
+/// - A `String` of the form `First Last` for [`Locale::En`] and [`Locale::De`], and of the form
+///   `Family Given` for [`Locale::Ja`], e.g., `山田 花子`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake::{self, Locale};
+///
+/// let x = fake::full_name_in(Locale::Ja);
+/// assert!(!x.is_ascii());
+/// ```
+pub fn full_name_in(locale: Locale) -> String {
+    let words = locale.words();
+    let mut rng = super::rng();
+    let first = pick(&mut rng, words.first_names);
+    let last = pick(&mut rng, words.last_names);
+    match locale {
+        Locale::Ja => format!("{last} {first}"),
+        Locale::En | Locale::De => format!("{first} {last}"),
+    }
+}
+
+/// Generates a random street address in English.
+///
+/// This function is a shorthand of [`street_address_in`] called with [`Locale::En`].
+///
+/// # Returns
+/// - A `String` of the form `1234 Maple Avenue`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// let x = fake::street_address();
+/// let (number, street) = x.split_once(' ').unwrap();
+/// assert!(number.parse::<u32>().is_ok());
+/// assert!(!street.is_empty());
+/// ```
+pub fn street_address() -> String {
+    street_address_in(Locale::En)
+}
+
+/// Generates a random street address following the format of the given locale.
+///
+/// # Parameters
+/// - `locale`: The locale of the generated address.
+///
+/// # Returns
+/// - A `String` of the form `1234 Maple Avenue` for [`Locale::En`], `Hauptstraße 12` for
+///   [`Locale::De`], and `西新宿2丁目8-1` for [`Locale::Ja`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake::{self, Locale};
+///
+/// let x = fake::street_address_in(Locale::De);
+/// let (street, number) = x.rsplit_once(' ').unwrap();
+/// assert!(number.parse::<u32>().is_ok());
+/// assert!(!street.is_empty());
+/// ```
+pub fn street_address_in(locale: Locale) -> String {
+    let words = locale.words();
+    let mut rng = super::rng();
+    let street = pick(&mut rng, words.streets);
+    match locale {
+        Locale::En => format!(
+            "{} {street} {}",
+            rng.random_range(1..=9999),
+            pick(&mut rng, words.street_suffixes)
+        ),
+        Locale::De => format!(
+            "{street}{} {}",
+            pick(&mut rng, words.street_suffixes),
+            rng.random_range(1..=200)
+        ),
+        Locale::Ja => format!(
+            "{street}{}丁目{}-{}",
+            rng.random_range(1..=9),
+            rng.random_range(1..=30),
+            rng.random_range(1..=20)
+        ),
+    }
+}
+
+/// Generates a random city name in English.
+///
+/// This function is a shorthand of [`city_in`] called with [`Locale::En`].
+///
+/// # Returns
+/// - A `String` containing a city name, e.g., `Springfield`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// let x = fake::city();
+/// assert!(x.chars().next().unwrap().is_uppercase());
+/// ```
+pub fn city() -> String {
+    city_in(Locale::En)
+}
+
+/// Generates a random city name of the given locale.
+///
+/// # Parameters
+/// - `locale`: The locale of the generated city name.
+///
+/// # Returns
+/// - A `String` containing a city name, e.g., `Springfield`, `München` or `横浜市`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake::{self, Locale};
+///
+/// let x = fake::city_in(Locale::Ja);
+/// assert!(x.ends_with(['市', '区']));
+/// ```
+pub fn city_in(locale: Locale) -> String {
+    pick(&mut super::rng(), locale.words().cities).to_string()
+}
+
+/// Generates a random company name in English.
+///
+/// This function is a shorthand of [`company_in`] called with [`Locale::En`].
+///
+/// # Returns
+/// - A `String` of the form `Word Suffix` or `Word Word Suffix`, e.g., `Cobalt Labs`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// let x = fake::company();
+/// assert!(x.split(' ').count() >= 2);
+/// ```
+pub fn company() -> String {
+    company_in(Locale::En)
+}
+
+/// Generates a random company name following the conventions of the given locale.
+///
+/// # Parameters
+/// - `locale`: The locale of the generated company name.
+///
+/// # Returns
+/// - A `String` such as `Cobalt Labs` for [`Locale::En`], `Rhein Kraft GmbH` for
+///   [`Locale::De`], and `株式会社富士電機` for [`Locale::Ja`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake::{self, Locale};
+///
+/// let x = fake::company_in(Locale::Ja);
+/// assert!(x.contains("株式会社"));
+/// ```
+pub fn company_in(locale: Locale) -> String {
+    let words = locale.words();
+    let mut rng = super::rng();
+    let mut name = pick(&mut rng, words.company_words).to_string();
+    match locale {
+        Locale::En | Locale::De => {
+            if rng.random_bool(0.3) {
+                name.push(' ');
+                name.push_str(pick(&mut rng, words.company_words));
+            }
+            name.push(' ');
+            name.push_str(pick(&mut rng, words.company_suffixes));
+            name
+        }
+        Locale::Ja => {
+            name.push_str(pick(&mut rng, words.company_suffixes));
+            if rng.random_bool(0.5) {
+                format!("株式会社{name}")
+            } else {
+                format!("{name}株式会社")
+            }
+        }
+    }
+}
+
+/// Generates a random phone number in the North American format.
+///
+/// This function is a shorthand of [`phone_number_in`] called with [`Locale::En`].
+///
+/// # Returns
+/// - A `String` of the form `(XXX) 555-01XX`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// let x = fake::phone_number();
+/// assert_eq!(x.len(), 14);
+/// assert!(x.contains(" 555-01"));
+/// ```
+pub fn phone_number() -> String {
+    phone_number_in(Locale::En)
+}
+
+/// Generates a random phone number following the format of the given locale.
+///
+/// For [`Locale::En`], the number is drawn from the `555-0100` to `555-0199` range of the North
+/// American Numbering Plan, which is reserved for fictional use, so that it never reaches a real
+/// subscriber. The other locales have no such range; their numbers merely follow the national
+/// format and should never be dialed.
+///
+/// # Parameters
+/// - `locale`: The locale of the generated phone number.
+///
+/// # Returns
+/// - A `String` of the form `(XXX) 555-01XX` for [`Locale::En`], `0X0-XXXX-XXXX` for
+///   [`Locale::Ja`], and `+49 XX XXXXXXX` for [`Locale::De`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake::{self, Locale};
+///
+/// let x = fake::phone_number_in(Locale::Ja);
+/// assert_eq!(x.split('-').count(), 3);
+///
+/// let y = fake::phone_number_in(Locale::De);
+/// assert!(y.starts_with("+49 "));
+/// ```
+pub fn phone_number_in(locale: Locale) -> String {
+    let mut rng = super::rng();
+    match locale {
+        Locale::En => format!(
+            "({}) 555-01{:02}",
+            rng.random_range(201..=989),
+            rng.random_range(0..100)
+        ),
+        Locale::Ja => format!(
+            "0{}0-{:04}-{:04}",
+            rng.random_range(7..=9),
+            rng.random_range(0..10000),
+            rng.random_range(0..10000)
+        ),
+        Locale::De => format!(
+            "+49 {} {:07}",
+            rng.random_range(20..=99),
+            rng.random_range(0..10_000_000)
+        ),
+    }
+}
+
+/// The IBAN formats of the supported countries, as the layout of their BBAN: `n` for a digit,
+/// `a` for an uppercase letter, and `c` for a digit or an uppercase letter.
+const IBAN_FORMATS: &[(&str, &str)] = &[
+    ("AT", "nnnnnnnnnnnnnnnn"),
+    ("CH", "nnnnncccccccccccc"),
+    ("DE", "nnnnnnnnnnnnnnnnnn"),
+    ("GB", "aaaannnnnnnnnnnnnn"),
+    ("NL", "aaaannnnnnnnnn"),
+];
+
+/// Returns the check digit making the given digits pass the Luhn algorithm.
+fn luhn_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| {
+            let digit = u32::from(*digit);
+            if i % 2 == 0 {
+                let doubled = digit * 2;
+                doubled / 10 + doubled % 10
+            } else {
+                digit
+            }
+        })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Formats the given digits into a string.
+fn digits_to_string(digits: &[u8]) -> String {
+    digits
+        .iter()
+        .map(|digit| char::from(b'0' + digit))
+        .collect()
+}
+
+/// Generates a card number with the check digit shifted by `offset`.
+fn card_number(offset: u8) -> String {
+    let mut rng = super::rng();
+    let (prefix, len): (&[u8], usize) = match rng.random_range(0..4) {
+        0 => (&[4], 16),
+        1 => (&[5, rng.random_range(1..=5)], 16),
+        2 => (&[3, 4], 15),
+        _ => (&[3, 7], 15),
+    };
+    let mut digits = prefix.to_vec();
+    digits.extend((prefix.len()..len - 1).map(|_| rng.random_range(0..10)));
+    digits.push((luhn_check_digit(&digits) + offset) % 10);
+    digits_to_string(&digits)
+}
+
+/// Generates a random credit card number passing the Luhn check.
+///
+/// The number is a 16-digit Visa or Mastercard number, or a 15-digit American Express number,
+/// without separators. Since its digits are random besides its prefix and its check digit, it is
+/// very unlikely to belong to an issued card, but it should still never be charged.
+///
+/// # Returns
+/// - A `String` of 15 or 16 digits passing the Luhn check.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// fn luhn(x: &str) -> bool {
+///     let sum: u32 = x
+///         .chars()
+///         .rev()
+///         .enumerate()
+///         .map(|(i, c)| {
+///             let d = c.to_digit(10).unwrap();
+///             if i % 2 == 1 { d * 2 / 10 + d * 2 % 10 } else { d }
+///         })
+///         .sum();
+///     sum % 10 == 0
+/// }
+///
+/// let x = fake::credit_card_number();
+/// assert!(x.len() == 15 || x.len() == 16);
+/// assert!(x.starts_with(['3', '4', '5']));
+/// assert!(luhn(&x));
+/// ```
+pub fn credit_card_number() -> String {
+    card_number(0)
+}
+
+/// Generates a random credit card number failing the Luhn check by one.
+///
+/// The number is generated as by [`credit_card_number`], except that its check digit is
+/// incremented by one, modulo 10, so that it is structurally valid except for its checksum.
+///
+/// # Returns
+/// - A `String` of 15 or 16 digits failing the Luhn check.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// fn luhn(x: &str) -> bool {
+///     let sum: u32 = x
+///         .chars()
+///         .rev()
+///         .enumerate()
+///         .map(|(i, c)| {
+///             let d = c.to_digit(10).unwrap();
+///             if i % 2 == 1 { d * 2 / 10 + d * 2 % 10 } else { d }
+///         })
+///         .sum();
+///     sum % 10 == 0
+/// }
+///
+/// let x = fake::invalid_credit_card_number();
+/// assert!(x.len() == 15 || x.len() == 16);
+/// assert!(!luhn(&x));
+/// ```
+pub fn invalid_credit_card_number() -> String {
+    card_number(1)
+}
+
+/// Returns the remainder of the division by 97 of the IBAN whose check digits are `00`.
+fn iban_remainder(country: &str, bban: &str) -> u32 {
+    bban.chars()
+        .chain(country.chars())
+        .chain("00".chars())
+        .fold(0, |remainder, c| {
+            let value = c.to_digit(36).unwrap_or_default();
+            if value < 10 {
+                (remainder * 10 + value) % 97
+            } else {
+                (remainder * 100 + value) % 97
+            }
+        })
+}
+
+/// Generates an IBAN with the check digits shifted by one if `invalid` is set.
+fn iban_number(country: &str, invalid: bool) -> String {
+    let country = country.to_ascii_uppercase();
+    let Some((_, format)) = IBAN_FORMATS.iter().find(|(code, _)| *code == country) else {
+        panic!("cannot generate IBAN of unsupported country {country:?}");
+    };
+    let mut rng = super::rng();
+    let bban: String = format
+        .chars()
+        .map(|kind| {
+            let alphabet: &[u8] = match kind {
+                'n' => b"0123456789",
+                'a' => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+                _ => b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            };
+            char::from(*alphabet.choose(&mut rng).unwrap_or(&b'0'))
+        })
+        .collect();
+    let mut check = 98 - iban_remainder(&country, &bban);
+    if invalid {
+        check = if check == 98 { 97 } else { check + 1 };
+    }
+    format!("{country}{check:02}{bban}")
+}
+
+/// Generates a random IBAN of the given country passing the mod-97 check.
+///
+/// The basic bank account number follows the length and the character classes of the country,
+/// but its bank code and account number are random, so that it does not designate a real
+/// account.
+///
+/// # Parameters
+/// - `country`: The ISO 3166-1 alpha-2 code of the country, one of `AT`, `CH`, `DE`, `GB`, and
+///   `NL`, in any case.
+///
+/// # Returns
+/// - A `String` IBAN without spaces.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// fn mod97(x: &str) -> u32 {
+///     let (head, tail) = x.split_at(4);
+///     tail.chars().chain(head.chars()).fold(0, |r, c| {
+///         let v = c.to_digit(36).unwrap();
+///         (r * if v < 10 { 10 } else { 100 } + v) % 97
+///     })
+/// }
+///
+/// let x = fake::iban("DE");
+/// assert_eq!(x.len(), 22);
+/// assert!(x.starts_with("DE"));
+/// assert_eq!(mod97(&x), 1);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `country` is not supported.
+pub fn iban(country: &str) -> String {
+    iban_number(country, false)
+}
+
+/// Generates a random IBAN of the given country failing the mod-97 check by one.
+///
+/// The IBAN is generated as by [`iban`], except that its check digits are incremented by one,
+/// or decremented from `98`, so that it is structurally valid except for its checksum.
+///
+/// # Parameters
+/// - `country`: The ISO 3166-1 alpha-2 code of the country, one of `AT`, `CH`, `DE`, `GB`, and
+///   `NL`, in any case.
+///
+/// # Returns
+/// - A `String` IBAN without spaces.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// fn mod97(x: &str) -> u32 {
+///     let (head, tail) = x.split_at(4);
+///     tail.chars().chain(head.chars()).fold(0, |r, c| {
+///         let v = c.to_digit(36).unwrap();
+///         (r * if v < 10 { 10 } else { 100 } + v) % 97
+///     })
+/// }
+///
+/// let x = fake::invalid_iban("gb");
+/// assert_eq!(x.len(), 22);
+/// assert!(x.starts_with("GB"));
+/// assert_ne!(mod97(&x), 1);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `country` is not supported.
+pub fn invalid_iban(country: &str) -> String {
+    iban_number(country, true)
+}
+
+/// Generates an ISBN-13 with the check digit shifted by `offset`.
+fn isbn13_number(offset: u8) -> String {
+    let mut rng = super::rng();
+    let mut digits = vec![9, 7, rng.random_range(8..=9)];
+    digits.extend((0..9).map(|_| rng.random_range(0..10)));
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, digit)| u32::from(*digit) * if i % 2 == 0 { 1 } else { 3 })
+        .sum();
+    digits.push(((10 - sum % 10) % 10) as u8);
+    digits[12] = (digits[12] + offset) % 10;
+    digits_to_string(&digits)
+}
+
+/// Generates a random ISBN-13 with a valid check digit.
+///
+/// # Returns
+/// - A `String` of 13 digits starting with `978` or `979`, without hyphens.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// fn isbn(x: &str) -> bool {
+///     let sum: u32 = x
+///         .chars()
+///         .enumerate()
+///         .map(|(i, c)| c.to_digit(10).unwrap() * if i % 2 == 0 { 1 } else { 3 })
+///         .sum();
+///     sum % 10 == 0
+/// }
+///
+/// let x = fake::isbn13();
+/// assert_eq!(x.len(), 13);
+/// assert!(x.starts_with("978") || x.starts_with("979"));
+/// assert!(isbn(&x));
+/// ```
+pub fn isbn13() -> String {
+    isbn13_number(0)
+}
+
+/// Generates a random ISBN-13 whose check digit is off by one.
+///
+/// The ISBN is generated as by [`isbn13`], except that its check digit is incremented by one,
+/// modulo 10, so that it is structurally valid except for its checksum.
+///
+/// # Returns
+/// - A `String` of 13 digits starting with `978` or `979`, without hyphens.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::fake;
+///
+/// fn isbn(x: &str) -> bool {
+///     let sum: u32 = x
+///         .chars()
+///         .enumerate()
+///         .map(|(i, c)| c.to_digit(10).unwrap() * if i % 2 == 0 { 1 } else { 3 })
+///         .sum();
+///     sum % 10 == 0
+/// }
+///
+/// let x = fake::invalid_isbn13();
+/// assert_eq!(x.len(), 13);
+/// assert!(!isbn(&x));
+/// ```
+pub fn invalid_isbn13() -> String {
+    isbn13_number(1)
+}
@@ -0,0 +1,180 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random framed binary messages.
+
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+/// The layout of the frames generated by [`generate_frames`].
+///
+/// Each frame is laid out as the `header`, the length of the payload as a big-endian `u32`, the
+/// payload, and, if `checksum` is set, the CRC-32 (IEEE) of the payload as a big-endian `u32`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::FrameSpec;
+///
+/// let spec = FrameSpec {
+///     len_range: 1..=16,
+///     header: b"RG".to_vec(),
+///     checksum: true,
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameSpec {
+    /// The range of the lengths of the payloads. Defaults to `0..=64`.
+    pub len_range: RangeInclusive<usize>,
+    /// The bytes starting every frame, e.g., a magic number. Defaults to no bytes.
+    pub header: Vec<u8>,
+    /// Whether to end every frame with the CRC-32 of its payload. Defaults to `false`.
+    pub checksum: bool,
+}
+
+impl Default for FrameSpec {
+    fn default() -> Self {
+        Self {
+            len_range: 0..=64,
+            header: Vec::new(),
+            checksum: false,
+        }
+    }
+}
+
+/// A frame generated by [`generate_frames`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// The random payload of the frame.
+    pub payload: Vec<u8>,
+    /// The encoded frame, including the header, the length, and the checksum.
+    pub bytes: Vec<u8>,
+}
+
+/// Returns the CRC-32 (IEEE) of the given bytes.
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ u32::from(*byte), |crc, _| {
+            (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+/// Generates random frames with the given layout.
+///
+/// # Parameters
+/// - `count`: The number of frames.
+/// - `spec`: The layout of the frames.
+///
+/// # Returns
+/// - The frames, whose encoded bytes may be concatenated into a stream with
+///   `frames.iter().flat_map(|f| &f.bytes)`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::FrameSpec;
+///
+/// fn crc32(bytes: &[u8]) -> u32 {
+///     let mut crc = 0xFFFF_FFFF_u32;
+///     for &b in bytes {
+///         crc ^= u32::from(b);
+///         for _ in 0..8 {
+///             crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+///         }
+///     }
+///     !crc
+/// }
+/// assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+///
+/// let spec = FrameSpec {
+///     len_range: 4..=4,
+///     header: vec![0xAB],
+///     checksum: true,
+/// };
+/// let frames = regd_testing::rand::generate_frames(3, &spec);
+/// for frame in &frames {
+///     assert_eq!(frame.bytes.len(), 1 + 4 + 4 + 4);
+///     assert_eq!(frame.bytes[..5], [0xAB, 0, 0, 0, 4]);
+///     assert_eq!(frame.bytes[5..9], frame.payload[..]);
+///     assert_eq!(frame.bytes[9..], crc32(&frame.payload).to_be_bytes());
+/// }
+/// ```
+///
+/// # Panics
+/// - This function will panic if `spec.len_range` is empty, or if it contains a length which
+///   does not fit in a `u32`.
+pub fn generate_frames(count: usize, spec: &FrameSpec) -> Vec<Frame> {
+    assert!(
+        !spec.len_range.is_empty(),
+        "{}",
+        crate::error::Error::EmptyRange
+    );
+    (0..count)
+        .map(|_| {
+            let payload = super::generate_bytes(super::generate_range(spec.len_range.clone()));
+            let len = u32::try_from(payload.len())
+                .unwrap_or_else(|_| panic!("cannot encode payload of {} bytes", payload.len()));
+            let mut bytes = spec.header.clone();
+            bytes.extend_from_slice(&len.to_be_bytes());
+            bytes.extend_from_slice(&payload);
+            if spec.checksum {
+                bytes.extend_from_slice(&crc32(&payload).to_be_bytes());
+            }
+            Frame { payload, bytes }
+        })
+        .collect()
+}
+
+/// Generates random frames with the given layout, one of which is corrupted.
+///
+/// A single bit of the encoded bytes of a random frame is flipped, in its header, its length,
+/// its payload, or its checksum, while its `payload` is left as generated, so that a decoder may
+/// be checked to reject the corrupted frame, or at least not to crash on it. A corrupted length
+/// misaligns the following frames of a stream.
+///
+/// # Parameters
+/// - `count`: The number of frames.
+/// - `spec`: The layout of the frames.
+///
+/// # Returns
+/// - The frames and the index of the corrupted one.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::FrameSpec;
+///
+/// let spec = FrameSpec {
+///     checksum: true,
+///     ..Default::default()
+/// };
+/// let (frames, corrupted) = regd_testing::rand::generate_corrupted_frames(10, &spec);
+/// assert_eq!(frames.len(), 10);
+/// assert!(corrupted < 10);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `count` is zero, if `spec.len_range` is empty, or if it
+///   contains a length which does not fit in a `u32`.
+pub fn generate_corrupted_frames(count: usize, spec: &FrameSpec) -> (Vec<Frame>, usize) {
+    if count == 0 {
+        panic!("cannot corrupt one of zero frames");
+    }
+    let mut frames = generate_frames(count, spec);
+    let mut rng = super::rng();
+    let index = rng.random_range(0..count);
+    let bytes = &mut frames[index].bytes;
+    let byte = rng.random_range(0..bytes.len());
+    bytes[byte] ^= 1 << rng.random_range(0..8);
+    (frames, index)
+}
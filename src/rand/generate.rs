@@ -0,0 +1,186 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of traits for generating random instances of arbitrary types.
+
+use rand::Rng;
+
+/// The maximum length of strings and vectors generated by [`Generate`].
+const MAX_LEN: usize = 16;
+
+/// A trait for types whose random instances can be generated.
+///
+/// This trait is implemented for primitive types, `String`, and common containers of types
+/// implementing it. It can be derived for structs and enums with `#[derive(Generate)]` when the
+/// `macros` feature is enabled, in which case every field is filled with a random value:
+/// - `#[generate(range = "1..100")]` generates the field within the given range.
+/// - `#[generate(len = 16)]` generates a string or vector field of the given length.
+/// - `#[generate(with = "path::to::function")]` generates the field by calling the given function.
+///
+/// Enum variants are chosen uniformly at random, unless weighted:
+/// - `#[generate(weight = 5)]` chooses the variant five times as often as an unweighted one.
+/// - `#[generate(skip)]` never chooses the variant, whose fields then need not implement
+///   [`Generate`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::Generate;
+///
+/// let x = <(u8, String, Option<bool>)>::generate();
+/// println!("Generated tuple: {:?}", x);
+/// ```
+///
+/// ```
+/// # #[cfg(feature = "macros")]
+/// # {
+/// use regd_testing::rand::Generate;
+///
+/// #[derive(Debug, Generate)]
+/// struct Config {
+///     #[generate(range = "1..100")]
+///     workers: u32,
+///     #[generate(len = 16)]
+///     name: String,
+///     verbose: bool,
+/// }
+///
+/// let x = Config::generate();
+/// assert!((1..100).contains(&x.workers));
+/// assert_eq!(x.name.len(), 16);
+/// # }
+/// ```
+///
+/// ```
+/// # #[cfg(feature = "macros")]
+/// # {
+/// use regd_testing::rand::Generate;
+///
+/// #[derive(Debug, Generate)]
+/// enum Response {
+///     #[generate(weight = 9)]
+///     Ok(u16),
+///     Error,
+///     #[generate(skip)]
+///     Internal(std::fs::File),
+/// }
+///
+/// let oks = (0..1000)
+///     .filter(|_| matches!(Response::generate(), Response::Ok(_)))
+///     .count();
+/// assert!(oks > 800);
+/// # }
+/// ```
+pub trait Generate: Sized {
+    /// Generates a random instance of the type.
+    fn generate() -> Self;
+}
+
+/// A trait for sized collections whose random instances of a given length can be generated.
+///
+/// This trait backs the `#[generate(len = ...)]` attribute of `#[derive(Generate)]`.
+pub trait GenerateLen: Sized {
+    /// Generates a random instance of the type with `len` elements.
+    fn generate_len(len: usize) -> Self;
+}
+
+macro_rules! impl_generate_standard {
+    ($($t:ty),*) => {
+        $(
+            impl Generate for $t {
+                fn generate() -> Self {
+                    super::generate()
+                }
+            }
+        )*
+    };
+}
+
+impl_generate_standard!(
+    u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, bool, char
+);
+
+impl Generate for usize {
+    fn generate() -> Self {
+        super::generate::<u64>() as usize
+    }
+}
+
+impl Generate for isize {
+    fn generate() -> Self {
+        super::generate::<i64>() as isize
+    }
+}
+
+impl Generate for () {
+    fn generate() -> Self {}
+}
+
+impl Generate for String {
+    fn generate() -> Self {
+        Self::generate_len(super::rng().random_range(0..=MAX_LEN))
+    }
+}
+
+impl GenerateLen for String {
+    fn generate_len(len: usize) -> Self {
+        super::generate_alphanumeric(len)
+    }
+}
+
+impl<T: Generate> Generate for Vec<T> {
+    fn generate() -> Self {
+        Self::generate_len(super::rng().random_range(0..=MAX_LEN))
+    }
+}
+
+impl<T: Generate> GenerateLen for Vec<T> {
+    fn generate_len(len: usize) -> Self {
+        (0..len).map(|_| T::generate()).collect()
+    }
+}
+
+impl<T: Generate> Generate for Option<T> {
+    fn generate() -> Self {
+        super::generate_bool(0.5).then(T::generate)
+    }
+}
+
+impl<T: Generate> Generate for Box<T> {
+    fn generate() -> Self {
+        Box::new(T::generate())
+    }
+}
+
+impl<T: Generate, const N: usize> Generate for [T; N] {
+    fn generate() -> Self {
+        std::array::from_fn(|_| T::generate())
+    }
+}
+
+macro_rules! impl_generate_tuple {
+    ($($t:ident),+) => {
+        impl<$($t: Generate),+> Generate for ($($t,)+) {
+            fn generate() -> Self {
+                ($($t::generate(),)+)
+            }
+        }
+    };
+}
+
+impl_generate_tuple!(A);
+impl_generate_tuple!(A, B);
+impl_generate_tuple!(A, B, C);
+impl_generate_tuple!(A, B, C, D);
+impl_generate_tuple!(A, B, C, D, E);
+impl_generate_tuple!(A, B, C, D, E, F);
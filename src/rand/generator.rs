@@ -0,0 +1,537 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of composable random value generators.
+//!
+//! Generators are values implementing [`Gen`], which are built from the primitives of this
+//! module, e.g., [`range`] or [`alphanumeric`], and combined declaratively with the combinators
+//! of [`Gen`], e.g., [`Gen::map`] or [`Gen::zip`].
+//!
+//! Note that this module is not named `gen` since it is a reserved keyword as of Rust 2024.
+//!
+//! # Examples
+//! ```
+//! use regd_testing::rand::generator::{self as g, Gen};
+//!
+//! let users = g::range(1..10).zip(g::alphanumeric(8)).vec_of(100);
+//! let xs = users.generate();
+//! assert_eq!(xs.len(), 100);
+//! assert!(xs.iter().all(|(id, name)| (1..10).contains(id) && name.len() == 8));
+//! ```
+
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+
+use rand::distr::StandardUniform;
+use rand::distr::uniform::{SampleRange, SampleUniform};
+use rand::prelude::Distribution;
+
+#[cfg(feature = "proptest")]
+use super::GenStrategy;
+use super::{Edge, Shrink, generate_alphanumeric};
+
+/// The maximum number of consecutive values rejected by [`Gen::filter`] before giving up.
+const MAX_FILTER_ATTEMPTS: usize = 10_000;
+
+/// A trait for composable generators of random values of type `T`.
+///
+/// This trait provides a single required method, [`generate`], an optional method, [`shrink`],
+/// used by [`check`](super::check) to simplify failing inputs, and a set of combinators which
+/// build new generators out of existing ones:
+/// - [`map`]: Transforms the generated values.
+/// - [`filter`]: Discards the generated values not satisfying a predicate.
+/// - [`zip`]: Pairs the values of two generators.
+/// - [`vec_of`]: Collects a fixed number of generated values into a vector.
+/// - [`boxed`]: Erases the type of the generator.
+/// - [`into_strategy`]: Converts the generator into a `proptest` strategy, with the `proptest`
+///   feature.
+///
+/// [`generate`]: Self::generate
+/// [`shrink`]: Self::shrink
+/// [`map`]: Self::map
+/// [`filter`]: Self::filter
+/// [`zip`]: Self::zip
+/// [`vec_of`]: Self::vec_of
+/// [`boxed`]: Self::boxed
+/// [`into_strategy`]: Self::into_strategy
+pub trait Gen<T> {
+    /// Generates a random value.
+    fn generate(&self) -> T;
+
+    /// Returns the candidates simpler than `value`, from the simplest one.
+    ///
+    /// The default implementation returns no candidate, i.e., the generated values are never
+    /// simplified.
+    fn shrink(&self, value: &T) -> Vec<T> {
+        let _ = value;
+        Vec::new()
+    }
+
+    /// Creates a generator transforming the generated values with `f`.
+    ///
+    /// Since `f` cannot be inverted, the values of the returned generator are never shrunk.
+    fn map<U, F>(self, f: F) -> Map<Self, F, T>
+    where
+        Self: Sized,
+        F: Fn(T) -> U,
+    {
+        Map {
+            inner: self,
+            f,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a generator discarding the generated values not satisfying `predicate`.
+    ///
+    /// The returned generator panics if it fails to generate a satisfying value after 10,000
+    /// consecutive attempts.
+    fn filter<F>(self, predicate: F) -> Filter<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&T) -> bool,
+    {
+        Filter {
+            inner: self,
+            predicate,
+        }
+    }
+
+    /// Creates a generator pairing the values of this generator with the values of `other`.
+    fn zip<U, G>(self, other: G) -> Zip<Self, G>
+    where
+        Self: Sized,
+        G: Gen<U>,
+    {
+        Zip {
+            left: self,
+            right: other,
+        }
+    }
+
+    /// Creates a generator collecting `len` generated values into a vector.
+    fn vec_of(self, len: usize) -> VecOf<Self>
+    where
+        Self: Sized,
+    {
+        VecOf { inner: self, len }
+    }
+
+    /// Erases the type of the generator, e.g., to store generators of different types together.
+    fn boxed(self) -> BoxedGen<T>
+    where
+        Self: Sized + 'static,
+    {
+        BoxedGen(Box::new(self))
+    }
+
+    /// Converts the generator into a `proptest` strategy, simplifying the failing values through
+    /// [`shrink`](Self::shrink).
+    #[cfg(feature = "proptest")]
+    fn into_strategy(self) -> GenStrategy<Self, T>
+    where
+        Self: Sized,
+    {
+        GenStrategy::new(self)
+    }
+}
+
+/// A type-erased generator, created by [`Gen::boxed`].
+pub struct BoxedGen<T>(Box<dyn Gen<T>>);
+
+impl<T> Gen<T> for BoxedGen<T> {
+    fn generate(&self) -> T {
+        self.0.generate()
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        self.0.shrink(value)
+    }
+}
+
+/// A generator transforming the values of another generator, created by [`Gen::map`].
+#[derive(Clone, Debug)]
+pub struct Map<G, F, T> {
+    inner: G,
+    f: F,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, U, G, F> Gen<U> for Map<G, F, T>
+where
+    G: Gen<T>,
+    F: Fn(T) -> U,
+{
+    fn generate(&self) -> U {
+        (self.f)(self.inner.generate())
+    }
+}
+
+/// A generator discarding the values of another generator, created by [`Gen::filter`].
+#[derive(Clone, Debug)]
+pub struct Filter<G, F> {
+    inner: G,
+    predicate: F,
+}
+
+impl<T, G, F> Gen<T> for Filter<G, F>
+where
+    G: Gen<T>,
+    F: Fn(&T) -> bool,
+{
+    fn generate(&self) -> T {
+        for _ in 0..MAX_FILTER_ATTEMPTS {
+            let value = self.inner.generate();
+            if (self.predicate)(&value) {
+                return value;
+            }
+        }
+        panic!("cannot sample filtered generator after {MAX_FILTER_ATTEMPTS} attempts");
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        self.inner
+            .shrink(value)
+            .into_iter()
+            .filter(|candidate| (self.predicate)(candidate))
+            .collect()
+    }
+}
+
+/// A generator pairing the values of two generators, created by [`Gen::zip`].
+#[derive(Clone, Debug)]
+pub struct Zip<L, R> {
+    left: L,
+    right: R,
+}
+
+impl<T, U, L, R> Gen<(T, U)> for Zip<L, R>
+where
+    T: Clone,
+    U: Clone,
+    L: Gen<T>,
+    R: Gen<U>,
+{
+    fn generate(&self) -> (T, U) {
+        (self.left.generate(), self.right.generate())
+    }
+
+    fn shrink(&self, value: &(T, U)) -> Vec<(T, U)> {
+        let (left, right) = value;
+        let lefts = self
+            .left
+            .shrink(left)
+            .into_iter()
+            .map(|simpler| (simpler, right.clone()));
+        let rights = self
+            .right
+            .shrink(right)
+            .into_iter()
+            .map(|simpler| (left.clone(), simpler));
+        lefts.chain(rights).collect()
+    }
+}
+
+/// A generator collecting the values of another generator, created by [`Gen::vec_of`].
+#[derive(Clone, Debug)]
+pub struct VecOf<G> {
+    inner: G,
+    len: usize,
+}
+
+impl<T, G> Gen<Vec<T>> for VecOf<G>
+where
+    T: Clone,
+    G: Gen<T>,
+{
+    fn generate(&self) -> Vec<T> {
+        (0..self.len).map(|_| self.inner.generate()).collect()
+    }
+
+    /// Shrinks the elements one at a time, keeping the length fixed.
+    fn shrink(&self, value: &Vec<T>) -> Vec<Vec<T>> {
+        let mut candidates = Vec::new();
+        for (i, element) in value.iter().enumerate() {
+            for simpler in self.inner.shrink(element) {
+                let mut candidate = value.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+/// A generator of values following the standard distribution, created by [`any`].
+#[derive(Clone, Copy, Debug)]
+pub struct Any<T>(PhantomData<fn() -> T>);
+
+impl<T> Gen<T> for Any<T>
+where
+    T: Shrink,
+    StandardUniform: Distribution<T>,
+{
+    fn generate(&self) -> T {
+        super::generate()
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        value.shrink()
+    }
+}
+
+/// Creates a generator of values of type `T`, generated in the same way as [`generate`].
+///
+/// [`generate`]: super::generate
+pub fn any<T>() -> Any<T>
+where
+    T: Shrink,
+    StandardUniform: Distribution<T>,
+{
+    Any(PhantomData)
+}
+
+/// A generator of values within a range, created by [`range`].
+#[derive(Clone, Debug)]
+pub struct Range<T, R> {
+    range: R,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, R> Gen<T> for Range<T, R>
+where
+    T: SampleUniform + Shrink + PartialOrd + Clone,
+    R: SampleRange<T> + RangeBounds<T> + Clone,
+{
+    fn generate(&self) -> T {
+        super::generate_range(self.range.clone())
+    }
+
+    /// Shrinks toward the start of the range, keeping the candidates within the range.
+    fn shrink(&self, value: &T) -> Vec<T> {
+        let start = match self.range.start_bound() {
+            Bound::Included(start) if start != value => Some(start.clone()),
+            _ => None,
+        };
+        start
+            .into_iter()
+            .chain(value.shrink())
+            .filter(|candidate| self.range.contains(candidate))
+            .collect()
+    }
+}
+
+/// Creates a generator of values within the specified range.
+///
+/// # Panics
+/// - The returned generator panics if the provided range is empty.
+pub fn range<T, R>(range: R) -> Range<T, R>
+where
+    T: SampleUniform + Shrink + PartialOrd + Clone,
+    R: SampleRange<T> + RangeBounds<T> + Clone,
+{
+    Range {
+        range,
+        _marker: PhantomData,
+    }
+}
+
+/// A generator of values biased toward numeric boundaries, created by [`edge`].
+#[derive(Clone, Copy, Debug)]
+pub struct EdgeGen<T>(PhantomData<fn() -> T>);
+
+impl<T> Gen<T> for EdgeGen<T>
+where
+    T: Edge + Shrink,
+    StandardUniform: Distribution<T>,
+{
+    fn generate(&self) -> T {
+        super::generate_edge()
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        value.shrink()
+    }
+}
+
+/// Creates a generator of values biased toward numeric boundaries, generated in the same way as
+/// [`generate_edge`].
+///
+/// [`generate_edge`]: super::generate_edge
+pub fn edge<T>() -> EdgeGen<T>
+where
+    T: Edge + Shrink,
+    StandardUniform: Distribution<T>,
+{
+    EdgeGen(PhantomData)
+}
+
+/// A generator of alphanumeric strings, created by [`alphanumeric`].
+#[derive(Clone, Copy, Debug)]
+pub struct Alphanumeric {
+    len: usize,
+}
+
+impl Gen<String> for Alphanumeric {
+    fn generate(&self) -> String {
+        generate_alphanumeric(self.len)
+    }
+
+    /// Shrinks the characters one at a time toward `'0'` or `'a'`, keeping the length fixed.
+    fn shrink(&self, value: &String) -> Vec<String> {
+        let chars: Vec<char> = value.chars().collect();
+        let mut candidates = Vec::new();
+        for (i, c) in chars.iter().enumerate() {
+            let simpler = if c.is_ascii_digit() { '0' } else { 'a' };
+            if *c != simpler {
+                let mut candidate = chars.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate.into_iter().collect());
+            }
+        }
+        candidates
+    }
+}
+
+/// Creates a generator of alphanumeric strings of the specified length.
+pub fn alphanumeric(len: usize) -> Alphanumeric {
+    Alphanumeric { len }
+}
+
+/// A generator of random bytes, created by [`bytes`].
+#[derive(Clone, Copy, Debug)]
+pub struct Bytes {
+    len: usize,
+}
+
+impl Gen<Vec<u8>> for Bytes {
+    fn generate(&self) -> Vec<u8> {
+        super::generate_bytes(self.len)
+    }
+
+    /// Shrinks the bytes one at a time, keeping the length fixed.
+    fn shrink(&self, value: &Vec<u8>) -> Vec<Vec<u8>> {
+        let mut candidates = Vec::new();
+        for (i, byte) in value.iter().enumerate() {
+            for simpler in byte.shrink() {
+                let mut candidate = value.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+/// Creates a generator of byte vectors of the specified length.
+pub fn bytes(len: usize) -> Bytes {
+    Bytes { len }
+}
+
+/// A generator of booleans, created by [`bool`].
+#[derive(Clone, Copy, Debug)]
+pub struct Bool {
+    p: f64,
+}
+
+impl Gen<bool> for Bool {
+    fn generate(&self) -> bool {
+        super::generate_bool(self.p)
+    }
+
+    fn shrink(&self, value: &bool) -> Vec<bool> {
+        value.shrink()
+    }
+}
+
+/// Creates a generator of booleans which are `true` with the specified probability.
+///
+/// # Panics
+/// - The returned generator panics if `p` is not in the range `[0.0, 1.0]`.
+pub fn bool(p: f64) -> Bool {
+    Bool { p }
+}
+
+/// A generator always returning the same value, created by [`just`].
+#[derive(Clone, Copy, Debug)]
+pub struct Just<T>(T);
+
+impl<T: Clone> Gen<T> for Just<T> {
+    fn generate(&self) -> T {
+        self.0.clone()
+    }
+}
+
+/// Creates a generator always returning a clone of `value`.
+pub fn just<T: Clone>(value: T) -> Just<T> {
+    Just(value)
+}
+
+/// A generator calling a function, created by [`from_fn`].
+#[derive(Clone, Copy, Debug)]
+pub struct FromFn<F>(F);
+
+impl<T, F> Gen<T> for FromFn<F>
+where
+    F: Fn() -> T,
+{
+    fn generate(&self) -> T {
+        (self.0)()
+    }
+}
+
+/// Creates a generator calling `f` to generate each value.
+///
+/// This is the escape hatch for turning any of the free functions of [`rand`](super) into a
+/// generator, e.g., `from_fn(|| rand::generate_unicode(8))`.
+pub fn from_fn<T, F>(f: F) -> FromFn<F>
+where
+    F: Fn() -> T,
+{
+    FromFn(f)
+}
+
+/// A generator delegating to one of several generators, created by [`one_of`].
+pub struct OneOf<T> {
+    gens: Vec<BoxedGen<T>>,
+}
+
+impl<T> Gen<T> for OneOf<T> {
+    fn generate(&self) -> T {
+        let index = super::generate_range(0..self.gens.len());
+        self.gens[index].generate()
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        self.gens.iter().flat_map(|g| g.shrink(value)).collect()
+    }
+}
+
+/// Creates a generator delegating each generation to one of `gens` chosen uniformly at random.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::generator::{self as g, Gen};
+///
+/// let status = g::one_of(vec![g::just(200).boxed(), g::range(400..600).boxed()]);
+/// let x: u16 = status.generate();
+/// assert!(x == 200 || (400..600).contains(&x));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `gens` is empty.
+pub fn one_of<T>(gens: Vec<BoxedGen<T>>) -> OneOf<T> {
+    assert!(!gens.is_empty(), "cannot sample empty generators");
+    OneOf { gens }
+}
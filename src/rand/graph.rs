@@ -0,0 +1,324 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random tree and graph topologies.
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+/// A rooted tree whose nodes are identified by the indices `0..len`.
+///
+/// The root is node `0`, and every other node has a smaller index than any of its children, so
+/// that iterating over the indices in order visits every parent before its children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tree {
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+}
+
+impl Tree {
+    /// Returns the number of nodes of the tree.
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Returns `false`, since a tree always has a root.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns the root of the tree, i.e., `0`.
+    pub fn root(&self) -> usize {
+        0
+    }
+
+    /// Returns the parent of the given node, or `None` for the root.
+    ///
+    /// # Panics
+    /// - This method will panic if `node` is not a node of the tree.
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.parents[node]
+    }
+
+    /// Returns the children of the given node.
+    ///
+    /// # Panics
+    /// - This method will panic if `node` is not a node of the tree.
+    pub fn children(&self, node: usize) -> &[usize] {
+        &self.children[node]
+    }
+
+    /// Returns the edges of the tree as `(parent, child)` pairs.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter_map(|(child, parent)| Some(((*parent)?, child)))
+    }
+
+    /// Returns the number of edges on the longest path from the root to a leaf.
+    pub fn depth(&self) -> usize {
+        let mut depths = vec![0; self.len()];
+        for (parent, child) in self.edges() {
+            depths[child] = depths[parent] + 1;
+        }
+        depths.into_iter().max().unwrap_or_default()
+    }
+}
+
+/// Generates a random tree of the given number of nodes.
+///
+/// Each node but the root is attached to a uniformly chosen node among the previous ones which
+/// have fewer than `max_children` children, so that both deep and bushy shapes are generated.
+///
+/// # Parameters
+/// - `nodes`: The number of nodes of the tree.
+/// - `max_children`: The maximum number of children of a node.
+///
+/// # Returns
+/// - A random [`Tree`] of `nodes` nodes.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let tree = regd_testing::rand::generate_tree(20, 2);
+/// assert_eq!(tree.len(), 20);
+/// assert_eq!(tree.edges().count(), 19);
+/// assert!((0..tree.len()).all(|n| tree.children(n).len() <= 2));
+/// assert!(tree.depth() >= 4);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `nodes` is zero, or if `max_children` is zero while `nodes` is
+///   greater than one.
+pub fn generate_tree(nodes: usize, max_children: usize) -> Tree {
+    if nodes == 0 {
+        panic!("cannot generate empty tree");
+    }
+    if max_children == 0 && nodes > 1 {
+        panic!("cannot generate tree of {nodes} nodes without children");
+    }
+    let mut rng = super::rng();
+    let mut parents = vec![None];
+    let mut children = vec![Vec::new()];
+    let mut open = vec![0];
+    for node in 1..nodes {
+        let slot = rng.random_range(0..open.len());
+        let parent = open[slot];
+        parents.push(Some(parent));
+        children.push(Vec::new());
+        children[parent].push(node);
+        if children[parent].len() == max_children {
+            open.swap_remove(slot);
+        }
+        open.push(node);
+    }
+    Tree { parents, children }
+}
+
+/// The kinds of graphs generated by [`generate_graph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum GraphKind {
+    /// A directed acyclic graph, whose nodes are ordered along a hidden random topological order.
+    Dag,
+    /// A connected undirected graph, i.e., a random spanning tree with additional random edges.
+    Connected,
+    /// A directed graph, possibly cyclic and disconnected, without self-loops.
+    Arbitrary,
+}
+
+/// A graph whose nodes are identified by the indices `0..len`, stored as adjacency lists.
+///
+/// The adjacency of an undirected graph is symmetric, i.e., an edge between `a` and `b` appears
+/// both in the neighbors of `a` and in the neighbors of `b`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graph {
+    directed: bool,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Returns the number of nodes of the graph.
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Returns `true` if the graph has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    /// Returns `true` if the edges of the graph are directed.
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Returns the nodes adjacent to the given node, in ascending order.
+    ///
+    /// # Panics
+    /// - This method will panic if `node` is not a node of the graph.
+    pub fn neighbors(&self, node: usize) -> &[usize] {
+        &self.adjacency[node]
+    }
+
+    /// Returns `true` if the graph has an edge from `from` to `to`.
+    pub fn has_edge(&self, from: usize, to: usize) -> bool {
+        self.adjacency
+            .get(from)
+            .is_some_and(|neighbors| neighbors.binary_search(&to).is_ok())
+    }
+
+    /// Returns the edges of the graph, each undirected edge being returned once as `(a, b)` with
+    /// `a < b`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(from, neighbors)| neighbors.iter().map(move |to| (from, *to)))
+            .filter(|(from, to)| self.directed || from < to)
+    }
+
+    /// Returns the nodes in a topological order, or `None` if the graph has a cycle.
+    ///
+    /// An undirected graph is considered cyclic as soon as it has an edge.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        if !self.directed {
+            return self
+                .edges()
+                .next()
+                .is_none()
+                .then(|| (0..self.len()).collect());
+        }
+        let mut indegrees = vec![0; self.len()];
+        for (_, to) in self.edges() {
+            indegrees[to] += 1;
+        }
+        let mut ready: Vec<usize> = (0..self.len()).filter(|n| indegrees[*n] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop() {
+            order.push(node);
+            for to in &self.adjacency[node] {
+                indegrees[*to] -= 1;
+                if indegrees[*to] == 0 {
+                    ready.push(*to);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Returns `true` if every node is reachable from every other node when ignoring the
+    /// direction of the edges.
+    pub fn is_connected(&self) -> bool {
+        let mut undirected = vec![Vec::new(); self.len()];
+        for (from, to) in self.edges() {
+            undirected[from].push(to);
+            undirected[to].push(from);
+        }
+        let mut visited = vec![false; self.len()];
+        let mut stack = vec![0];
+        let mut count = 0;
+        while let Some(node) = stack.pop() {
+            if node >= self.len() || std::mem::replace(&mut visited[node], true) {
+                continue;
+            }
+            count += 1;
+            stack.extend(&undirected[node]);
+        }
+        count == self.len()
+    }
+}
+
+/// Generates a random graph of the given kind.
+///
+/// # Parameters
+/// - `nodes`: The number of nodes of the graph.
+/// - `edge_prob`: The probability of each possible edge, in the range `[0.0, 1.0]`. For
+///   [`GraphKind::Connected`], this is the probability of each edge beyond the spanning tree.
+/// - `kind`: The kind of the graph.
+///
+/// # Returns
+/// - A random [`Graph`] of `nodes` nodes.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::{GraphKind, generate_graph};
+///
+/// let dag = generate_graph(30, 0.2, GraphKind::Dag);
+/// assert!(dag.is_directed());
+/// assert!(dag.topological_order().is_some());
+///
+/// let connected = generate_graph(30, 0.0, GraphKind::Connected);
+/// assert!(connected.is_connected());
+/// assert_eq!(connected.edges().count(), 29);
+///
+/// let cyclic = generate_graph(10, 1.0, GraphKind::Arbitrary);
+/// assert!(cyclic.topological_order().is_none());
+/// ```
+///
+/// # Panics
+/// - This function will panic if `edge_prob` is not in the range `[0.0, 1.0]`.
+pub fn generate_graph(nodes: usize, edge_prob: f64, kind: GraphKind) -> Graph {
+    assert!(
+        (0.0..=1.0).contains(&edge_prob),
+        "cannot sample invalid probability"
+    );
+    let mut rng = super::rng();
+    let mut adjacency = vec![Vec::new(); nodes];
+    let directed = kind != GraphKind::Connected;
+    match kind {
+        GraphKind::Dag => {
+            let mut order: Vec<usize> = (0..nodes).collect();
+            order.shuffle(&mut rng);
+            for (i, from) in order.iter().enumerate() {
+                for to in &order[i + 1..] {
+                    if rng.random_bool(edge_prob) {
+                        adjacency[*from].push(*to);
+                    }
+                }
+            }
+        }
+        GraphKind::Connected => {
+            let mut order: Vec<usize> = (0..nodes).collect();
+            order.shuffle(&mut rng);
+            for i in 1..nodes {
+                let parent = order[rng.random_range(0..i)];
+                adjacency[parent].push(order[i]);
+                adjacency[order[i]].push(parent);
+            }
+            for a in 0..nodes {
+                for b in a + 1..nodes {
+                    if !adjacency[a].contains(&b) && rng.random_bool(edge_prob) {
+                        adjacency[a].push(b);
+                        adjacency[b].push(a);
+                    }
+                }
+            }
+        }
+        GraphKind::Arbitrary => {
+            for (from, neighbors) in adjacency.iter_mut().enumerate() {
+                neighbors.extend((0..nodes).filter(|to| *to != from && rng.random_bool(edge_prob)));
+            }
+        }
+    }
+    for neighbors in &mut adjacency {
+        neighbors.sort_unstable();
+    }
+    Graph {
+        directed,
+        adjacency,
+    }
+}
@@ -0,0 +1,407 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random raw HTTP/1.x message generators.
+
+use rand::Rng;
+use rand::seq::{IndexedRandom, SliceRandom};
+
+use super::{Charset, generate_string};
+
+/// The methods of generated requests.
+const METHODS: &[&str] = &["GET", "HEAD", "POST", "PUT", "DELETE", "PATCH", "OPTIONS"];
+
+/// The status codes of generated responses and their reason phrases.
+const STATUSES: &[(u16, &str)] = &[
+    (200, "OK"),
+    (201, "Created"),
+    (204, "No Content"),
+    (301, "Moved Permanently"),
+    (304, "Not Modified"),
+    (400, "Bad Request"),
+    (401, "Unauthorized"),
+    (404, "Not Found"),
+    (429, "Too Many Requests"),
+    (500, "Internal Server Error"),
+    (503, "Service Unavailable"),
+];
+
+/// The names of optional headers of generated requests.
+const REQUEST_HEADERS: &[&str] = &[
+    "Accept",
+    "Accept-Encoding",
+    "Accept-Language",
+    "Authorization",
+    "Cache-Control",
+    "Cookie",
+    "User-Agent",
+    "X-Forwarded-For",
+    "X-Request-Id",
+];
+
+/// The names of optional headers of generated responses.
+const RESPONSE_HEADERS: &[&str] = &[
+    "Cache-Control",
+    "Content-Type",
+    "ETag",
+    "Location",
+    "Server",
+    "Set-Cookie",
+    "Vary",
+    "X-Request-Id",
+];
+
+/// The range of the length of the value of an oversized header.
+const OVERSIZED_LEN: std::ops::RangeInclusive<usize> = 8 * 1024..=16 * 1024;
+
+/// Options of [`generate_http_request_with`] and [`generate_http_response_with`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::HttpOpts;
+///
+/// let opts = HttpOpts {
+///     duplicate_headers: true,
+///     folded_headers: true,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_http_request_with(&opts);
+/// let x = String::from_utf8_lossy(&x);
+/// assert!(x.contains("\r\nX-Folded: "));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpOpts {
+    /// The maximum number of optional headers. Defaults to 8.
+    pub max_headers: usize,
+    /// The maximum length of the body. Defaults to 256.
+    pub max_body_len: usize,
+    /// Whether to repeat an optional header with a different value.
+    pub duplicate_headers: bool,
+    /// Whether to fold an optional header value over two lines, i.e., the obsolete line folding
+    /// of RFC 7230.
+    pub folded_headers: bool,
+    /// Whether to add a header whose value is between 8 and 16 KiB long.
+    pub oversized_headers: bool,
+    /// Whether to deliberately violate the message grammar.
+    pub invalid: bool,
+}
+
+impl Default for HttpOpts {
+    fn default() -> Self {
+        Self {
+            max_headers: 8,
+            max_body_len: 256,
+            duplicate_headers: false,
+            folded_headers: false,
+            oversized_headers: false,
+            invalid: false,
+        }
+    }
+}
+
+/// Returns a random header value of visible ASCII characters.
+fn value(rng: &mut impl Rng) -> String {
+    let length = rng.random_range(1..=24);
+    generate_string(length, &Charset::UrlSafe)
+}
+
+/// Returns whether a response of the given status code must not have a body, i.e., is
+/// informational, `204 No Content` or `304 Not Modified`, as required by RFC 9110.
+fn is_bodiless(status: u16) -> bool {
+    status < 200 || status == 204 || status == 304
+}
+
+/// Assembles a message from its start line, headers and body, applying the quirks and, if
+/// requested, a single grammar violation.
+///
+/// Unless the message is invalid, a message which must not have a body has neither a body nor a
+/// `Content-Length` header.
+fn assemble(
+    rng: &mut impl Rng,
+    start_line: String,
+    names: &[&str],
+    mut headers: Vec<(String, String)>,
+    bodiless: bool,
+    opts: &HttpOpts,
+) -> Vec<u8> {
+    let count = rng.random_range(0..=opts.max_headers);
+    for _ in 0..count {
+        if let Some(name) = names.choose(rng) {
+            headers.push((name.to_string(), value(rng)));
+        }
+    }
+    if opts.duplicate_headers {
+        let name = names.choose(rng).copied().unwrap_or("X-Duplicate");
+        headers.push((name.to_string(), value(rng)));
+        headers.push((name.to_string(), value(rng)));
+    }
+    if opts.folded_headers {
+        headers.push((
+            "X-Folded".to_string(),
+            format!("{}\r\n {}", value(rng), value(rng)),
+        ));
+    }
+    if opts.oversized_headers {
+        let length = rng.random_range(OVERSIZED_LEN);
+        headers.push((
+            "X-Oversized".to_string(),
+            generate_string(length, &Charset::UrlSafe),
+        ));
+    }
+    let bodiless = bodiless && !opts.invalid;
+    let body_len = if bodiless {
+        0
+    } else {
+        rng.random_range(0..=opts.max_body_len)
+    };
+    let body = super::generate_bytes(body_len);
+    let mut content_length = body_len.to_string();
+
+    let mut eol = "\r\n";
+    let mut terminated = true;
+    let mut separator = ": ";
+    if opts.invalid {
+        match rng.random_range(0..6) {
+            0 => eol = "\n",
+            1 => separator = " : ",
+            2 => content_length = (body_len + rng.random_range(1..=64)).to_string(),
+            3 => content_length = format!("-{}", body_len.max(1)),
+            4 => terminated = false,
+            _ => headers.push(("X-Control".to_string(), format!("{}\0\x07", value(rng)))),
+        }
+    }
+    headers.shuffle(rng);
+    if !bodiless {
+        headers.push(("Content-Length".to_string(), content_length));
+    }
+
+    let mut message = start_line;
+    message.push_str(eol);
+    for (name, value) in &headers {
+        message.push_str(name);
+        message.push_str(separator);
+        message.push_str(value);
+        message.push_str(eol);
+    }
+    if terminated {
+        message.push_str(eol);
+    }
+    let mut message = message.into_bytes();
+    if terminated {
+        message.extend_from_slice(&body);
+    }
+    message
+}
+
+/// Generates a random, syntactically valid raw HTTP/1.1 request.
+///
+/// # Returns
+/// - The bytes of a request with a request line, a `Host` header, random optional headers, and a
+///   random binary body delimited by `Content-Length`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_http_request();
+/// let head = String::from_utf8_lossy(&x);
+/// assert!(head.lines().next().unwrap().ends_with(" HTTP/1.1"));
+/// assert!(head.contains("\r\nHost: "));
+/// ```
+pub fn generate_http_request() -> Vec<u8> {
+    generate_http_request_with(&HttpOpts::default())
+}
+
+/// Generates a random raw HTTP/1.1 request with the given options.
+///
+/// If `opts.invalid` is `true`, the request violates the grammar in exactly one way, e.g., a
+/// malformed request line, bare `LF` line endings, whitespace before a header colon, a
+/// `Content-Length` not matching the body, control characters in a header value, or an
+/// unterminated header section.
+///
+/// # Parameters
+/// - `opts`: The options of the generated request.
+///
+/// # Returns
+/// - The bytes of a request.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::HttpOpts;
+///
+/// let opts = HttpOpts {
+///     oversized_headers: true,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_http_request_with(&opts);
+/// assert!(x.len() > 8 * 1024);
+///
+/// // A strict parser accepts every valid request, and rejects every invalid one.
+/// fn parses(x: &[u8]) -> bool {
+///     let Some(end) = x.windows(4).position(|w| w == b"\r\n\r\n") else {
+///         return false;
+///     };
+///     let Ok(head) = std::str::from_utf8(&x[..end]) else {
+///         return false;
+///     };
+///     let mut lines = head.split("\r\n");
+///     let start: Vec<&str> = lines.next().unwrap().split(' ').collect();
+///     if start.len() != 3
+///         || !start[0].bytes().all(|b| b.is_ascii_uppercase())
+///         || start[2] != "HTTP/1.1"
+///     {
+///         return false;
+///     }
+///     let mut length = None;
+///     for line in lines {
+///         let Some((name, value)) = line.split_once(':') else {
+///             return false;
+///         };
+///         if name.ends_with(' ') || value.bytes().any(|b| b.is_ascii_control()) {
+///             return false;
+///         }
+///         if name == "Content-Length" {
+///             length = value.trim().parse::<usize>().ok();
+///         }
+///     }
+///     length == Some(x.len() - end - 4)
+/// }
+///
+/// for invalid in [false, true] {
+///     let opts = HttpOpts {
+///         invalid,
+///         ..Default::default()
+///     };
+///     for _ in 0..100 {
+///         let x = regd_testing::rand::generate_http_request_with(&opts);
+///         assert_eq!(parses(&x), !invalid, "{}", String::from_utf8_lossy(&x));
+///     }
+/// }
+/// ```
+pub fn generate_http_request_with(opts: &HttpOpts) -> Vec<u8> {
+    let mut rng = super::rng();
+    let method = METHODS.choose(&mut rng).copied().unwrap_or("GET");
+    let depth = rng.random_range(0..=3);
+    let mut target: String = (0..depth)
+        .map(|_| {
+            format!(
+                "/{}",
+                generate_string(rng.random_range(1..=8), &Charset::Lowercase)
+            )
+        })
+        .collect();
+    if target.is_empty() {
+        target.push('/');
+    }
+    let mut start_line = format!("{method} {target} HTTP/1.1");
+    let mut opts = opts.clone();
+    if opts.invalid && rng.random_bool(0.25) {
+        opts.invalid = false;
+        start_line = match rng.random_range(0..3) {
+            0 => {
+                let (left, right) = method.split_at(rng.random_range(1..method.len()));
+                format!("{left} {right} {target} HTTP/1.1")
+            }
+            1 => format!("{method} {target}"),
+            _ => format!("{method} {target} HTTP/{}", rng.random_range(2..=9)),
+        };
+    }
+    let host = format!(
+        "{}.test",
+        generate_string(rng.random_range(1..=12), &Charset::Lowercase)
+    );
+    let headers = vec![("Host".to_string(), host)];
+    assemble(&mut rng, start_line, REQUEST_HEADERS, headers, false, &opts)
+}
+
+/// Generates a random, syntactically valid raw HTTP/1.1 response.
+///
+/// # Returns
+/// - The bytes of a response with a status line of a common status code, random optional
+///   headers, and a random binary body delimited by `Content-Length`, except for `204` and `304`
+///   responses, which have neither.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_http_response();
+/// assert!(x.starts_with(b"HTTP/1.1 "));
+/// ```
+pub fn generate_http_response() -> Vec<u8> {
+    generate_http_response_with(&HttpOpts::default())
+}
+
+/// Generates a random raw HTTP/1.1 response with the given options.
+///
+/// If `opts.invalid` is `true`, the response violates the grammar in exactly one way, e.g., a
+/// status code out of range or not numeric, bare `LF` line endings, whitespace before a header
+/// colon, a `Content-Length` not matching the body, control characters in a header value, or an
+/// unterminated header section.
+///
+/// # Parameters
+/// - `opts`: The options of the generated response.
+///
+/// # Returns
+/// - The bytes of a response.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::HttpOpts;
+///
+/// for _ in 0..100 {
+///     let x = regd_testing::rand::generate_http_response_with(&HttpOpts::default());
+///     let end = x.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+///     let head = std::str::from_utf8(&x[..end]).unwrap();
+///     let status: u16 = head["HTTP/1.1 ".len()..][..3].parse().unwrap();
+///     let length = head.lines().find_map(|line| line.strip_prefix("Content-Length: "));
+///     let body = &x[end + 4..];
+///     if status == 204 || status == 304 {
+///         assert!(length.is_none() && body.is_empty());
+///     } else {
+///         assert_eq!(length.unwrap().parse::<usize>().unwrap(), body.len());
+///     }
+/// }
+///
+/// let opts = HttpOpts {
+///     invalid: true,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_http_response_with(&opts);
+/// assert!(x.starts_with(b"HTTP/1.1 "));
+/// ```
+pub fn generate_http_response_with(opts: &HttpOpts) -> Vec<u8> {
+    let mut rng = super::rng();
+    let (status, reason) = STATUSES.choose(&mut rng).copied().unwrap_or((200, "OK"));
+    let mut start_line = format!("HTTP/1.1 {status} {reason}");
+    let mut opts = opts.clone();
+    if opts.invalid && rng.random_bool(0.25) {
+        opts.invalid = false;
+        start_line = match rng.random_range(0..3) {
+            0 => format!("HTTP/1.1 {} {reason}", rng.random_range(1000..=9999)),
+            1 => format!("HTTP/1.1 {} {reason}", rng.random_range(0..=99)),
+            _ => format!("HTTP/1.1 {reason}"),
+        };
+    }
+    assemble(
+        &mut rng,
+        start_line,
+        RESPONSE_HEADERS,
+        Vec::new(),
+        is_bodiless(status),
+        &opts,
+    )
+}
@@ -0,0 +1,138 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random identifier generators.
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use super::Charset;
+
+/// The positions of the hyphens within the canonical textual representation of a UUID.
+const UUID_HYPHENS: [usize; 4] = [8, 13, 18, 23];
+
+/// The length of the canonical textual representation of a UUID.
+const UUID_LENGTH: usize = 36;
+
+/// Returns the 16 bytes of a random RFC 4122 version 4 UUID.
+fn uuid_v4_bytes() -> [u8; 16] {
+    let mut bytes: [u8; 16] = super::rng().random();
+    // Version 4 (random) in the high nibble of the 7th byte.
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    // RFC 4122 variant (`10xx`) in the high bits of the 9th byte.
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    bytes
+}
+
+/// Generates a random RFC 4122 version 4 UUID in its canonical textual representation.
+///
+/// # Returns
+/// - A `String` of the form `xxxxxxxx-xxxx-4xxx-yxxx-xxxxxxxxxxxx` with lowercase hexadecimal
+///   digits, where `y` is one of `8`, `9`, `a` or `b`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_uuid();
+/// assert_eq!(x.len(), 36);
+/// assert_eq!(&x[14..15], "4");
+/// ```
+pub fn generate_uuid() -> String {
+    let hex: String = uuid_v4_bytes().iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Generates a random RFC 4122 version 4 UUID as a [`uuid::Uuid`].
+///
+/// # Returns
+/// - A randomly generated version 4 `Uuid`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_uuid_v4();
+/// assert_eq!(x.get_version_num(), 4);
+/// ```
+#[cfg(feature = "uuid")]
+pub fn generate_uuid_v4() -> ::uuid::Uuid {
+    ::uuid::Uuid::from_bytes(uuid_v4_bytes())
+}
+
+/// Generates a random string which looks like a UUID but is not a valid one.
+///
+/// This function produces near-miss variants of UUIDs, which are useful for testing validators
+/// and parsers:
+/// - If `length` is shorter or longer than 36, hexadecimal digits are removed from or inserted
+///   into the groups of a valid UUID, while the hyphens are kept in place where possible.
+/// - If `length` is exactly 36, either one hexadecimal digit is replaced with a non-hexadecimal
+///   letter, or one hyphen is swapped with a neighboring digit.
+///
+/// # Parameters
+/// - `length`: The number of characters of the generated string.
+///
+/// # Returns
+/// - A `String` of `length` characters which cannot be parsed as a UUID.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_uuid_like(36);
+/// assert_eq!(x.len(), 36);
+/// assert_ne!(x, regd_testing::rand::generate_uuid());
+///
+/// let y = regd_testing::rand::generate_uuid_like(35);
+/// assert_eq!(y.len(), 35);
+/// ```
+pub fn generate_uuid_like(length: usize) -> String {
+    let mut rng = super::rng();
+    let mut chars: Vec<char> = generate_uuid().chars().collect();
+    let digits =
+        |chars: &[char]| -> Vec<usize> { (0..chars.len()).filter(|i| chars[*i] != '-').collect() };
+    if length < UUID_LENGTH {
+        while chars.len() > length {
+            let i = match digits(&chars).choose(&mut rng) {
+                Some(&i) => i,
+                None => chars.len() - 1,
+            };
+            chars.remove(i);
+        }
+    } else if length > UUID_LENGTH {
+        while chars.len() < length {
+            let i = rng.random_range(0..=chars.len());
+            chars.insert(i, rng.sample(Charset::Hex));
+        }
+    } else if rng.random_bool(0.5) {
+        let i = *digits(&chars).choose(&mut rng).unwrap_or(&0);
+        chars[i] = rng.random_range('g'..='z');
+    } else {
+        let hyphen = *UUID_HYPHENS.choose(&mut rng).unwrap_or(&8);
+        let neighbor = if rng.random_bool(0.5) {
+            hyphen - 1
+        } else {
+            hyphen + 1
+        };
+        chars.swap(hyphen, neighbor);
+    }
+    chars.into_iter().collect()
+}
@@ -0,0 +1,230 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of interoperability with other property
+//! testing crates.
+
+#[cfg(feature = "proptest")]
+use std::fmt;
+#[cfg(feature = "proptest")]
+use std::marker::PhantomData;
+#[cfg(feature = "proptest")]
+use std::sync::Arc;
+
+#[cfg(feature = "proptest")]
+use proptest::num::u64;
+#[cfg(feature = "proptest")]
+use proptest::strategy::{NewTree, Strategy, ValueTree};
+#[cfg(feature = "proptest")]
+use proptest::test_runner::TestRunner;
+#[cfg(feature = "quickcheck")]
+use rand::Rng;
+
+#[cfg(feature = "proptest")]
+use super::generator::Gen;
+
+/// The size passed to [`quickcheck::Gen`] by [`generate_arbitrary`], which is the default size
+/// of quickcheck itself.
+#[cfg(feature = "quickcheck")]
+const ARBITRARY_SIZE: usize = 100;
+
+/// A `proptest` strategy drawing its values from a [`Gen`], created by [`Gen::into_strategy`].
+///
+/// Each value is generated with the random number generator of the current thread temporarily
+/// reseeded from the random number generator of the `proptest` runner, so that the failures
+/// persisted by `proptest` are replayed with the same values, while the state of the generator
+/// of the thread is restored afterwards. Failing values are simplified through
+/// [`Gen::shrink`], trying the candidates from the simplest one.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "proptest")]
+/// # {
+/// use proptest::prelude::*;
+/// use regd_testing::rand::generator::{self, Gen};
+///
+/// proptest!(|(x in generator::range(1u32..100).into_strategy())| {
+///     prop_assert!((1..100).contains(&x));
+/// });
+/// # }
+/// ```
+#[cfg(feature = "proptest")]
+pub struct GenStrategy<G, T> {
+    inner: Arc<G>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "proptest")]
+impl<G, T> GenStrategy<G, T> {
+    /// Creates a strategy drawing its values from `generator`.
+    pub fn new(generator: G) -> Self {
+        Self {
+            inner: Arc::new(generator),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<G, T> Clone for GenStrategy<G, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<G, T> fmt::Debug for GenStrategy<G, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GenStrategy").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<G, T> Strategy for GenStrategy<G, T>
+where
+    G: Gen<T>,
+    T: Clone + fmt::Debug,
+{
+    type Tree = GenValueTree<G, T>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let seed = u64::ANY.new_tree(runner)?.current();
+        let current = {
+            let _rng = super::rng::save();
+            super::set_seed(seed);
+            self.inner.generate()
+        };
+        Ok(GenValueTree {
+            inner: Arc::clone(&self.inner),
+            accepted: current.clone(),
+            current,
+            candidates: Vec::new(),
+            next: 0,
+            pending: true,
+        })
+    }
+}
+
+/// The value tree of a [`GenStrategy`], which simplifies the values through [`Gen::shrink`].
+#[cfg(feature = "proptest")]
+pub struct GenValueTree<G, T> {
+    inner: Arc<G>,
+    /// The simplest value known to fail.
+    accepted: T,
+    /// The value under test.
+    current: T,
+    /// The candidates simpler than `accepted`.
+    candidates: Vec<T>,
+    /// The index of the next candidate to try.
+    next: usize,
+    /// Whether `current` has not been proven to pass yet.
+    pending: bool,
+}
+
+#[cfg(feature = "proptest")]
+impl<G, T> GenValueTree<G, T>
+where
+    T: Clone,
+{
+    /// Moves on to the next candidate, if any, returning whether `current` has changed.
+    fn advance(&mut self) -> bool {
+        match self.candidates.get(self.next) {
+            Some(candidate) => {
+                self.current = candidate.clone();
+                self.next += 1;
+                self.pending = true;
+                true
+            }
+            None => {
+                self.current = self.accepted.clone();
+                self.pending = false;
+                false
+            }
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<G, T: fmt::Debug> fmt::Debug for GenValueTree<G, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GenValueTree")
+            .field("current", &self.current)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<G, T> ValueTree for GenValueTree<G, T>
+where
+    G: Gen<T>,
+    T: Clone + fmt::Debug,
+{
+    type Value = T;
+
+    fn current(&self) -> T {
+        self.current.clone()
+    }
+
+    fn simplify(&mut self) -> bool {
+        if self.pending {
+            self.accepted = self.current.clone();
+            self.candidates = self.inner.shrink(&self.accepted);
+            self.next = 0;
+        }
+        self.advance()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.advance()
+    }
+}
+
+/// Generates a random value of a type implementing [`quickcheck::Arbitrary`].
+///
+/// This function makes the types which already describe their random values for `quickcheck`
+/// usable with the rest of this crate, e.g., as the input of [`check`](super::check).
+///
+/// # Returns
+/// - A randomly generated value of type `T`.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "quickcheck")]
+/// # {
+/// use regd_testing;
+///
+/// let x: Vec<u8> = regd_testing::rand::generate_arbitrary();
+/// assert!(x.len() <= 100);
+///
+/// regd_testing::rand::set_seed(42);
+/// let a: String = regd_testing::rand::generate_arbitrary();
+/// regd_testing::rand::set_seed(42);
+/// let b: String = regd_testing::rand::generate_arbitrary();
+/// assert_eq!(a, b);
+/// # }
+/// ```
+///
+/// # Notes
+/// - The [`quickcheck::Gen`] is seeded from the random number generator of this crate, so that
+///   the values are reproducible through [`set_seed`](super::set_seed), and has the default size
+///   of `quickcheck`, i.e., `100`.
+#[cfg(feature = "quickcheck")]
+pub fn generate_arbitrary<T: quickcheck::Arbitrary>() -> T {
+    let mut r#gen = quickcheck::Gen::from_size_and_seed(ARBITRARY_SIZE, super::rng().random());
+    T::arbitrary(&mut r#gen)
+}
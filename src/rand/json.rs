@@ -0,0 +1,224 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random JSON value generators.
+
+use rand::Rng;
+use serde_json::{Map, Number, Value};
+
+use super::{Weighted, generate_alphanumeric, generate_unicode};
+
+/// The kinds of JSON nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+/// The relative weights with which each kind of JSON node is generated.
+///
+/// The weights are relative to each other and do not need to sum up to `1.0`. Setting a weight
+/// to `0.0` disables the corresponding kind of node.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::JsonWeights;
+///
+/// // Flat documents made of strings and numbers only.
+/// let weights = JsonWeights {
+///     null: 0.0,
+///     bool: 0.0,
+///     array: 0.0,
+///     object: 0.0,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_json_with(4, 4, &weights);
+/// assert!(x.is_string() || x.is_number());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JsonWeights {
+    /// The weight of `null` nodes.
+    pub null: f64,
+    /// The weight of boolean nodes.
+    pub bool: f64,
+    /// The weight of number nodes, which are either integers or finite floats.
+    pub number: f64,
+    /// The weight of string nodes.
+    pub string: f64,
+    /// The weight of array nodes.
+    pub array: f64,
+    /// The weight of object nodes.
+    pub object: f64,
+}
+
+impl Default for JsonWeights {
+    fn default() -> Self {
+        Self {
+            null: 1.0,
+            bool: 1.0,
+            number: 2.0,
+            string: 2.0,
+            array: 1.0,
+            object: 1.0,
+        }
+    }
+}
+
+impl JsonWeights {
+    /// Builds the distribution of node kinds.
+    fn distribution(&self) -> Weighted<Kind> {
+        Weighted::builder()
+            .add(Kind::Null, self.null)
+            .add(Kind::Bool, self.bool)
+            .add(Kind::Number, self.number)
+            .add(Kind::String, self.string)
+            .add(Kind::Array, self.array)
+            .add(Kind::Object, self.object)
+            .build()
+    }
+}
+
+/// Returns a random number of container elements, which is zero at the maximum depth.
+fn width(rng: &mut impl Rng, depth: usize, max_width: usize) -> usize {
+    if depth == 0 {
+        0
+    } else {
+        rng.random_range(0..=max_width)
+    }
+}
+
+/// Generates a random JSON node, recursing into containers until `depth` reaches zero.
+fn node(rng: &mut impl Rng, kinds: &Weighted<Kind>, depth: usize, max_width: usize) -> Value {
+    match rng.sample(kinds) {
+        Kind::Null => Value::Null,
+        Kind::Bool => Value::Bool(rng.random()),
+        Kind::Number => {
+            if rng.random_bool(0.5) {
+                Value::Number(Number::from(rng.random::<i64>()))
+            } else {
+                let float = rng.random_range(-1e9..1e9);
+                Number::from_f64(float).map_or(Value::Null, Value::Number)
+            }
+        }
+        Kind::String => {
+            let length = rng.random_range(0..=16);
+            if rng.random_bool(0.75) {
+                Value::String(generate_alphanumeric(length))
+            } else {
+                Value::String(generate_unicode(length))
+            }
+        }
+        Kind::Array => {
+            let width = width(rng, depth, max_width);
+            Value::Array(
+                (0..width)
+                    .map(|_| node(rng, kinds, depth - 1, max_width))
+                    .collect(),
+            )
+        }
+        Kind::Object => {
+            let width = width(rng, depth, max_width);
+            let mut map = Map::new();
+            for _ in 0..width {
+                let length = rng.random_range(1..=8);
+                map.insert(
+                    generate_alphanumeric(length),
+                    node(rng, kinds, depth - 1, max_width),
+                );
+            }
+            Value::Object(map)
+        }
+    }
+}
+
+/// Generates a random JSON value.
+///
+/// This function is a shorthand of [`generate_json_with`] called with the default
+/// [`JsonWeights`].
+///
+/// # Parameters
+/// - `max_depth`: The maximum nesting depth of arrays and objects.
+/// - `max_width`: The maximum number of elements of each array and object.
+///
+/// # Returns
+/// - A randomly generated `serde_json::Value`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_json(3, 4);
+/// println!("Generated JSON: {}", x);
+/// ```
+pub fn generate_json(max_depth: usize, max_width: usize) -> Value {
+    generate_json_with(max_depth, max_width, &JsonWeights::default())
+}
+
+/// Generates a random JSON value whose node kinds follow the given weights.
+///
+/// Arrays and objects nested `max_depth` levels deep are always generated empty, so that the
+/// generated value never exceeds the maximum depth.
+///
+/// # Parameters
+/// - `max_depth`: The maximum nesting depth of arrays and objects.
+/// - `max_width`: The maximum number of elements of each array and object.
+/// - `weights`: The relative weights of each kind of node.
+///
+/// # Returns
+/// - A randomly generated `serde_json::Value`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::JsonWeights;
+///
+/// let weights = JsonWeights {
+///     object: 10.0,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_json_with(2, 8, &weights);
+/// println!("Generated JSON: {}", x);
+/// ```
+///
+/// # Panics
+/// - This function will panic if any weight is negative or not a number, or if all weights are
+///   zero.
+pub fn generate_json_with(max_depth: usize, max_width: usize, weights: &JsonWeights) -> Value {
+    let kinds = weights.distribution();
+    node(&mut super::rng(), &kinds, max_depth, max_width)
+}
+
+/// Generates the serialized form of a random JSON value.
+///
+/// # Parameters
+/// - `max_depth`: The maximum nesting depth of arrays and objects.
+/// - `max_width`: The maximum number of elements of each array and object.
+///
+/// # Returns
+/// - A `String` containing a randomly generated, valid JSON document.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_json_string(3, 4);
+/// assert!(serde_json::from_str::<serde_json::Value>(&x).is_ok());
+/// ```
+pub fn generate_json_string(max_depth: usize, max_width: usize) -> String {
+    generate_json(max_depth, max_width).to_string()
+}
@@ -0,0 +1,138 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of regex-driven string generators.
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use regex_syntax::Parser;
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+/// Generates a random string which matches the given regular expression.
+///
+/// This function parses `pattern` with the syntax of the `regex` crate and walks the resulting
+/// expression, picking a random branch of every alternation, a random member of every character
+/// class and a random count for every repetition. Unbounded repetitions, e.g., `*`, `+` or
+/// `{n,}`, repeat at most `max_repeat` times beyond their minimum.
+///
+/// Anchors and word boundaries, e.g., `^`, `$` or `\b`, are ignored and produce no characters,
+/// so patterns relying on them to reject an input may yield strings which do not match. Classes
+/// of arbitrary bytes, which can only be written with Unicode mode disabled, are restricted to
+/// their ASCII members.
+///
+/// # Parameters
+/// - `pattern`: The regular expression the generated string should match.
+/// - `max_repeat`: The maximum number of extra repetitions of unbounded repetition operators.
+///
+/// # Returns
+/// - A `String` matching `pattern`.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "regex")]
+/// # {
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_matching("[a-f0-9]{8}-v[0-9]+", 4);
+/// let (id, version) = x.split_once("-v").unwrap();
+/// assert_eq!(id.len(), 8);
+/// assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+/// assert!((1..=5).contains(&version.len()));
+/// assert!(version.chars().all(|c| c.is_ascii_digit()));
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if `pattern` is not a valid regular expression, or if it contains
+///   a character class which matches nothing or, in the case of byte classes, no ASCII byte.
+pub fn generate_matching(pattern: &str, max_repeat: usize) -> String {
+    let hir = Parser::new()
+        .parse(pattern)
+        .unwrap_or_else(|e| panic!("cannot sample invalid pattern: {e}"));
+    let mut rng = super::rng();
+    let mut out = String::new();
+    push_matching(&mut rng, &hir, max_repeat, &mut out);
+    out
+}
+
+/// Appends a random string matching `hir` to `out`.
+fn push_matching(rng: &mut impl Rng, hir: &Hir, max_repeat: usize, out: &mut String) {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => out.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(Class::Unicode(class)) => {
+            let ranges: Vec<_> = class
+                .ranges()
+                .iter()
+                .map(|r| (u32::from(r.start()), u32::from(r.end())))
+                .collect();
+            out.push(sample_class(rng, &ranges));
+        }
+        HirKind::Class(Class::Bytes(class)) => {
+            let ranges: Vec<_> = class
+                .ranges()
+                .iter()
+                .filter(|r| r.start().is_ascii())
+                .map(|r| (u32::from(r.start()), u32::from(r.end().min(0x7F))))
+                .collect();
+            out.push(sample_class(rng, &ranges));
+        }
+        HirKind::Repetition(repetition) => {
+            let min = repetition.min as usize;
+            let max = repetition
+                .max
+                .map_or(min.saturating_add(max_repeat), |max| max as usize);
+            for _ in 0..rng.random_range(min..=max) {
+                push_matching(rng, &repetition.sub, max_repeat, out);
+            }
+        }
+        HirKind::Capture(capture) => push_matching(rng, &capture.sub, max_repeat, out),
+        HirKind::Concat(subs) => {
+            for sub in subs {
+                push_matching(rng, sub, max_repeat, out);
+            }
+        }
+        HirKind::Alternation(subs) => {
+            if let Some(sub) = subs.choose(rng) {
+                push_matching(rng, sub, max_repeat, out);
+            }
+        }
+    }
+}
+
+/// Randomly selects a character from the given inclusive ranges of code points.
+///
+/// Each range is chosen with a probability proportional to its size, so that every code point
+/// is equally likely. Code points which are not valid characters, i.e., surrogates, are rejected
+/// and drawn again.
+fn sample_class(rng: &mut impl Rng, ranges: &[(u32, u32)]) -> char {
+    let total: u64 = ranges
+        .iter()
+        .map(|&(start, end)| u64::from(end - start) + 1)
+        .sum();
+    assert!(total > 0, "cannot sample empty character class");
+    loop {
+        let mut offset = rng.random_range(0..total);
+        for &(start, end) in ranges {
+            let size = u64::from(end - start) + 1;
+            if offset < size {
+                if let Some(c) = char::from_u32(start + offset as u32) {
+                    return c;
+                }
+                break;
+            }
+            offset -= size;
+        }
+    }
+}
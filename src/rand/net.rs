@@ -0,0 +1,579 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random network address generators.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use crate::error::Error;
+
+/// The first port of the dynamic (ephemeral) port range as defined by IANA.
+const EPHEMERAL_PORT_START: u16 = 49152;
+
+/// The characters allowed at the start and the end of a hostname label.
+const HOSTNAME_ALNUM: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// The maximum length of a hostname label as defined by RFC 1123.
+const MAX_LABEL_LEN: usize = 63;
+
+/// The maximum length of a hostname as defined by RFC 1123.
+const MAX_HOSTNAME_LEN: usize = 253;
+
+/// The scope to which generated IP addresses are restricted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IpScope {
+    /// Any address, including reserved and special-purpose ones.
+    #[default]
+    Any,
+    /// Private addresses, i.e., `10.0.0.0/8`, `172.16.0.0/12` and `192.168.0.0/16` for IPv4, and
+    /// unique local addresses `fc00::/7` for IPv6.
+    Private,
+    /// Loopback addresses, i.e., `127.0.0.0/8` for IPv4 and `::1` for IPv6.
+    Loopback,
+    /// Globally routable addresses, i.e., addresses which are not private, loopback, link-local,
+    /// multicast, documentation, or otherwise reserved. IPv6 addresses are drawn from the global
+    /// unicast range `2000::/3`.
+    Global,
+}
+
+/// Returns `true` if the given IPv4 address belongs to a special-purpose range.
+fn is_special_ipv4(addr: Ipv4Addr) -> bool {
+    let [a, b, c, _] = addr.octets();
+    addr.is_private()
+        || addr.is_loopback()
+        || addr.is_link_local()
+        || addr.is_multicast()
+        || addr.is_broadcast()
+        || addr.is_documentation()
+        || a == 0
+        || a >= 240
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (18..20).contains(&b))
+}
+
+/// Returns `true` if the given IPv6 address belongs to a special-purpose range within `2000::/3`.
+fn is_special_ipv6(addr: Ipv6Addr) -> bool {
+    let segments = addr.segments();
+    match segments[0] {
+        // IETF protocol assignments (`2001::/23`) and documentation (`2001:db8::/32`).
+        0x2001 => segments[1] < 0x0200 || segments[1] == 0x0db8,
+        // 6to4 (`2002::/16`).
+        0x2002 => true,
+        _ => false,
+    }
+}
+
+/// Generates a random IPv4 address without any restriction.
+///
+/// # Returns
+/// - A randomly generated `Ipv4Addr`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net;
+///
+/// let x = net::generate_ipv4();
+/// println!("Generated address: {}", x);
+/// ```
+pub fn generate_ipv4() -> Ipv4Addr {
+    generate_ipv4_in(IpScope::Any)
+}
+
+/// Generates a random IPv4 address restricted to the given scope.
+///
+/// # Parameters
+/// - `scope`: The scope to which the generated address is restricted.
+///
+/// # Returns
+/// - A randomly generated `Ipv4Addr` within `scope`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net::{self, IpScope};
+///
+/// assert!(net::generate_ipv4_in(IpScope::Private).is_private());
+/// assert!(net::generate_ipv4_in(IpScope::Loopback).is_loopback());
+/// ```
+pub fn generate_ipv4_in(scope: IpScope) -> Ipv4Addr {
+    let mut rng = super::rng();
+    match scope {
+        IpScope::Any => Ipv4Addr::from(rng.random::<u32>()),
+        IpScope::Private => {
+            let (network, prefix) = match rng.random_range(0..3) {
+                0 => (Ipv4Addr::new(10, 0, 0, 0), 8),
+                1 => (Ipv4Addr::new(172, 16, 0, 0), 12),
+                _ => (Ipv4Addr::new(192, 168, 0, 0), 16),
+            };
+            let host = rng.random::<u32>() >> prefix;
+            Ipv4Addr::from(u32::from(network) | host)
+        }
+        IpScope::Loopback => {
+            Ipv4Addr::from(u32::from(Ipv4Addr::new(127, 0, 0, 0)) | rng.random::<u32>() >> 8)
+        }
+        IpScope::Global => loop {
+            let addr = Ipv4Addr::from(rng.random::<u32>());
+            if !is_special_ipv4(addr) {
+                return addr;
+            }
+        },
+    }
+}
+
+/// Generates a random IPv6 address without any restriction.
+///
+/// # Returns
+/// - A randomly generated `Ipv6Addr`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net;
+///
+/// let x = net::generate_ipv6();
+/// println!("Generated address: {}", x);
+/// ```
+pub fn generate_ipv6() -> Ipv6Addr {
+    generate_ipv6_in(IpScope::Any)
+}
+
+/// Generates a random IPv6 address restricted to the given scope.
+///
+/// # Parameters
+/// - `scope`: The scope to which the generated address is restricted.
+///
+/// # Returns
+/// - A randomly generated `Ipv6Addr` within `scope`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net::{self, IpScope};
+///
+/// assert!(net::generate_ipv6_in(IpScope::Private).is_unique_local());
+/// assert!(net::generate_ipv6_in(IpScope::Loopback).is_loopback());
+/// ```
+pub fn generate_ipv6_in(scope: IpScope) -> Ipv6Addr {
+    let mut rng = super::rng();
+    match scope {
+        IpScope::Any => Ipv6Addr::from(rng.random::<u128>()),
+        IpScope::Private => Ipv6Addr::from((0xfc00 << 112) | rng.random::<u128>() >> 7),
+        IpScope::Loopback => Ipv6Addr::LOCALHOST,
+        IpScope::Global => loop {
+            let addr = Ipv6Addr::from((0x2000 << 112) | rng.random::<u128>() >> 3);
+            if !is_special_ipv6(addr) {
+                return addr;
+            }
+        },
+    }
+}
+
+/// Generates a random IPv4 or IPv6 address restricted to the given scope.
+///
+/// # Parameters
+/// - `scope`: The scope to which the generated address is restricted.
+///
+/// # Returns
+/// - A randomly generated `IpAddr` within `scope`, equally likely to be IPv4 or IPv6.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net::{self, IpScope};
+///
+/// assert!(net::generate_ip_in(IpScope::Loopback).is_loopback());
+/// ```
+pub fn generate_ip_in(scope: IpScope) -> IpAddr {
+    if super::rng().random_bool(0.5) {
+        IpAddr::V4(generate_ipv4_in(scope))
+    } else {
+        IpAddr::V6(generate_ipv6_in(scope))
+    }
+}
+
+/// A block of IP addresses in CIDR notation, e.g., `10.0.0.0/8`.
+///
+/// The host bits of the network address are always zero, so that two blocks covering the same
+/// addresses compare equal.
+///
+/// # Examples
+/// ```
+/// use std::net::Ipv4Addr;
+///
+/// use regd_testing::rand::net::Cidr;
+///
+/// let cidr = Cidr::new(Ipv4Addr::new(192, 168, 1, 42).into(), 24);
+/// assert_eq!(cidr.to_string(), "192.168.1.0/24");
+/// assert!(cidr.contains(Ipv4Addr::new(192, 168, 1, 7).into()));
+/// assert!(!cidr.contains(Ipv4Addr::new(192, 168, 2, 7).into()));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    /// Creates the block of the given prefix length containing `addr`.
+    ///
+    /// # Panics
+    /// - This function will panic if `prefix` is longer than the address, i.e., 32 bits for IPv4
+    ///   and 128 bits for IPv6.
+    pub fn new(addr: IpAddr, prefix: u8) -> Self {
+        let bits = address_bits(addr);
+        if u32::from(prefix) > bits {
+            panic!("cannot create /{prefix} block of {bits}-bit address {addr}");
+        }
+        Self {
+            network: from_bits(addr, to_bits(addr) & network_mask(bits, prefix)),
+            prefix,
+        }
+    }
+
+    /// Returns the network address, i.e., the first address of the block.
+    pub fn network(&self) -> IpAddr {
+        self.network
+    }
+
+    /// Returns the length of the prefix in bits.
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Returns `true` if the block contains the given address, which is never the case for an
+    /// address of the other family.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        let bits = address_bits(self.network);
+        address_bits(addr) == bits
+            && to_bits(addr) & network_mask(bits, self.prefix) == to_bits(self.network)
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix)
+    }
+}
+
+/// Returns the number of bits of an address of the family of `addr`.
+fn address_bits(addr: IpAddr) -> u32 {
+    if addr.is_ipv4() { 32 } else { 128 }
+}
+
+/// Returns the bits of `addr`, right-aligned.
+fn to_bits(addr: IpAddr) -> u128 {
+    match addr {
+        IpAddr::V4(addr) => u128::from(u32::from(addr)),
+        IpAddr::V6(addr) => u128::from(addr),
+    }
+}
+
+/// Returns the address of the family of `family` with the given right-aligned bits.
+fn from_bits(family: IpAddr, bits: u128) -> IpAddr {
+    match family {
+        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(bits as u32)),
+        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(bits)),
+    }
+}
+
+/// Returns the right-aligned mask of the network bits of a `/prefix` block of `bits`-bit
+/// addresses.
+fn network_mask(bits: u32, prefix: u8) -> u128 {
+    let all = u128::MAX >> (128 - bits);
+    all & !(all.checked_shr(u32::from(prefix)).unwrap_or(0))
+}
+
+/// Generates a random IPv4 block whose prefix length is drawn from the given range.
+///
+/// # Parameters
+/// - `prefix_range`: The range of the prefix lengths, within `0..=32`.
+///
+/// # Returns
+/// - A randomly generated IPv4 [`Cidr`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net;
+///
+/// let x = net::generate_ipv4_cidr(16..=24);
+/// assert!(x.network().is_ipv4());
+/// assert!((16..=24).contains(&x.prefix()));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `prefix_range` is empty or extends beyond 32.
+pub fn generate_ipv4_cidr(prefix_range: RangeInclusive<u8>) -> Cidr {
+    generate_cidr(IpAddr::V4(generate_ipv4()), prefix_range)
+}
+
+/// Generates a random IPv6 block whose prefix length is drawn from the given range.
+///
+/// # Parameters
+/// - `prefix_range`: The range of the prefix lengths, within `0..=128`.
+///
+/// # Returns
+/// - A randomly generated IPv6 [`Cidr`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net;
+///
+/// let x = net::generate_ipv6_cidr(48..=64);
+/// assert!(x.network().is_ipv6());
+/// assert!((48..=64).contains(&x.prefix()));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `prefix_range` is empty or extends beyond 128.
+pub fn generate_ipv6_cidr(prefix_range: RangeInclusive<u8>) -> Cidr {
+    generate_cidr(IpAddr::V6(generate_ipv6()), prefix_range)
+}
+
+/// Generates the block containing `addr` whose prefix length is drawn from the given range.
+fn generate_cidr(addr: IpAddr, prefix_range: RangeInclusive<u8>) -> Cidr {
+    assert!(!prefix_range.is_empty(), "{}", Error::EmptyRange);
+    Cidr::new(addr, super::rng().random_range(prefix_range))
+}
+
+/// Generates a random address inside the given block.
+///
+/// # Parameters
+/// - `cidr`: The block to which the generated address belongs.
+///
+/// # Returns
+/// - A randomly generated `IpAddr` contained in `cidr`, which may be its network or its
+///   broadcast address.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net;
+///
+/// let cidr = net::generate_ipv4_cidr(8..=30);
+/// assert!(cidr.contains(net::generate_ip_in_cidr(&cidr)));
+/// ```
+pub fn generate_ip_in_cidr(cidr: &Cidr) -> IpAddr {
+    let bits = address_bits(cidr.network);
+    let host = super::rng().random::<u128>() & !network_mask(bits, cidr.prefix);
+    from_bits(
+        cidr.network,
+        to_bits(cidr.network) | host & (u128::MAX >> (128 - bits)),
+    )
+}
+
+/// Generates a random address of the same family outside the given block.
+///
+/// Half of the addresses are adjacent to the block, i.e., just before or just after it, which
+/// are the boundaries most often mishandled by range checks.
+///
+/// # Parameters
+/// - `cidr`: The block to which the generated address does not belong.
+///
+/// # Returns
+/// - A randomly generated `IpAddr` of the family of `cidr` which `cidr` does not contain.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net;
+///
+/// let cidr = net::generate_ipv6_cidr(1..=128);
+/// let x = net::generate_ip_outside_cidr(&cidr);
+/// assert!(x.is_ipv6());
+/// assert!(!cidr.contains(x));
+/// ```
+///
+/// # Panics
+/// - This function will panic if the prefix of `cidr` is zero, since it then contains every
+///   address of its family.
+pub fn generate_ip_outside_cidr(cidr: &Cidr) -> IpAddr {
+    if cidr.prefix == 0 {
+        panic!("cannot generate address outside {cidr}");
+    }
+    let bits = address_bits(cidr.network);
+    let all = u128::MAX >> (128 - bits);
+    let first = to_bits(cidr.network);
+    let last = first | (all & !network_mask(bits, cidr.prefix));
+    let mut rng = super::rng();
+    if rng.random_bool(0.5) {
+        let adjacent = match (
+            first.checked_sub(1),
+            last.checked_add(1).filter(|x| *x <= all),
+        ) {
+            (Some(before), Some(after)) => {
+                if rng.random_bool(0.5) {
+                    before
+                } else {
+                    after
+                }
+            }
+            (Some(before), None) => before,
+            (None, Some(after)) => after,
+            (None, None) => unreachable!("a block with a prefix covers a part of its family"),
+        };
+        return from_bits(cidr.network, adjacent);
+    }
+    // Flipping one of the network bits leaves the block.
+    let bit = rng.random_range(0..u32::from(cidr.prefix));
+    let addr = to_bits(generate_ip_in_cidr(cidr)) ^ (1 << (bits - 1 - bit));
+    from_bits(cidr.network, addr)
+}
+
+/// Generates a random non-zero port number.
+///
+/// # Parameters
+/// - `ephemeral_only`: Whether to restrict the generated port to the dynamic (ephemeral) range
+///   `[49152, 65535]` as defined by IANA.
+///
+/// # Returns
+/// - A randomly generated port number in the range `[1, 65535]`, or `[49152, 65535]` if
+///   `ephemeral_only` is `true`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net;
+///
+/// let x = net::generate_port(true);
+/// assert!(x >= 49152);
+/// ```
+pub fn generate_port(ephemeral_only: bool) -> u16 {
+    let start = if ephemeral_only {
+        EPHEMERAL_PORT_START
+    } else {
+        1
+    };
+    super::rng().random_range(start..=u16::MAX)
+}
+
+/// Generates a random socket address without any restriction.
+///
+/// # Returns
+/// - A randomly generated `SocketAddr`, equally likely to be IPv4 or IPv6, with a non-zero port.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net;
+///
+/// let x = net::generate_socket_addr();
+/// assert_ne!(x.port(), 0);
+/// ```
+pub fn generate_socket_addr() -> SocketAddr {
+    generate_socket_addr_in(IpScope::Any)
+}
+
+/// Generates a random socket address whose IP address is restricted to the given scope.
+///
+/// # Parameters
+/// - `scope`: The scope to which the IP address of the generated socket address is restricted.
+///
+/// # Returns
+/// - A randomly generated `SocketAddr` within `scope`, with a non-zero port.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::net::{self, IpScope};
+///
+/// let x = net::generate_socket_addr_in(IpScope::Loopback);
+/// assert!(x.ip().is_loopback());
+/// ```
+pub fn generate_socket_addr_in(scope: IpScope) -> SocketAddr {
+    SocketAddr::new(generate_ip_in(scope), generate_port(false))
+}
+
+/// Generates a random MAC address.
+///
+/// The two least significant bits of the first octet are set according to the flags, while
+/// every other bit is random.
+///
+/// # Parameters
+/// - `unicast`: Whether to generate a unicast address; otherwise a multicast address is generated.
+/// - `locally_administered`: Whether to generate a locally administered address; otherwise a
+///   universally administered address is generated.
+///
+/// # Returns
+/// - The six octets of the randomly generated MAC address.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand;
+///
+/// let x = rand::generate_mac(true, true);
+/// assert_eq!(x[0] & 0b01, 0);
+/// assert_eq!(x[0] & 0b10, 0b10);
+///
+/// let formatted = x.map(|b| format!("{b:02x}")).join(":");
+/// assert_eq!(formatted.len(), 17);
+/// ```
+pub fn generate_mac(unicast: bool, locally_administered: bool) -> [u8; 6] {
+    let mut octets: [u8; 6] = super::rng().random();
+    octets[0] &= !0b11;
+    if !unicast {
+        octets[0] |= 0b01;
+    }
+    if locally_administered {
+        octets[0] |= 0b10;
+    }
+    octets
+}
+
+/// Generates a random hostname of the specified number of labels following RFC 1123.
+///
+/// Each label is made of 1 to 63 lowercase letters, digits and hyphens, and neither starts nor
+/// ends with a hyphen. The length of each label is chosen so that the whole hostname never
+/// exceeds 253 characters.
+///
+/// # Parameters
+/// - `label_count`: The number of dot-separated labels. Must be in the range `[1, 127]`.
+///
+/// # Returns
+/// - A `String` containing a valid hostname.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand;
+///
+/// let x = rand::generate_hostname(3);
+/// assert_eq!(x.split('.').count(), 3);
+/// assert!(x.len() <= 253);
+/// assert!(x.split('.').all(|l| !l.starts_with('-') && !l.ends_with('-')));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `label_count == 0` or `label_count > 127`.
+pub fn generate_hostname(label_count: usize) -> String {
+    assert!(
+        (1..=MAX_HOSTNAME_LEN.div_ceil(2)).contains(&label_count),
+        "cannot sample hostname of {label_count} labels"
+    );
+    let max_len = ((MAX_HOSTNAME_LEN + 1) / label_count - 1).min(MAX_LABEL_LEN);
+    let mut rng = super::rng();
+    let labels: Vec<String> = (0..label_count)
+        .map(|_| {
+            let length = rng.random_range(1..=max_len);
+            (0..length)
+                .map(|i| {
+                    if i == 0 || i == length - 1 || rng.random_bool(0.9) {
+                        HOSTNAME_ALNUM
+                            .choose(&mut rng)
+                            .copied()
+                            .map_or('a', char::from)
+                    } else {
+                        '-'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    labels.join(".")
+}
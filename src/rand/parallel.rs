@@ -0,0 +1,105 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of parallel bulk random value generators.
+
+use rand::distr::StandardUniform;
+use rand::prelude::Distribution;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use rayon::prelude::*;
+
+/// The number of bytes generated by each parallel task.
+const BYTES_PER_CHUNK: usize = 1024 * 1024;
+
+/// The number of values generated by each parallel task.
+const VALUES_PER_CHUNK: usize = 64 * 1024;
+
+/// Returns the generator of the chunk at `index`, derived from the master seed.
+fn chunk_rng(master: u64, index: usize) -> StdRng {
+    StdRng::seed_from_u64(master ^ (index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+/// Generates a vector of random bytes of the specified length using multiple threads.
+///
+/// This function splits the buffer into fixed-size chunks filled in parallel on the rayon
+/// thread pool. Each chunk is filled by its own generator, deterministically seeded from a
+/// master seed drawn from the random number generator of the current thread, so that the output
+/// only depends on that generator's state and not on how the chunks are scheduled. In particular,
+/// the output is reproducible after [`set_seed`](super::set_seed).
+///
+/// # Parameters
+/// - `length`: The number of random bytes to generate.
+///
+/// # Returns
+/// - A `Vec<u8>` containing `length` random bytes.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// regd_testing::rand::set_seed(42);
+/// let x = regd_testing::rand::generate_bytes_parallel(8 * 1024 * 1024);
+///
+/// regd_testing::rand::set_seed(42);
+/// let y = regd_testing::rand::generate_bytes_parallel(8 * 1024 * 1024);
+/// assert_eq!(x, y);
+/// ```
+pub fn generate_bytes_parallel(length: usize) -> Vec<u8> {
+    let master = super::rng().random::<u64>();
+    let mut bytes = vec![0; length];
+    bytes
+        .par_chunks_mut(BYTES_PER_CHUNK)
+        .enumerate()
+        .for_each(|(index, chunk)| chunk_rng(master, index).fill_bytes(chunk));
+    bytes
+}
+
+/// Generates a vector of random values of type `T` of the specified length using multiple threads.
+///
+/// This function generates the values in the same way as [`generate`](super::generate), splitting
+/// the work into fixed-size chunks on the rayon thread pool. Like [`generate_bytes_parallel`],
+/// each chunk is deterministically seeded from a master seed, so that the output is reproducible
+/// after [`set_seed`](super::set_seed).
+///
+/// # Parameters
+/// - `length`: The number of random values to generate.
+///
+/// # Returns
+/// - A `Vec<T>` containing `length` random values.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x: Vec<u64> = regd_testing::rand::generate_vec_parallel(1_000_000);
+/// assert_eq!(x.len(), 1_000_000);
+/// ```
+pub fn generate_vec_parallel<T>(length: usize) -> Vec<T>
+where
+    T: Send,
+    StandardUniform: Distribution<T>,
+{
+    let master = super::rng().random::<u64>();
+    let chunks = length.div_ceil(VALUES_PER_CHUNK);
+    (0..chunks)
+        .into_par_iter()
+        .flat_map_iter(|index| {
+            let len = VALUES_PER_CHUNK.min(length - index * VALUES_PER_CHUNK);
+            chunk_rng(master, index)
+                .sample_iter(StandardUniform)
+                .take(len)
+        })
+        .collect()
+}
@@ -0,0 +1,335 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random filesystem path generators.
+
+use std::path::{MAIN_SEPARATOR_STR, PathBuf};
+
+use rand::Rng;
+
+/// The probability with which a component is replaced with `..` when enabled.
+const PARENT_DIR_PROBABILITY: f64 = 0.25;
+
+/// Options of [`generate_path_with`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::PathOpts;
+///
+/// let opts = PathOpts {
+///     absolute: true,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_path_with(3, 8, &opts);
+/// assert!(x.is_absolute());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PathOpts {
+    /// Whether to generate an absolute path, i.e., rooted at `/` on Unix and at `C:\` on Windows.
+    pub absolute: bool,
+    /// Whether to terminate the path with a trailing separator.
+    pub trailing_separator: bool,
+    /// Whether to randomly replace components with `..`.
+    pub parent_dirs: bool,
+}
+
+/// Generates a random relative path of the specified depth.
+///
+/// This function only manipulates the path and never touches the filesystem, so that the
+/// generated path may or may not exist.
+///
+/// # Parameters
+/// - `depth`: The number of components of the generated path. Must be greater than 0.
+/// - `segment_len`: The length of each component. Must be greater than 0.
+///
+/// # Returns
+/// - A `PathBuf` consisting of `depth` random alphanumeric components joined by the platform's
+///   separator.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_path(3, 8);
+/// assert_eq!(x.components().count(), 3);
+/// assert!(x.is_relative());
+/// ```
+///
+/// # Panics
+/// - This function will panic if `depth == 0` or `segment_len == 0`.
+pub fn generate_path(depth: usize, segment_len: usize) -> PathBuf {
+    generate_path_with(depth, segment_len, &PathOpts::default())
+}
+
+/// Generates a random path of the specified depth with the given options.
+///
+/// This function only manipulates the path and never touches the filesystem, so that the
+/// generated path may or may not exist.
+///
+/// # Parameters
+/// - `depth`: The number of components of the generated path, excluding its root. Must be
+///   greater than 0.
+/// - `segment_len`: The length of each component other than `..`. Must be greater than 0.
+/// - `opts`: The options of the generated path.
+///
+/// # Returns
+/// - A `PathBuf` consisting of `depth` components joined by the platform's separator.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::PathOpts;
+///
+/// let opts = PathOpts {
+///     trailing_separator: true,
+///     parent_dirs: true,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_path_with(4, 6, &opts);
+/// assert!(x.to_string_lossy().ends_with(std::path::MAIN_SEPARATOR));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `depth == 0` or `segment_len == 0`.
+pub fn generate_path_with(depth: usize, segment_len: usize, opts: &PathOpts) -> PathBuf {
+    assert!(depth > 0, "cannot sample empty path");
+    assert!(segment_len > 0, "cannot sample empty path component");
+    let mut rng = super::rng();
+    let components: Vec<String> = (0..depth)
+        .map(|_| {
+            if opts.parent_dirs && rng.random_bool(PARENT_DIR_PROBABILITY) {
+                "..".to_string()
+            } else {
+                super::generate_alphanumeric(segment_len)
+            }
+        })
+        .collect();
+    let mut path = String::new();
+    if opts.absolute {
+        path.push_str(if cfg!(windows) { "C:\\" } else { "/" });
+    }
+    path.push_str(&components.join(MAIN_SEPARATOR_STR));
+    if opts.trailing_separator {
+        path.push_str(MAIN_SEPARATOR_STR);
+    }
+    PathBuf::from(path)
+}
+
+/// The maximum length of a file name, in bytes on Unix and in UTF-16 code units on Windows.
+const MAX_FILENAME_LEN: usize = 255;
+
+/// The maximum number of attempts of [`generate_hostile_filename_for`] to draw an accepted name.
+const MAX_HOSTILE_ATTEMPTS: usize = 1000;
+
+/// The device names reserved by Windows, regardless of case and extension.
+const WINDOWS_RESERVED: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// The operating systems whose file name rules [`generate_hostile_filename_for`] follows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TargetOs {
+    /// Unix-like systems, which accept any name of at most 255 bytes without `/` and NUL.
+    Unix,
+    /// Windows, which additionally rejects control characters, `<>:"\|?*`, trailing dots and
+    /// spaces, reserved device names such as `CON` or `nul.txt`, and names longer than 255
+    /// UTF-16 code units.
+    Windows,
+    /// Both Unix-like systems and Windows.
+    Portable,
+}
+
+impl TargetOs {
+    /// Returns the operating system the code is compiled for.
+    pub const fn current() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Unix
+        }
+    }
+
+    /// Returns `true` if the given file name may be created on the operating system.
+    ///
+    /// # Examples
+    /// ```
+    /// use regd_testing::rand::TargetOs;
+    ///
+    /// assert!(TargetOs::Unix.accepts("nul.txt"));
+    /// assert!(!TargetOs::Windows.accepts("nul.txt"));
+    /// assert!(!TargetOs::Portable.accepts("a/b"));
+    /// ```
+    pub fn accepts(&self, name: &str) -> bool {
+        let unix = !name.is_empty()
+            && name != "."
+            && name != ".."
+            && !name.contains(['/', '\0'])
+            && name.len() <= MAX_FILENAME_LEN;
+        match self {
+            Self::Unix => unix,
+            Self::Windows => accepted_by_windows(name),
+            Self::Portable => unix && accepted_by_windows(name),
+        }
+    }
+}
+
+/// Returns `true` if the given file name may be created on Windows.
+fn accepted_by_windows(name: &str) -> bool {
+    let stem = name
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(' ');
+    !name.is_empty()
+        && !name.ends_with(['.', ' '])
+        && !name
+            .chars()
+            .any(|c| c < ' ' || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*'))
+        && !WINDOWS_RESERVED
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+        && name.encode_utf16().count() <= MAX_FILENAME_LEN
+}
+
+/// Draws a file name of a random hostile kind, which may not be accepted by every system.
+fn hostile_filename<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let base = super::generate_alphanumeric(rng.random_range(1..=8));
+    let pick = |rng: &mut R, names: &[&str]| names[rng.random_range(0..names.len())].to_string();
+    match rng.random_range(0..10) {
+        // Spaces.
+        0 => match rng.random_range(0..4) {
+            0 => format!(" {base}"),
+            1 => format!("{base} "),
+            2 => format!("{base} with spaces.txt"),
+            _ => " ".repeat(rng.random_range(1..4)),
+        },
+        // Leading dashes, mistaken for options.
+        1 => pick(rng, &["-", "-rf", "--help", "-n", "--", "-e x"]),
+        // Control characters.
+        2 => pick(
+            rng,
+            &[
+                "a\nb",
+                "line\n",
+                "tab\there",
+                "\r",
+                "bell\x07",
+                "\x1b[31mred",
+            ],
+        ),
+        // Unicode which is not in NFC, or which is invisible or reordering.
+        3 => pick(
+            rng,
+            &[
+                "cafe\u{301}",
+                "\u{212b}ngstr\u{f6}m",
+                "A\u{30a}",
+                "\u{202e}txt.exe",
+                "zero\u{200b}width",
+                "\u{feff}bom",
+                "\u{1f600}.txt",
+            ],
+        ),
+        // Windows device names.
+        4 => {
+            let reserved = WINDOWS_RESERVED[rng.random_range(0..WINDOWS_RESERVED.len())];
+            match rng.random_range(0..3) {
+                0 => reserved.to_string(),
+                1 => reserved.to_ascii_lowercase(),
+                _ => format!("{}.txt", reserved.to_ascii_lowercase()),
+            }
+        }
+        // Components of the maximum length, in bytes or in code units.
+        5 => {
+            if rng.random_bool(0.5) {
+                "a".repeat(MAX_FILENAME_LEN)
+            } else {
+                "\u{e9}".repeat(MAX_FILENAME_LEN / 2)
+            }
+        }
+        // Trailing dots.
+        6 => pick(rng, &["name.", "name..", "...", ".hidden", "trailing. "]),
+        // Shell and glob metacharacters.
+        7 => pick(
+            rng,
+            &[
+                "*", "?", "[a-z]", "$HOME", "`id`", "a;b", "'\"", "~", "a&b", "!!",
+            ],
+        ),
+        // Characters which Windows rejects.
+        8 => pick(rng, &["a:b", "a<b>", "a|b", "a\\b", "\"quoted\"", "C:"]),
+        // Names which differ only by case or by normalization from a plain one.
+        _ => pick(
+            rng,
+            &["README", "readme", "ReadMe", "caf\u{e9}", "cafe\u{301}"],
+        ),
+    }
+}
+
+/// Generates a random hostile file name which may be created on the current operating system.
+///
+/// This function is a shorthand of [`generate_hostile_filename_for`] called with
+/// [`TargetOs::current`].
+///
+/// # Returns
+/// - A random hostile file name.
+///
+/// # Examples
+/// ```
+/// use regd_testing::fs::TempDir;
+///
+/// let dir = TempDir::new();
+/// for _ in 0..20 {
+///     let name = regd_testing::rand::generate_hostile_filename();
+///     std::fs::write(dir.path().join(&name), b"x").unwrap();
+/// }
+/// ```
+pub fn generate_hostile_filename() -> String {
+    generate_hostile_filename_for(TargetOs::current())
+}
+
+/// Generates a random hostile file name which may be created on the given operating system.
+///
+/// Path-handling code is usually tested with alphanumeric names only. This function instead
+/// returns names with leading, trailing or only spaces, leading dashes, control characters
+/// such as newlines, Unicode which is not in NFC or which is invisible, Windows device names
+/// such as `CON` or `nul.txt`, components of the maximum length, trailing dots, shell and glob
+/// metacharacters, and characters which Windows rejects, keeping only the names accepted by
+/// `os`.
+///
+/// # Parameters
+/// - `os`: The operating system which must accept the name.
+///
+/// # Returns
+/// - A random hostile file name accepted by `os`.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::TargetOs;
+///
+/// let x = regd_testing::rand::generate_hostile_filename_for(TargetOs::Portable);
+/// assert!(TargetOs::Windows.accepts(&x) && TargetOs::Unix.accepts(&x));
+/// ```
+pub fn generate_hostile_filename_for(os: TargetOs) -> String {
+    let mut rng = super::rng();
+    for _ in 0..MAX_HOSTILE_ATTEMPTS {
+        let name = hostile_filename(&mut rng);
+        if os.accepts(&name) {
+            return name;
+        }
+    }
+    unreachable!("every target accepts some of the hostile names")
+}
@@ -0,0 +1,215 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains the seedable random number generator shared by the generators of this crate.
+
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::panic;
+use std::sync::Once;
+
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+use super::check::SILENT;
+
+/// The state of the random number generator of a thread.
+#[derive(Clone)]
+struct Context {
+    seed: u64,
+    draws: u64,
+    rng: StdRng,
+}
+
+impl Context {
+    fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            draws: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+/// Guards the installation of the hook of [`install_failure_hook`].
+static FAILURE_HOOK: Once = Once::new();
+
+thread_local! {
+    /// The random number generator of the current thread, seeded from the OS entropy by default.
+    static CONTEXT: RefCell<Context> = RefCell::new(Context::new(rand::rng().random()));
+}
+
+/// Reseeds the random number generator of the current thread.
+///
+/// Every generator of this crate draws from a thread-local random number generator, which is
+/// seeded from the OS entropy by default. Reseeding it with a fixed seed makes the values
+/// subsequently generated on the current thread reproducible.
+///
+/// # Parameters
+/// - `seed`: The seed to install.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// regd_testing::rand::set_seed(42);
+/// let x: u64 = regd_testing::rand::generate();
+///
+/// regd_testing::rand::set_seed(42);
+/// let y: u64 = regd_testing::rand::generate();
+/// assert_eq!(x, y);
+/// ```
+pub fn set_seed(seed: u64) {
+    CONTEXT.with_borrow_mut(|context| *context = Context::new(seed));
+}
+
+/// A guard restoring the state of the random number generator of the current thread on drop, so
+/// that reseeding it temporarily does not affect the values generated afterwards.
+pub(super) struct RngGuard(Option<Context>);
+
+impl Drop for RngGuard {
+    fn drop(&mut self) {
+        if let Some(saved) = self.0.take() {
+            let _ = CONTEXT.try_with(|context| *context.borrow_mut() = saved);
+        }
+    }
+}
+
+/// Saves the state of the random number generator of the current thread until the returned guard
+/// is dropped.
+pub(super) fn save() -> RngGuard {
+    RngGuard(Some(CONTEXT.with_borrow(Context::clone)))
+}
+
+/// Returns the seed of the random number generator of the current thread.
+///
+/// This is either the seed installed by [`set_seed`], or the one drawn from the OS entropy when
+/// the thread first generated a value. Reporting it on failure allows reproducing the values
+/// generated by a test.
+///
+/// # Returns
+/// - The seed of the random number generator of the current thread.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// regd_testing::rand::set_seed(42);
+/// assert_eq!(regd_testing::rand::seed(), 42);
+/// ```
+pub fn seed() -> u64 {
+    CONTEXT.with_borrow(|context| context.seed)
+}
+
+/// A handle to the random number generator of the current thread.
+///
+/// This type is returned by [`rng`] and implements `rand::RngCore`, so that every method of
+/// `rand::Rng` draws from the seedable generator shared by this crate. Like `rand::rngs::ThreadRng`,
+/// it is neither `Send` nor `Sync`.
+#[derive(Clone, Debug, Default)]
+pub struct TestRng {
+    _marker: PhantomData<*const ()>,
+}
+
+impl RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 {
+        CONTEXT.with_borrow_mut(|context| {
+            context.draws += 1;
+            context.rng.next_u32()
+        })
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        CONTEXT.with_borrow_mut(|context| {
+            context.draws += 1;
+            context.rng.next_u64()
+        })
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        CONTEXT.with_borrow_mut(|context| {
+            context.draws += 1;
+            context.rng.fill_bytes(dst)
+        })
+    }
+}
+
+/// Returns a handle to the random number generator of the current thread.
+///
+/// # Returns
+/// - A [`TestRng`] drawing from the seedable generator shared by this crate.
+///
+/// # Examples
+/// ```
+/// use rand::Rng;
+/// use regd_testing;
+///
+/// let mut rng = regd_testing::rand::rng();
+/// let x: u8 = rng.random();
+/// println!("Generated number: {}", x);
+/// ```
+pub fn rng() -> TestRng {
+    TestRng::default()
+}
+
+/// Installs a panic hook reporting the seed of the random number generator of the panicking
+/// thread.
+///
+/// A failure often surfaces in an assertion far from the code generating the values, so that the
+/// panic message alone does not tell which values were generated. The installed hook runs the
+/// previous hook, then reports the seed of the panicking thread together with the number of
+/// values drawn since it was seeded, which allows reproducing the values through [`set_seed`].
+/// Nothing is reported for threads which have not drawn any value, nor for the panics silenced
+/// by [`check`](super::check) and [`repeat`](super::repeat) while exploring inputs.
+///
+/// This function is called by every test expanded from `#[regd_test]`, and installs the hook
+/// only once per process however many times it is called.
+///
+/// # Examples
+/// ```should_panic
+/// use regd_testing;
+///
+/// regd_testing::rand::install_failure_hook();
+/// let bytes = regd_testing::rand::generate_bytes(64);
+/// // Panics, as random bytes are not all ASCII, then reports, e.g., "the random number generator
+/// // of thread 'main' was seeded with 1234 (1 draws since); reproduce with
+/// // `regd_testing::rand::set_seed(1234)`".
+/// assert!(bytes.is_ascii());
+/// ```
+pub fn install_failure_hook() {
+    FAILURE_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            previous(info);
+            if SILENT.try_with(Cell::get).unwrap_or(false) {
+                return;
+            }
+            let state = CONTEXT
+                .try_with(|context| {
+                    let context = context.try_borrow().ok()?;
+                    (context.draws > 0).then_some((context.seed, context.draws))
+                })
+                .ok()
+                .flatten();
+            if let Some((seed, draws)) = state {
+                let thread = std::thread::current();
+                eprintln!(
+                    "the random number generator of thread '{}' was seeded with {seed} \
+                     ({draws} draws since); reproduce with `regd_testing::rand::set_seed({seed})`",
+                    thread.name().unwrap_or("<unnamed>")
+                );
+            }
+        }));
+    });
+}
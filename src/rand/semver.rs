@@ -0,0 +1,186 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random semantic version generators.
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+/// The pre-release tags, in increasing order of precedence.
+const PRE_RELEASE_TAGS: &[&str] = &["alpha", "beta", "rc"];
+
+/// Options of [`generate_semver_with`] and [`generate_semver_sequence`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::SemverOpts;
+///
+/// let opts = SemverOpts {
+///     pre_release: true,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_semver_with(&opts);
+/// assert!(x.contains('-'));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SemverOpts {
+    /// Whether to attach a pre-release identifier, e.g., `-alpha.1` or `-rc.2`.
+    pub pre_release: bool,
+    /// Whether to attach build metadata, e.g., `+build.42` or `+sha.1f2e3d4`.
+    pub build_metadata: bool,
+}
+
+/// Returns a random pre-release identifier with the given tag.
+fn pre_release(rng: &mut impl Rng, tag: &str) -> String {
+    format!("{tag}.{}", rng.random_range(1..=5))
+}
+
+/// Returns a random build metadata identifier.
+fn build_metadata(rng: &mut impl Rng) -> String {
+    if rng.random_bool(0.5) {
+        format!("build.{}", rng.random_range(1..=999))
+    } else {
+        format!("sha.{:07x}", rng.random_range(0..0x1000_0000u32))
+    }
+}
+
+/// Formats a version from its core components and optional identifiers.
+fn format(core: (u64, u64, u64), pre: Option<&str>, build: Option<&str>) -> String {
+    let (major, minor, patch) = core;
+    let mut version = format!("{major}.{minor}.{patch}");
+    if let Some(pre) = pre {
+        version.push('-');
+        version.push_str(pre);
+    }
+    if let Some(build) = build {
+        version.push('+');
+        version.push_str(build);
+    }
+    version
+}
+
+/// Generates a random semantic version without pre-release or build metadata.
+///
+/// # Returns
+/// - A `String` of the form `MAJOR.MINOR.PATCH`, e.g., `1.12.3`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_semver();
+/// assert_eq!(x.split('.').count(), 3);
+/// assert!(x.split('.').all(|c| c.parse::<u64>().is_ok()));
+/// ```
+pub fn generate_semver() -> String {
+    generate_semver_with(&SemverOpts::default())
+}
+
+/// Generates a random semantic version with the given options.
+///
+/// The core components are kept small, i.e., a major version below 10, a minor version below 20
+/// and a patch version below 50, so that generated versions look realistic.
+///
+/// # Parameters
+/// - `opts`: Whether to attach a pre-release identifier and build metadata.
+///
+/// # Returns
+/// - A `String` of the form `MAJOR.MINOR.PATCH[-PRE][+BUILD]` following Semantic Versioning 2.0.0.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::SemverOpts;
+///
+/// let opts = SemverOpts {
+///     pre_release: true,
+///     build_metadata: true,
+/// };
+/// let x = regd_testing::rand::generate_semver_with(&opts);
+/// let (version, build) = x.split_once('+').unwrap();
+/// let (core, pre) = version.split_once('-').unwrap();
+/// assert_eq!(core.split('.').count(), 3);
+/// assert!(!pre.is_empty() && !build.is_empty());
+/// ```
+pub fn generate_semver_with(opts: &SemverOpts) -> String {
+    let mut rng = super::rng();
+    let core = (
+        rng.random_range(0..10),
+        rng.random_range(0..20),
+        rng.random_range(0..50),
+    );
+    let pre = opts.pre_release.then(|| {
+        let tag = PRE_RELEASE_TAGS.choose(&mut rng).copied().unwrap_or("rc");
+        pre_release(&mut rng, tag)
+    });
+    let build = opts.build_metadata.then(|| build_metadata(&mut rng));
+    format(core, pre.as_deref(), build.as_deref())
+}
+
+/// Generates a strictly increasing sequence of random semantic versions.
+///
+/// Each release bumps the major, minor or patch version of the previous one, resetting the lower
+/// components as usual. When `opts.pre_release` is set, some releases are preceded by a few of
+/// their own pre-releases, e.g., `2.0.0-alpha.1`, `2.0.0-rc.1` and then `2.0.0`. When
+/// `opts.build_metadata` is set, build metadata is randomly attached; it does not take part in
+/// the ordering.
+///
+/// # Parameters
+/// - `count`: The number of versions to generate.
+/// - `opts`: Whether to emit pre-releases and attach build metadata.
+///
+/// # Returns
+/// - A `Vec<String>` of `count` versions, each of which has a strictly higher precedence than
+///   the previous one.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::SemverOpts;
+///
+/// let xs = regd_testing::rand::generate_semver_sequence(8, &SemverOpts::default());
+/// assert_eq!(xs.len(), 8);
+///
+/// let parse = |x: &str| -> Vec<u64> { x.split('.').map(|c| c.parse().unwrap()).collect() };
+/// assert!(xs.windows(2).all(|w| parse(&w[0]) < parse(&w[1])));
+/// ```
+pub fn generate_semver_sequence(count: usize, opts: &SemverOpts) -> Vec<String> {
+    let mut rng = super::rng();
+    let mut core: (u64, u64, u64) = (rng.random_range(0..3), rng.random_range(0..10), 0);
+    let mut versions = Vec::with_capacity(count);
+    while versions.len() < count {
+        core = match rng.random_range(0..10) {
+            0 => (core.0 + 1, 0, 0),
+            1..=3 => (core.0, core.1 + 1, 0),
+            _ => (core.0, core.1, core.2 + 1),
+        };
+        if opts.pre_release && rng.random_bool(0.5) {
+            for tag in PRE_RELEASE_TAGS {
+                if !rng.random_bool(0.5) {
+                    continue;
+                }
+                for n in 1..=rng.random_range(1..=2) {
+                    let build = (opts.build_metadata && rng.random_bool(0.5))
+                        .then(|| build_metadata(&mut rng));
+                    let pre = format!("{tag}.{n}");
+                    versions.push(format(core, Some(&pre), build.as_deref()));
+                }
+            }
+        }
+        let build = (opts.build_metadata && rng.random_bool(0.5)).then(|| build_metadata(&mut rng));
+        versions.push(format(core, None, build.as_deref()));
+    }
+    versions.truncate(count);
+    versions
+}
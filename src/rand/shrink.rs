@@ -0,0 +1,217 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of traits for simplifying random values.
+
+/// A trait for values which can be simplified into "smaller" candidates.
+///
+/// Shrinking is used by [`check`](super::check) to reduce a failing input to a minimal
+/// counterexample: numbers shrink toward zero, booleans toward `false`, and strings and vectors
+/// toward shorter ones made of simpler elements.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::Shrink;
+///
+/// assert_eq!(100u32.shrink(), vec![0, 50, 99]);
+/// assert!("abc".to_string().shrink().contains(&String::new()));
+/// ```
+pub trait Shrink: Sized {
+    /// Returns the candidates simpler than `self`, from the simplest one.
+    ///
+    /// The returned vector is empty if `self` cannot be simplified any further.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+/// Appends `candidate` to `candidates` unless it is equal to `value` or already present.
+fn push<T: PartialEq>(candidates: &mut Vec<T>, value: &T, candidate: T) {
+    if candidate != *value && !candidates.contains(&candidate) {
+        candidates.push(candidate);
+    }
+}
+
+macro_rules! impl_shrink_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Shrink for $t {
+                fn shrink(&self) -> Vec<Self> {
+                    let mut candidates = Vec::new();
+                    if *self != 0 {
+                        push(&mut candidates, self, 0);
+                        push(&mut candidates, self, self / 2);
+                        push(&mut candidates, self, self - 1);
+                    }
+                    candidates
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_shrink_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Shrink for $t {
+                fn shrink(&self) -> Vec<Self> {
+                    let mut candidates = Vec::new();
+                    if *self != 0 {
+                        push(&mut candidates, self, 0);
+                        if *self < 0 && *self != <$t>::MIN {
+                            push(&mut candidates, self, -self);
+                        }
+                        push(&mut candidates, self, self / 2);
+                        push(&mut candidates, self, self - self.signum());
+                    }
+                    candidates
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_shrink_float {
+    ($($t:ty),*) => {
+        $(
+            impl Shrink for $t {
+                fn shrink(&self) -> Vec<Self> {
+                    let mut candidates = Vec::new();
+                    if self.is_nan() || self.is_infinite() {
+                        candidates.push(0.0);
+                    } else if *self != 0.0 {
+                        push(&mut candidates, self, 0.0);
+                        if *self < 0.0 {
+                            push(&mut candidates, self, -self);
+                        }
+                        push(&mut candidates, self, self.trunc());
+                        push(&mut candidates, self, self / 2.0);
+                    }
+                    candidates
+                }
+            }
+        )*
+    };
+}
+
+impl_shrink_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_shrink_signed!(i8, i16, i32, i64, i128, isize);
+impl_shrink_float!(f32, f64);
+
+impl Shrink for () {
+    fn shrink(&self) -> Vec<Self> {
+        Vec::new()
+    }
+}
+
+impl Shrink for bool {
+    fn shrink(&self) -> Vec<Self> {
+        if *self { vec![false] } else { Vec::new() }
+    }
+}
+
+impl Shrink for char {
+    fn shrink(&self) -> Vec<Self> {
+        if *self == 'a' { Vec::new() } else { vec!['a'] }
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        if self.is_empty() {
+            return candidates;
+        }
+        candidates.push(Vec::new());
+        let half = self.len() / 2;
+        if half > 0 {
+            candidates.push(self[..half].to_vec());
+            candidates.push(self[half..].to_vec());
+        }
+        if self.len() > 1 {
+            for i in 0..self.len() {
+                let mut candidate = self.clone();
+                candidate.remove(i);
+                candidates.push(candidate);
+            }
+        }
+        for (i, element) in self.iter().enumerate() {
+            for simpler in element.shrink() {
+                let mut candidate = self.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+impl Shrink for String {
+    fn shrink(&self) -> Vec<Self> {
+        let chars: Vec<char> = self.chars().collect();
+        chars
+            .shrink()
+            .into_iter()
+            .map(|chars| chars.into_iter().collect())
+            .collect()
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Option<T> {
+    fn shrink(&self) -> Vec<Self> {
+        match self {
+            None => Vec::new(),
+            Some(value) => std::iter::once(None)
+                .chain(value.shrink().into_iter().map(Some))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Shrink + Clone, const N: usize> Shrink for [T; N] {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        for (i, element) in self.iter().enumerate() {
+            for simpler in element.shrink() {
+                let mut candidate = self.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+macro_rules! impl_shrink_tuple {
+    ($(($t:ident, $i:tt)),+) => {
+        impl<$($t: Shrink + Clone),+> Shrink for ($($t,)+) {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = Vec::new();
+                $(
+                    for simpler in self.$i.shrink() {
+                        let mut candidate = self.clone();
+                        candidate.$i = simpler;
+                        candidates.push(candidate);
+                    }
+                )+
+                candidates
+            }
+        }
+    };
+}
+
+impl_shrink_tuple!((A, 0));
+impl_shrink_tuple!((A, 0), (B, 1));
+impl_shrink_tuple!((A, 0), (B, 1), (C, 2));
+impl_shrink_tuple!((A, 0), (B, 1), (C, 2), (D, 3));
+impl_shrink_tuple!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4));
+impl_shrink_tuple!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5));
@@ -0,0 +1,206 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random natural-language text generators.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+/// The built-in corpus from which the default [`Markov`] chain is built.
+const CORPUS: &str = "\
+The registry keeps a record of every package that was ever published. Each record holds the \
+name of the package, the version of the release and the time at which it was uploaded. When a \
+client asks for a package, the registry looks up the record and returns the latest version \
+that matches the request. A small team maintains the service and reviews every change to the \
+code before it is merged. The team writes tests for each new feature and runs them on every \
+commit. Most of the tests are fast and run in a few seconds, but some of them start a real \
+server and take much longer. The slow tests are the ones that find the most interesting bugs. \
+Last year the team found a bug that only appeared when two clients uploaded the same package at \
+the same time. The bug was hard to reproduce because the timing had to be just right. After a \
+long week of debugging, the team wrote a test that started many clients at once and the bug \
+appeared within a minute. The fix was a single line, but the test is still part of the suite \
+today. Good tests describe the behavior of the system in plain words. They tell the reader what \
+the code is expected to do and what should happen when something goes wrong. A test that fails \
+should point to the problem as clearly as possible. When the data in a test looks like real \
+data, the failure is much easier to understand. A name, an address or a short description is \
+easier to read than a long string of random letters. The documentation of the project explains \
+how to publish a package, how to yank a broken release and how to recover a lost token. Every \
+page of the documentation was written by a member of the team and reviewed by another one. The \
+project is open to new contributors and the team is happy to help anyone who wants to start.";
+
+/// An order-one, word-level Markov chain which generates plausible natural-language sentences.
+///
+/// The chain records which words follow each word of a training corpus, as well as which words
+/// start a sentence, and then generates text by walking those transitions at random. The
+/// generated text is grammatically loose but reads much more naturally than random letters.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::Markov;
+///
+/// let markov = Markov::new("the cat sat on the mat. the dog sat on the cat.");
+/// let x = markov.generate_sentence(5);
+/// assert_eq!(x.split(' ').count(), 5);
+/// assert!(x.starts_with("The ") && x.ends_with('.'));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Markov {
+    starts: Vec<String>,
+    transitions: HashMap<String, Vec<String>>,
+}
+
+impl Default for Markov {
+    /// Creates a new Markov chain trained on the built-in English corpus.
+    fn default() -> Self {
+        Self::new(CORPUS)
+    }
+}
+
+impl Markov {
+    /// Creates a new Markov chain trained on the given corpus.
+    ///
+    /// The corpus is split into words on whitespace; words are lowercased and stripped of
+    /// surrounding punctuation, and a word ending with `.`, `!` or `?` terminates a sentence.
+    ///
+    /// # Panics
+    /// - This method will panic if `corpus` contains no words.
+    pub fn new(corpus: &str) -> Self {
+        let mut starts = Vec::new();
+        let mut transitions: HashMap<String, Vec<String>> = HashMap::new();
+        let mut previous: Option<String> = None;
+        for token in corpus.split_whitespace() {
+            let word = token
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase();
+            if word.is_empty() {
+                continue;
+            }
+            match previous {
+                Some(previous) => transitions.entry(previous).or_default().push(word.clone()),
+                None => starts.push(word.clone()),
+            }
+            let ends_sentence = token.ends_with(['.', '!', '?']);
+            previous = (!ends_sentence).then_some(word);
+        }
+        assert!(!starts.is_empty(), "cannot sample empty corpus");
+        Self {
+            starts,
+            transitions,
+        }
+    }
+
+    /// Generates a random sentence of the specified number of words.
+    ///
+    /// The first word is capitalized and the sentence ends with a period. Whenever the chain
+    /// reaches a word with no recorded successor, it starts over from a random sentence start.
+    ///
+    /// # Panics
+    /// - This method will panic if `words == 0`.
+    pub fn generate_sentence(&self, words: usize) -> String {
+        assert!(words > 0, "cannot sample empty sentence");
+        let mut rng = super::rng();
+        let mut sentence: Vec<&str> = Vec::with_capacity(words);
+        while sentence.len() < words {
+            let next = sentence
+                .last()
+                .and_then(|word| self.transitions.get(*word))
+                .and_then(|followers| followers.choose(&mut rng))
+                .or_else(|| self.starts.choose(&mut rng));
+            let Some(next) = next else {
+                break;
+            };
+            sentence.push(next);
+        }
+        let mut sentence = sentence.join(" ");
+        if let Some(first) = sentence.get(..1) {
+            let upper = first.to_uppercase();
+            sentence.replace_range(..1, &upper);
+        }
+        sentence.push('.');
+        sentence
+    }
+
+    /// Generates the specified number of random paragraphs separated by blank lines.
+    ///
+    /// Each paragraph consists of three to six sentences of six to fourteen words.
+    pub fn generate_paragraphs(&self, n: usize) -> String {
+        let mut rng = super::rng();
+        let paragraphs: Vec<String> = (0..n)
+            .map(|_| {
+                let sentences: Vec<String> = (0..rng.random_range(3..=6))
+                    .map(|_| self.generate_sentence(rng.random_range(6..=14)))
+                    .collect();
+                sentences.join(" ")
+            })
+            .collect();
+        paragraphs.join("\n\n")
+    }
+}
+
+/// Returns the Markov chain trained on the built-in corpus.
+fn markov() -> &'static Markov {
+    static MARKOV: OnceLock<Markov> = OnceLock::new();
+    MARKOV.get_or_init(Markov::default)
+}
+
+/// Generates a random English-looking sentence of the specified number of words.
+///
+/// The sentence is generated by a [`Markov`] chain trained on a small built-in corpus, so that
+/// description or body fields get plausible text, which keeps golden files and snapshots
+/// readable.
+///
+/// # Parameters
+/// - `words`: The number of words of the sentence. Must be greater than 0.
+///
+/// # Returns
+/// - A `String` of `words` space-separated words starting with a capital letter and ending with
+///   a period.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_sentence(8);
+/// assert_eq!(x.split(' ').count(), 8);
+/// assert!(x.ends_with('.'));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `words == 0`.
+pub fn generate_sentence(words: usize) -> String {
+    markov().generate_sentence(words)
+}
+
+/// Generates the specified number of random English-looking paragraphs.
+///
+/// # Parameters
+/// - `n`: The number of paragraphs.
+///
+/// # Returns
+/// - A `String` of `n` paragraphs separated by blank lines, each of which consists of three to
+///   six sentences.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_paragraphs(3);
+/// assert_eq!(x.split("\n\n").count(), 3);
+/// ```
+pub fn generate_paragraphs(n: usize) -> String {
+    markov().generate_paragraphs(n)
+}
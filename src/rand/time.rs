@@ -0,0 +1,152 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random `Duration` and `SystemTime` generators.
+
+use std::ops::{Bound, RangeBounds};
+use std::time::{Duration, SystemTime};
+
+use rand::distr::uniform::SampleRange;
+
+/// Generates a random `Duration` within the specified range.
+///
+/// # Parameters
+/// - `range`: The range from which to generate a random duration.
+///
+/// # Returns
+/// - A randomly generated `Duration` within the specified range.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_duration(Duration::from_millis(10)..Duration::from_secs(1));
+/// assert!(x >= Duration::from_millis(10) && x < Duration::from_secs(1));
+/// ```
+///
+/// # Panics
+/// - This function will panic if the provided range is empty.
+pub fn generate_duration<R>(range: R) -> Duration
+where
+    R: SampleRange<Duration>,
+{
+    super::generate_range(range)
+}
+
+/// Generates a random `SystemTime` within the specified range.
+///
+/// # Parameters
+/// - `range`: The range from which to generate a random point in time. Both ends must be bounded,
+///   e.g., `start..end` or `start..=end`.
+///
+/// # Returns
+/// - A randomly generated `SystemTime` within the specified range.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// use regd_testing;
+///
+/// let start = SystemTime::UNIX_EPOCH;
+/// let end = start + Duration::from_secs(365 * 24 * 60 * 60);
+/// let x = regd_testing::rand::generate_system_time(start..end);
+/// assert!(start <= x && x < end);
+/// ```
+///
+/// # Panics
+/// - This function will panic if the provided range is empty or unbounded.
+pub fn generate_system_time<R>(range: R) -> SystemTime
+where
+    R: RangeBounds<SystemTime>,
+{
+    let (start, inclusive) = match range.start_bound() {
+        Bound::Included(start) => (*start, true),
+        Bound::Excluded(start) => (*start, false),
+        Bound::Unbounded => panic!("cannot sample unbounded range"),
+    };
+    let (end, end_inclusive) = match range.end_bound() {
+        Bound::Included(end) => (*end, true),
+        Bound::Excluded(end) => (*end, false),
+        Bound::Unbounded => panic!("cannot sample unbounded range"),
+    };
+    let span = end
+        .duration_since(start)
+        .unwrap_or_else(|_| panic!("cannot sample empty range"));
+    let offset = match (inclusive, end_inclusive) {
+        (true, true) => generate_duration(Duration::ZERO..=span),
+        (true, false) => generate_duration(Duration::ZERO..span),
+        (false, true) => span - generate_duration(Duration::ZERO..span),
+        (false, false) => {
+            assert!(span > Duration::from_nanos(1), "cannot sample empty range");
+            generate_duration(Duration::from_nanos(1)..span)
+        }
+    };
+    start + offset
+}
+
+/// Generates a random `SystemTime` in the past, at most `within` before now.
+///
+/// This is useful for producing realistic timestamps, e.g., "within the last 7 days".
+///
+/// # Parameters
+/// - `within`: The maximum distance from now into the past.
+///
+/// # Returns
+/// - A randomly generated `SystemTime` in the range `[now - within, now]`.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_past_system_time(Duration::from_secs(7 * 24 * 60 * 60));
+/// assert!(x <= SystemTime::now());
+/// ```
+pub fn generate_past_system_time(within: Duration) -> SystemTime {
+    let now = SystemTime::now();
+    now - generate_duration(Duration::ZERO..=within)
+}
+
+/// Generates a random `SystemTime` in the future, at most `within` after now.
+///
+/// This is useful for producing expiration timestamps, e.g., "expires within the next hour".
+///
+/// # Parameters
+/// - `within`: The maximum distance from now into the future. Must be greater than zero.
+///
+/// # Returns
+/// - A randomly generated `SystemTime` in the range `(now, now + within]`.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// use regd_testing;
+///
+/// let now = SystemTime::now();
+/// let x = regd_testing::rand::generate_future_system_time(Duration::from_secs(60 * 60));
+/// assert!(x > now);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `within` is zero.
+pub fn generate_future_system_time(within: Duration) -> SystemTime {
+    assert!(!within.is_zero(), "cannot sample empty range");
+    let now = SystemTime::now();
+    now + generate_duration(Duration::from_nanos(1)..=within)
+}
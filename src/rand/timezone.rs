@@ -0,0 +1,325 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of timestamps at time zone and calendar edges.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Offset, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+/// The time zones observing daylight saving time, including unusual shifts, e.g., of 30 minutes
+/// in `Australia/Lord_Howe` or with a negative saving in `Europe/Dublin`.
+const DST_ZONES: &[Tz] = &[
+    Tz::America__New_York,
+    Tz::America__Los_Angeles,
+    Tz::America__Santiago,
+    Tz::America__St_Johns,
+    Tz::Australia__Sydney,
+    Tz::Australia__Lord_Howe,
+    Tz::Europe__Berlin,
+    Tz::Europe__Dublin,
+    Tz::Europe__London,
+    Tz::Pacific__Auckland,
+    Tz::Pacific__Chatham,
+];
+
+/// The time zones with extreme or fractional offsets, used for year boundaries.
+const YEAR_ZONES: &[Tz] = &[
+    Tz::UTC,
+    Tz::Asia__Kathmandu,
+    Tz::Asia__Kolkata,
+    Tz::America__St_Johns,
+    Tz::Pacific__Kiritimati,
+    Tz::Pacific__Pago_Pago,
+];
+
+/// The days at the end of which a leap second was inserted, as `(year, month, day)`.
+const LEAP_SECOND_DAYS: &[(i32, u32, u32)] = &[
+    (1972, 6, 30),
+    (1972, 12, 31),
+    (1973, 12, 31),
+    (1974, 12, 31),
+    (1975, 12, 31),
+    (1976, 12, 31),
+    (1977, 12, 31),
+    (1978, 12, 31),
+    (1979, 12, 31),
+    (1981, 6, 30),
+    (1982, 6, 30),
+    (1983, 6, 30),
+    (1985, 6, 30),
+    (1987, 12, 31),
+    (1989, 12, 31),
+    (1990, 12, 31),
+    (1992, 6, 30),
+    (1993, 6, 30),
+    (1994, 6, 30),
+    (1995, 12, 31),
+    (1997, 6, 30),
+    (1998, 12, 31),
+    (2005, 12, 31),
+    (2008, 12, 31),
+    (2012, 6, 30),
+    (2015, 6, 30),
+    (2016, 12, 31),
+];
+
+/// The Unix timestamps at which 32-bit timestamps overflow or underflow.
+const ROLLOVER_TIMESTAMPS: &[i64] = &[
+    i32::MIN as i64,
+    i32::MAX as i64,
+    i32::MAX as i64 + 1,
+    u32::MAX as i64,
+    u32::MAX as i64 + 1,
+];
+
+/// The kinds of edges generated by [`generate_datetime_edge`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DateTimeEdgeKind {
+    /// Around a transition into daylight saving time, where local times are skipped.
+    DstStart,
+    /// Around a transition out of daylight saving time, where local times are repeated.
+    DstEnd,
+    /// Around a leap second inserted into UTC, including the leap second itself.
+    LeapSecond,
+    /// Around the local midnight between two years.
+    YearBoundary,
+    /// At the overflow of signed or unsigned 32-bit Unix timestamps, e.g., in 2038.
+    EpochRollover,
+}
+
+impl DateTimeEdgeKind {
+    /// Every kind of edge.
+    const ALL: [Self; 5] = [
+        Self::DstStart,
+        Self::DstEnd,
+        Self::LeapSecond,
+        Self::YearBoundary,
+        Self::EpochRollover,
+    ];
+}
+
+/// A timestamp generated by [`generate_datetime_edge`], with its time zone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateTimeEdge {
+    /// The timestamp, in its time zone.
+    pub datetime: DateTime<Tz>,
+    /// The kind of the edge the timestamp is at.
+    pub kind: DateTimeEdgeKind,
+}
+
+impl DateTimeEdge {
+    /// Returns the IANA name of the time zone, e.g., `Europe/Berlin`.
+    pub fn timezone(&self) -> &'static str {
+        self.datetime.timezone().name()
+    }
+
+    /// Returns the timestamp in UTC.
+    pub fn utc(&self) -> DateTime<Utc> {
+        self.datetime.with_timezone(&Utc)
+    }
+}
+
+/// Returns the offset from UTC of `tz` at the given instant, in seconds.
+fn offset_at(tz: Tz, instant: NaiveDateTime) -> i32 {
+    tz.offset_from_utc_datetime(&instant)
+        .fix()
+        .local_minus_utc()
+}
+
+/// Returns the instant, in UTC, of the given Unix timestamp.
+fn timestamp(secs: i64) -> NaiveDateTime {
+    DateTime::from_timestamp(secs, 0)
+        .unwrap_or_else(|| panic!("cannot represent timestamp {secs}"))
+        .naive_utc()
+}
+
+/// Returns the first instant, in UTC, of each transition of `tz` during `year`, paired with
+/// whether the offset increases.
+fn transitions(tz: Tz, year: i32) -> Vec<(NaiveDateTime, bool)> {
+    let Some(start) = NaiveDate::from_ymd_opt(year, 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0))
+    else {
+        return Vec::new();
+    };
+    let day = TimeDelta::days(1);
+    let mut found = Vec::new();
+    let mut before = start;
+    while before.date() < start.date() + TimeDelta::days(366) {
+        let after = before + day;
+        let (from, to) = (offset_at(tz, before), offset_at(tz, after));
+        if from != to {
+            // The offset at `low` is always `from`, and the offset at `high` is always `to`.
+            let (mut low, mut high) = (before.and_utc().timestamp(), after.and_utc().timestamp());
+            while high - low > 1 {
+                let middle = low + (high - low) / 2;
+                if offset_at(tz, timestamp(middle)) == from {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+            found.push((timestamp(high), to > from));
+        }
+        before = after;
+    }
+    found
+}
+
+/// Generates a random timestamp around a daylight saving time transition in the given
+/// direction.
+fn dst_edge<R: Rng + ?Sized>(rng: &mut R, forward: bool) -> DateTime<Tz> {
+    loop {
+        let tz = *DST_ZONES.choose(rng).unwrap_or(&Tz::Europe__Berlin);
+        let year = rng.random_range(2000..=2037);
+        let candidates: Vec<NaiveDateTime> = transitions(tz, year)
+            .into_iter()
+            .filter(|(_, increases)| *increases == forward)
+            .map(|(instant, _)| instant)
+            .collect();
+        if let Some(instant) = candidates.choose(rng) {
+            let delta = *[-3600, -1800, -1, 0, 1, 1800, 3600]
+                .choose(rng)
+                .unwrap_or(&0);
+            return tz.from_utc_datetime(&(*instant + TimeDelta::seconds(delta)));
+        }
+    }
+}
+
+/// Generates a random timestamp of the given kind of edge.
+fn generate_edge<R: Rng + ?Sized>(rng: &mut R, kind: DateTimeEdgeKind) -> DateTime<Tz> {
+    match kind {
+        DateTimeEdgeKind::DstStart => dst_edge(rng, true),
+        DateTimeEdgeKind::DstEnd => dst_edge(rng, false),
+        DateTimeEdgeKind::LeapSecond => {
+            let (year, month, day) = *LEAP_SECOND_DAYS.choose(rng).unwrap_or(&(2016, 12, 31));
+            let date = NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default();
+            let instant = match rng.random_range(0..3) {
+                0 => date.and_hms_nano_opt(23, 59, 59, rng.random_range(0..1_000_000_000)),
+                // The leap second itself is represented by nanoseconds beyond one second.
+                1 => date.and_hms_nano_opt(
+                    23,
+                    59,
+                    59,
+                    rng.random_range(1_000_000_000..2_000_000_000),
+                ),
+                _ => date.succ_opt().and_then(|d| d.and_hms_opt(0, 0, 0)),
+            };
+            Tz::UTC.from_utc_datetime(&instant.unwrap_or_default())
+        }
+        DateTimeEdgeKind::YearBoundary => loop {
+            let tz = *YEAR_ZONES.choose(rng).unwrap_or(&Tz::UTC);
+            let year = rng.random_range(1970..=2100);
+            let local = if rng.random_bool(0.5) {
+                NaiveDate::from_ymd_opt(year, 12, 31).and_then(|d| {
+                    d.and_hms_nano_opt(23, 59, 59, *[0, 999_999_999].choose(rng).unwrap_or(&0))
+                })
+            } else {
+                NaiveDate::from_ymd_opt(year + 1, 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0))
+            };
+            // A zone may skip the boundary, e.g., `Pacific/Kiritimati` skipped 1994-12-31, so
+            // that another zone and year are drawn.
+            if let Some(datetime) = tz
+                .from_local_datetime(&local.unwrap_or_default())
+                .earliest()
+            {
+                return datetime;
+            }
+        },
+        DateTimeEdgeKind::EpochRollover => {
+            let timestamp = *ROLLOVER_TIMESTAMPS
+                .choose(rng)
+                .unwrap_or(&(i32::MAX as i64));
+            let timestamp = timestamp + rng.random_range(-1..=1);
+            Tz::UTC
+                .timestamp_opt(timestamp, 0)
+                .single()
+                .unwrap_or_else(|| panic!("cannot represent timestamp {timestamp}"))
+        }
+    }
+}
+
+/// Generates a random timestamp at a time zone or calendar edge.
+///
+/// Calendar bugs cluster at a few instants which uniform timestamps almost never hit. This
+/// function picks one kind of [`DateTimeEdgeKind`] uniformly, then a timestamp at or around it:
+/// - Within an hour of a daylight saving time transition between 2000 and 2037, in a zone such
+///   as `America/New_York`, `Australia/Lord_Howe`, or `Pacific/Chatham`.
+/// - On the second before, during, or after one of the 27 leap seconds inserted into UTC.
+/// - At the last or the first instant of a year, in a zone with an extreme or fractional offset.
+/// - Within a second of the overflow of 32-bit Unix timestamps, e.g., `2038-01-19T03:14:07Z`.
+///
+/// # Returns
+/// - A random [`DateTimeEdge`].
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "chrono-tz")]
+/// # {
+/// use chrono::{Datelike, Timelike};
+/// use regd_testing::rand::DateTimeEdgeKind;
+///
+/// for _ in 0..100 {
+///     let x = regd_testing::rand::generate_datetime_edge();
+///     assert_eq!(x.utc(), x.datetime);
+///     match x.kind {
+///         DateTimeEdgeKind::YearBoundary => {
+///             let day = (x.datetime.month(), x.datetime.day());
+///             assert!(day == (12, 31) || day == (1, 1));
+///         }
+///         DateTimeEdgeKind::LeapSecond => assert!([23, 0].contains(&x.utc().hour())),
+///         _ => {}
+///     }
+/// }
+/// # }
+/// ```
+pub fn generate_datetime_edge() -> DateTimeEdge {
+    let mut rng = super::rng();
+    let kind = *DateTimeEdgeKind::ALL
+        .choose(&mut rng)
+        .unwrap_or(&DateTimeEdgeKind::DstStart);
+    generate_datetime_edge_of(kind)
+}
+
+/// Generates a random timestamp at the given kind of time zone or calendar edge.
+///
+/// # Parameters
+/// - `kind`: The kind of the edge.
+///
+/// # Returns
+/// - A random [`DateTimeEdge`] of the given kind.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "chrono-tz")]
+/// # {
+/// use chrono::{Offset, TimeDelta};
+/// use regd_testing::rand::DateTimeEdgeKind;
+///
+/// let x = regd_testing::rand::generate_datetime_edge_of(DateTimeEdgeKind::DstStart);
+/// let before = (x.datetime - TimeDelta::hours(2)).offset().fix();
+/// let after = (x.datetime + TimeDelta::hours(2)).offset().fix();
+/// assert!(before.local_minus_utc() < after.local_minus_utc());
+///
+/// let y = regd_testing::rand::generate_datetime_edge_of(DateTimeEdgeKind::EpochRollover);
+/// assert_eq!(y.timezone(), "UTC");
+/// # }
+/// ```
+pub fn generate_datetime_edge_of(kind: DateTimeEdgeKind) -> DateTimeEdge {
+    DateTimeEdge {
+        datetime: generate_edge(&mut super::rng(), kind),
+        kind,
+    }
+}
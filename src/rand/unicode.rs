@@ -0,0 +1,249 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random Unicode string generators.
+
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use rand::seq::IndexedRandom;
+
+/// A profile describing which kinds of Unicode edge cases are mixed into generated strings.
+///
+/// Every kind is enabled by default; disable the ones the code under test is not expected to
+/// handle by overriding the corresponding fields.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::UnicodeProfile;
+///
+/// let profile = UnicodeProfile {
+///     rtl: false,
+///     long_graphemes: false,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_unicode_with(32, &profile);
+/// assert_eq!(x.chars().count(), 32);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnicodeProfile {
+    /// Plain ASCII alphanumeric characters.
+    pub ascii: bool,
+    /// Emoji, including skin tone modifiers, ZWJ sequences and regional indicator flags.
+    pub emoji: bool,
+    /// Base letters followed by a few combining marks.
+    pub combining_marks: bool,
+    /// Hebrew and Arabic letters together with bidirectional control characters.
+    pub rtl: bool,
+    /// Code points adjacent to the surrogate range and to other plane boundaries, e.g., `U+D7FF`,
+    /// `U+E000`, `U+FFFF`, `U+10000` and `U+10FFFF`.
+    pub surrogate_adjacent: bool,
+    /// Zero-width characters, e.g., `U+200B`, `U+200D`, `U+2060` and `U+FEFF`.
+    pub zero_width: bool,
+    /// Very long grapheme clusters built from a base letter and dozens of combining marks.
+    pub long_graphemes: bool,
+}
+
+impl Default for UnicodeProfile {
+    fn default() -> Self {
+        Self {
+            ascii: true,
+            emoji: true,
+            combining_marks: true,
+            rtl: true,
+            surrogate_adjacent: true,
+            zero_width: true,
+            long_graphemes: true,
+        }
+    }
+}
+
+const EMOJI: &[RangeInclusive<u32>] = &[0x1F300..=0x1F5FF, 0x1F600..=0x1F64F, 0x1F680..=0x1F6FF];
+const SKIN_TONES: RangeInclusive<u32> = 0x1F3FB..=0x1F3FF;
+const REGIONAL_INDICATORS: RangeInclusive<u32> = 0x1F1E6..=0x1F1FF;
+const COMBINING_MARKS: RangeInclusive<u32> = 0x0300..=0x036F;
+const RTL_LETTERS: &[RangeInclusive<u32>] = &[0x05D0..=0x05EA, 0x0627..=0x064A];
+const BIDI_CONTROLS: &[char] = &[
+    '\u{200E}', '\u{200F}', '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}', '\u{2066}',
+    '\u{2067}', '\u{2068}', '\u{2069}',
+];
+const SURROGATE_ADJACENT: &[char] = &[
+    '\u{D7FF}',
+    '\u{E000}',
+    '\u{FFFD}',
+    '\u{FFFE}',
+    '\u{FFFF}',
+    '\u{10000}',
+    '\u{10FFFF}',
+];
+const ZERO_WIDTH: &[char] = &['\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}'];
+const ZWJ: char = '\u{200D}';
+
+/// Returns a random character within the given range of code points.
+fn random_char(rng: &mut impl Rng, range: RangeInclusive<u32>) -> char {
+    char::from_u32(rng.random_range(range)).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// Returns a random ASCII letter used as the base of combining sequences.
+fn random_base(rng: &mut impl Rng) -> char {
+    char::from(rng.sample(Alphanumeric))
+}
+
+/// Returns a random character from one of the given ranges of code points.
+fn random_char_in(rng: &mut impl Rng, ranges: &[RangeInclusive<u32>]) -> char {
+    let range = ranges.choose(rng).cloned().unwrap_or(0x0041..=0x005A);
+    random_char(rng, range)
+}
+
+/// Returns a random emoji fragment, possibly made of several code points.
+fn emoji(rng: &mut impl Rng) -> Vec<char> {
+    match rng.random_range(0..4) {
+        0 => vec![random_char_in(rng, EMOJI)],
+        1 => vec![random_char_in(rng, EMOJI), random_char(rng, SKIN_TONES)],
+        2 => vec![
+            random_char_in(rng, EMOJI),
+            ZWJ,
+            random_char_in(rng, EMOJI),
+            ZWJ,
+            random_char_in(rng, EMOJI),
+        ],
+        _ => vec![
+            random_char(rng, REGIONAL_INDICATORS),
+            random_char(rng, REGIONAL_INDICATORS),
+        ],
+    }
+}
+
+/// Returns a base letter followed by the given number of random combining marks.
+fn combining(rng: &mut impl Rng, marks: usize) -> Vec<char> {
+    let mut chars = vec![random_base(rng)];
+    chars.extend((0..marks).map(|_| random_char(rng, COMBINING_MARKS)));
+    chars
+}
+
+/// Returns a random right-to-left fragment, optionally wrapped in bidirectional controls.
+fn rtl(rng: &mut impl Rng) -> Vec<char> {
+    let letters = rng.random_range(1..=4);
+    let mut chars: Vec<char> = (0..letters)
+        .map(|_| random_char_in(rng, RTL_LETTERS))
+        .collect();
+    if rng.random_bool(0.5) {
+        if let Some(&control) = BIDI_CONTROLS.choose(rng) {
+            chars.insert(0, control);
+        }
+    }
+    chars
+}
+
+/// Returns a single random character taken from the given list.
+fn one_of(rng: &mut impl Rng, chars: &[char]) -> Vec<char> {
+    chars.choose(rng).copied().into_iter().collect()
+}
+
+/// Generates a random string of the specified length mixing every kind of Unicode edge case.
+///
+/// This function is a shorthand of [`generate_unicode_with`] called with the default
+/// [`UnicodeProfile`], which enables emoji, combining marks, right-to-left text, code points
+/// adjacent to the surrogate range, zero-width characters and very long grapheme clusters.
+///
+/// # Parameters
+/// - `length`: The number of characters (Unicode scalar values) of the generated string.
+///
+/// # Returns
+/// - A `String` containing exactly `length` characters.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_unicode(64);
+/// assert_eq!(x.chars().count(), 64);
+/// ```
+pub fn generate_unicode(length: usize) -> String {
+    generate_unicode_with(length, &UnicodeProfile::default())
+}
+
+/// Generates a random string of the specified length mixing the Unicode edge cases of a profile.
+///
+/// This function repeatedly picks one of the kinds enabled in `profile` and appends a fragment
+/// of that kind, e.g., an emoji ZWJ sequence or a letter followed by combining marks, until the
+/// string reaches the requested length. The last fragment is truncated if necessary, so that
+/// multi-code-point sequences may be cut in the middle, which is an edge case on its own.
+///
+/// # Parameters
+/// - `length`: The number of characters (Unicode scalar values) of the generated string.
+/// - `profile`: The kinds of Unicode edge cases to mix into the string.
+///
+/// # Returns
+/// - A `String` containing exactly `length` characters.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::UnicodeProfile;
+///
+/// let profile = UnicodeProfile {
+///     ascii: false,
+///     emoji: false,
+///     combining_marks: false,
+///     rtl: false,
+///     surrogate_adjacent: false,
+///     zero_width: true,
+///     long_graphemes: false,
+/// };
+/// let x = regd_testing::rand::generate_unicode_with(8, &profile);
+/// assert!(x.chars().all(|c| ('\u{200B}'..='\u{FEFF}').contains(&c)));
+/// ```
+///
+/// # Panics
+/// - This function will panic if every kind of `profile` is disabled and `length > 0`.
+pub fn generate_unicode_with(length: usize, profile: &UnicodeProfile) -> String {
+    type Fragment = fn(&mut super::TestRng) -> Vec<char>;
+    let kinds: Vec<Fragment> = [
+        (profile.ascii, (|rng| vec![random_base(rng)]) as Fragment),
+        (profile.emoji, |rng| emoji(rng)),
+        (profile.combining_marks, |rng| {
+            let marks = rng.random_range(1..=3);
+            combining(rng, marks)
+        }),
+        (profile.rtl, |rng| rtl(rng)),
+        (profile.surrogate_adjacent, |rng| {
+            one_of(rng, SURROGATE_ADJACENT)
+        }),
+        (profile.zero_width, |rng| one_of(rng, ZERO_WIDTH)),
+        (profile.long_graphemes, |rng| {
+            let marks = rng.random_range(16..=64);
+            combining(rng, marks)
+        }),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, kind)| enabled.then_some(kind))
+    .collect();
+    assert!(
+        length == 0 || !kinds.is_empty(),
+        "cannot sample empty unicode profile"
+    );
+
+    let mut rng = super::rng();
+    let mut chars = Vec::with_capacity(length);
+    while chars.len() < length {
+        let Some(kind) = kinds.choose(&mut rng) else {
+            break;
+        };
+        chars.extend(kind(&mut rng));
+    }
+    chars.truncate(length);
+    chars.into_iter().collect()
+}
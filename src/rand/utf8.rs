@@ -0,0 +1,129 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of malformed UTF-8 byte sequence generators.
+
+use std::str;
+
+use rand::Rng;
+use rand::distr::Alphanumeric;
+
+/// Returns a random UTF-8 continuation byte.
+fn continuation(rng: &mut impl Rng) -> u8 {
+    rng.random_range(0x80..=0xBF)
+}
+
+/// Returns a random byte sequence which is never valid UTF-8 on its own.
+///
+/// Every returned fragment stays invalid when it is followed by a non-continuation byte or by
+/// the end of input, and any of its prefixes is invalid when placed at the end of input.
+fn invalid_fragment(rng: &mut impl Rng) -> Vec<u8> {
+    match rng.random_range(0..6) {
+        // Lone continuation byte.
+        0 => vec![continuation(rng)],
+        // Overlong two-byte encoding of an ASCII character.
+        1 => vec![rng.random_range(0xC0..=0xC1), continuation(rng)],
+        // Overlong three-byte encoding.
+        2 => vec![0xE0, rng.random_range(0x80..=0x9F), continuation(rng)],
+        // Encoded UTF-16 surrogate.
+        3 => vec![0xED, rng.random_range(0xA0..=0xBF), continuation(rng)],
+        // Bytes which never appear in UTF-8.
+        4 => vec![rng.random_range(0xF5..=0xFF)],
+        // Multi-byte sequence truncated before its last continuation byte.
+        _ => match rng.random_range(2..=4) {
+            2 => vec![rng.random_range(0xC2..=0xDF)],
+            3 => vec![0xE1, continuation(rng)],
+            _ => vec![0xF1, continuation(rng), continuation(rng)],
+        },
+    }
+}
+
+/// Generates a random byte vector of the specified length which is guaranteed not to be valid UTF-8.
+///
+/// This function interleaves ASCII characters with malformed sequences, such as overlong
+/// encodings, lone continuation bytes, encoded surrogates, bytes that never appear in UTF-8 and
+/// truncated multi-byte sequences.
+///
+/// # Parameters
+/// - `length`: The number of bytes to generate. Must be greater than 0.
+///
+/// # Returns
+/// - A `Vec<u8>` of `length` bytes for which `std::str::from_utf8` fails.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_invalid_utf8(16);
+/// assert_eq!(x.len(), 16);
+/// assert!(std::str::from_utf8(&x).is_err());
+/// ```
+///
+/// # Panics
+/// - This function will panic if `length == 0`.
+pub fn generate_invalid_utf8(length: usize) -> Vec<u8> {
+    assert!(length > 0, "cannot sample empty invalid utf-8");
+    let mut rng = super::rng();
+    loop {
+        let mut bytes = Vec::with_capacity(length + 3);
+        while bytes.len() < length {
+            if rng.random_bool(0.5) {
+                bytes.push(rng.sample(Alphanumeric));
+            } else {
+                bytes.extend(invalid_fragment(&mut rng));
+                bytes.push(rng.sample(Alphanumeric));
+            }
+        }
+        bytes.truncate(length);
+        if str::from_utf8(&bytes).is_err() {
+            return bytes;
+        }
+    }
+}
+
+/// Embeds a malformed UTF-8 sequence at a random offset inside otherwise-valid text.
+///
+/// This function picks a random character boundary of `text`, including its start and end,
+/// and inserts one malformed sequence there, leaving the rest of the text intact. This is
+/// useful for testing lossy decoding, which is expected to recover the surrounding text.
+///
+/// # Parameters
+/// - `text`: The valid text into which the malformed sequence is embedded.
+///
+/// # Returns
+/// - A `Vec<u8>` containing the bytes of `text` with a malformed sequence inserted, for which
+///   `std::str::from_utf8` fails.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_invalid_utf8_within("hello, world");
+/// assert!(std::str::from_utf8(&x).is_err());
+/// assert!(String::from_utf8_lossy(&x).contains('\u{FFFD}'));
+/// ```
+pub fn generate_invalid_utf8_within(text: &str) -> Vec<u8> {
+    let mut rng = super::rng();
+    let boundaries = text.char_indices().count() + 1;
+    let offset = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .nth(rng.random_range(0..boundaries))
+        .unwrap_or(text.len());
+    let mut bytes = Vec::with_capacity(text.len() + 3);
+    bytes.extend_from_slice(&text.as_bytes()[..offset]);
+    bytes.extend(invalid_fragment(&mut rng));
+    bytes.extend_from_slice(&text.as_bytes()[offset..]);
+    bytes
+}
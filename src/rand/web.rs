@@ -0,0 +1,279 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of random email address and URL generators.
+
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use super::{Charset, generate_string};
+
+/// The top-level domains used when none is specified.
+const TLDS: &[&str] = &[
+    "com", "net", "org", "io", "dev", "test", "example", "jp", "de",
+];
+
+/// The schemes used when none is specified.
+const SCHEMES: &[&str] = &["http", "https"];
+
+/// Lowercase letters and digits.
+const ALNUM: [char; 36] = [
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+];
+
+/// Options of [`generate_email_with`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::EmailOpts;
+///
+/// let opts = EmailOpts {
+///     tld: Some("test".to_string()),
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_email_with(&opts);
+/// assert!(x.ends_with(".test"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EmailOpts {
+    /// The top-level domain of the address. A common one is chosen at random if `None`.
+    pub tld: Option<String>,
+    /// Whether to deliberately violate the address grammar.
+    pub invalid: bool,
+}
+
+/// Options of [`generate_url_with`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::UrlOpts;
+///
+/// let opts = UrlOpts {
+///     scheme: Some("ftp".to_string()),
+///     path_depth: 2..=2,
+///     query_params: 0..=0,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_url_with(&opts);
+/// assert!(x.starts_with("ftp://"));
+/// assert!(!x.contains('?'));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UrlOpts {
+    /// The scheme of the URL. Either `http` or `https` is chosen at random if `None`.
+    pub scheme: Option<String>,
+    /// The top-level domain of the host. A common one is chosen at random if `None`.
+    pub tld: Option<String>,
+    /// The range of the number of path segments.
+    pub path_depth: RangeInclusive<usize>,
+    /// The range of the number of query parameters.
+    pub query_params: RangeInclusive<usize>,
+    /// Whether to deliberately violate the URL grammar.
+    pub invalid: bool,
+}
+
+impl Default for UrlOpts {
+    fn default() -> Self {
+        Self {
+            scheme: None,
+            tld: None,
+            path_depth: 0..=3,
+            query_params: 0..=2,
+            invalid: false,
+        }
+    }
+}
+
+/// Returns a random lowercase alphanumeric label starting with a letter.
+fn label(rng: &mut impl Rng) -> String {
+    let length = rng.random_range(2..=9);
+    let mut label = generate_string(1, &Charset::Lowercase);
+    label.push_str(&generate_string(length, &Charset::Custom(&ALNUM)));
+    label
+}
+
+/// Returns a random domain name ending with the given or a random top-level domain.
+fn domain(rng: &mut impl Rng, tld: Option<&str>) -> String {
+    let tld = tld.unwrap_or_else(|| TLDS.choose(rng).copied().unwrap_or("test"));
+    let labels = rng.random_range(1..=2);
+    let mut domain: Vec<String> = (0..labels).map(|_| label(rng)).collect();
+    domain.push(tld.to_string());
+    domain.join(".")
+}
+
+/// Generates a random, syntactically valid email address.
+///
+/// # Returns
+/// - A `String` of the form `local@domain.tld`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_email();
+/// assert_eq!(x.matches('@').count(), 1);
+/// ```
+pub fn generate_email() -> String {
+    generate_email_with(&EmailOpts::default())
+}
+
+/// Generates a random email address with the given options.
+///
+/// If `opts.invalid` is `true`, the address is a near-valid one violating the grammar in exactly
+/// one way, e.g., a missing or doubled `@`, an empty local part or domain, consecutive or
+/// leading dots, an embedded space, or a domain label starting with a hyphen.
+///
+/// # Parameters
+/// - `opts`: The options of the generated address.
+///
+/// # Returns
+/// - A `String` representing an email address.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::EmailOpts;
+///
+/// let opts = EmailOpts {
+///     invalid: true,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_email_with(&opts);
+/// println!("Generated invalid email: {}", x);
+/// ```
+pub fn generate_email_with(opts: &EmailOpts) -> String {
+    let mut rng = super::rng();
+    let mut local = label(&mut rng);
+    match rng.random_range(0..3) {
+        0 => {}
+        1 => local = format!("{}.{}", local, label(&mut rng)),
+        _ => local = format!("{}+{}", local, label(&mut rng)),
+    }
+    let domain = domain(&mut rng, opts.tld.as_deref());
+    if !opts.invalid {
+        return format!("{local}@{domain}");
+    }
+    match rng.random_range(0..7) {
+        0 => format!("{local}{domain}"),
+        1 => format!("{local}@@{domain}"),
+        2 => format!("@{domain}"),
+        3 => format!("{local}@"),
+        4 => format!("{local}..{}@{domain}", label(&mut rng)),
+        5 => format!(".{local}@{domain}"),
+        _ => {
+            if rng.random_bool(0.5) {
+                format!("{local} {}@{domain}", label(&mut rng))
+            } else {
+                format!("{local}@-{domain}")
+            }
+        }
+    }
+}
+
+/// Generates a random, syntactically valid URL.
+///
+/// # Returns
+/// - A `String` of the form `scheme://host.tld[:port][/path...][?key=value&...]`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_url();
+/// assert!(x.starts_with("http://") || x.starts_with("https://"));
+/// ```
+pub fn generate_url() -> String {
+    generate_url_with(&UrlOpts::default())
+}
+
+/// Generates a random URL with the given options.
+///
+/// If `opts.invalid` is `true`, the URL is a near-valid one violating the grammar in exactly one
+/// way, e.g., a missing scheme separator, an empty host, an out-of-range port, an illegal
+/// character in the scheme, or an unencoded space.
+///
+/// # Parameters
+/// - `opts`: The options of the generated URL.
+///
+/// # Returns
+/// - A `String` representing a URL.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::UrlOpts;
+///
+/// let opts = UrlOpts {
+///     invalid: true,
+///     ..Default::default()
+/// };
+/// let x = regd_testing::rand::generate_url_with(&opts);
+/// println!("Generated invalid URL: {}", x);
+/// ```
+///
+/// # Panics
+/// - This function will panic if `opts.path_depth` or `opts.query_params` is empty.
+pub fn generate_url_with(opts: &UrlOpts) -> String {
+    assert!(!opts.path_depth.is_empty(), "cannot sample empty range");
+    assert!(!opts.query_params.is_empty(), "cannot sample empty range");
+    let mut rng = super::rng();
+    let scheme = opts.scheme.clone().unwrap_or_else(|| {
+        SCHEMES
+            .choose(&mut rng)
+            .copied()
+            .unwrap_or("http")
+            .to_string()
+    });
+    let host = domain(&mut rng, opts.tld.as_deref());
+    let port = if rng.random_bool(0.25) {
+        format!(":{}", rng.random_range(1..=u16::MAX))
+    } else {
+        String::new()
+    };
+    let depth = rng.random_range(opts.path_depth.clone());
+    let path: String = (0..depth)
+        .map(|_| format!("/{}", label(&mut rng)))
+        .collect();
+    let params = rng.random_range(opts.query_params.clone());
+    let query = (0..params)
+        .map(|_| format!("{}={}", label(&mut rng), label(&mut rng)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let query = if query.is_empty() {
+        query
+    } else {
+        format!("?{query}")
+    };
+    if !opts.invalid {
+        return format!("{scheme}://{host}{port}{path}{query}");
+    }
+    match rng.random_range(0..6) {
+        0 => format!("{scheme}:/{host}{port}{path}{query}"),
+        1 => format!("{scheme}//{host}{port}{path}{query}"),
+        2 => format!("{scheme}://{port}{path}{query}"),
+        3 => format!(
+            "{scheme}://{host}:{}{path}{query}",
+            rng.random_range(65536..=999_999)
+        ),
+        4 => format!("{scheme}_{}://{host}{port}{path}{query}", label(&mut rng)),
+        _ => format!(
+            "{scheme}://{host}{port}{path}/{} {}{query}",
+            label(&mut rng),
+            label(&mut rng)
+        ),
+    }
+}
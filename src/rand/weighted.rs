@@ -0,0 +1,251 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of weighted random choices.
+
+use rand::distr::weighted::WeightedIndex;
+use rand::prelude::Distribution;
+
+/// Randomly selects an element from the given `(item, weight)` pairs.
+///
+/// This function picks one of the items with a probability proportional to its weight,
+/// e.g., an item weighted `0.9` is chosen nine times as often as an item weighted `0.1`.
+///
+/// # Parameters
+/// - `items`: The `(item, weight)` pairs to choose from.
+///
+/// # Returns
+/// - A reference to the randomly selected item.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let mix = [("read", 0.9), ("write", 0.1)];
+/// let x = regd_testing::rand::choose_weighted(&mix);
+/// assert!(*x == "read" || *x == "write");
+/// ```
+///
+/// # Panics
+/// - This function will panic if `items` is empty, if any weight is negative or not a number,
+///   or if all weights are zero.
+pub fn choose_weighted<T>(items: &[(T, f64)]) -> &T {
+    let index = new_index(items.iter().map(|(_, weight)| *weight));
+    let mut rng = super::rng();
+    &items[index.sample(&mut rng)].0
+}
+
+/// A precomputed weighted distribution over a fixed set of values.
+///
+/// This type is typically used to describe a mix of enum variants, e.g., "90% reads, 10% writes",
+/// once and then draw from it repeatedly. It is created through [`Weighted::builder`].
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::Weighted;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Request {
+///     Read,
+///     Write,
+/// }
+///
+/// let mix = Weighted::builder()
+///     .add(Request::Read, 90.0)
+///     .add(Request::Write, 10.0)
+///     .build();
+///
+/// let x = mix.generate();
+/// assert!(x == Request::Read || x == Request::Write);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Weighted<T> {
+    items: Vec<T>,
+    index: WeightedIndex<f64>,
+}
+
+impl<T> Weighted<T> {
+    /// Creates a new, empty [`WeightedBuilder`].
+    pub fn builder() -> WeightedBuilder<T> {
+        WeightedBuilder::default()
+    }
+
+    /// Randomly selects a reference to one of the values of the distribution.
+    pub fn choose(&self) -> &T {
+        let mut rng = super::rng();
+        &self.items[self.index.sample(&mut rng)]
+    }
+
+    /// Randomly selects one of the values of the distribution and returns a clone of it.
+    pub fn generate(&self) -> T
+    where
+        T: Clone,
+    {
+        self.choose().clone()
+    }
+}
+
+impl<T: Clone> Distribution<T> for Weighted<T> {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.items[self.index.sample(rng)].clone()
+    }
+}
+
+/// A builder of [`Weighted`] distributions.
+///
+/// Values and their weights are registered one at a time with [`add`]; the weights are
+/// relative to each other and do not need to sum up to `1.0` or `100.0`.
+///
+/// [`add`]: Self::add
+#[derive(Clone, Debug)]
+pub struct WeightedBuilder<T> {
+    items: Vec<T>,
+    weights: Vec<f64>,
+}
+
+impl<T> Default for WeightedBuilder<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            weights: Vec::new(),
+        }
+    }
+}
+
+impl<T> WeightedBuilder<T> {
+    /// Registers `item` to be chosen with the given relative `weight`.
+    pub fn add(mut self, item: T, weight: f64) -> Self {
+        self.items.push(item);
+        self.weights.push(weight);
+        self
+    }
+
+    /// Builds the [`Weighted`] distribution.
+    ///
+    /// # Panics
+    /// - This method will panic if no item has been registered, if any weight is negative or
+    ///   not a number, or if all weights are zero.
+    pub fn build(self) -> Weighted<T> {
+        Weighted {
+            index: new_index(self.weights),
+            items: self.items,
+        }
+    }
+}
+
+/// Creates a new `WeightedIndex` from the given weights, panicking on invalid weights.
+fn new_index(weights: impl IntoIterator<Item = f64>) -> WeightedIndex<f64> {
+    WeightedIndex::new(weights).unwrap_or_else(|e| panic!("cannot sample invalid weights: {e}"))
+}
+
+/// A precomputed sampler of indices weighted by the alias method.
+///
+/// Unlike [`choose_weighted`], which builds a distribution on every call, and [`Weighted`], whose
+/// draws take a time logarithmic in the number of weights, the sampler is built once in linear
+/// time and then draws each index in constant time. This makes it suited to tests drawing
+/// millions of samples, e.g., simulating a workload over many keys.
+///
+/// # Examples
+/// ```
+/// use regd_testing::rand::WeightedSampler;
+///
+/// let keys = ["hot", "warm", "cold"];
+/// let sampler = WeightedSampler::new([70.0, 25.0, 5.0]);
+/// let counts = (0..10_000).fold([0; 3], |mut counts, _| {
+///     counts[sampler.sample()] += 1;
+///     counts
+/// });
+/// assert!(counts[0] > counts[1] && counts[1] > counts[2]);
+/// println!("{} was drawn {} times", keys[0], counts[0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WeightedSampler {
+    probabilities: Vec<f64>,
+    aliases: Vec<usize>,
+}
+
+impl WeightedSampler {
+    /// Creates a new sampler of the indices of the given relative weights.
+    ///
+    /// # Parameters
+    /// - `weights`: The relative weights of the indices `0..n`.
+    ///
+    /// # Panics
+    /// - This function will panic if `weights` is empty, if any weight is negative or not finite,
+    ///   or if all weights are zero.
+    pub fn new(weights: impl IntoIterator<Item = f64>) -> Self {
+        let weights: Vec<f64> = weights.into_iter().collect();
+        if weights.is_empty() {
+            panic!("cannot sample invalid weights: no weights");
+        }
+        if let Some(weight) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+            panic!("cannot sample invalid weights: invalid weight {weight}");
+        }
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            panic!("cannot sample invalid weights: all weights are zero");
+        }
+        // Vose's alias method: every column of height 1 is filled by a small weight, topped up by
+        // the alias of a large one.
+        let n = weights.len();
+        let mut probabilities: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+        let mut aliases: Vec<usize> = (0..n).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|i| probabilities[*i] < 1.0);
+        while let (Some(s), Some(l)) = (small.pop(), large.pop()) {
+            aliases[s] = l;
+            probabilities[l] -= 1.0 - probabilities[s];
+            if probabilities[l] < 1.0 {
+                small.push(l);
+            } else {
+                large.push(l);
+            }
+        }
+        // The columns left over only differ from 1 by rounding errors.
+        for i in small.into_iter().chain(large) {
+            probabilities[i] = 1.0;
+        }
+        Self {
+            probabilities,
+            aliases,
+        }
+    }
+
+    /// Returns the number of weights of the sampler.
+    pub fn len(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    /// Returns `false`, since a sampler always has at least one weight.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Randomly draws an index with a probability proportional to its weight.
+    pub fn sample(&self) -> usize {
+        let mut rng = super::rng();
+        Distribution::sample(self, &mut rng)
+    }
+}
+
+impl Distribution<usize> for WeightedSampler {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let column = rng.random_range(0..self.probabilities.len());
+        if rng.random::<f64>() < self.probabilities[column] {
+            column
+        } else {
+            self.aliases[column]
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of wordlist-backed random string generators.
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+/// The default word list of short, lowercase, easily readable English words.
+///
+/// The list mixes adjectives and animal names, so that joined words read like `brisk-otter`.
+pub const DEFAULT_WORDLIST: &[&str] = &[
+    "amber", "brisk", "calm", "clever", "dusty", "eager", "fancy", "gentle", "happy", "icy",
+    "jolly", "keen", "lucky", "mellow", "nimble", "odd", "proud", "quiet", "rapid", "shy", "tidy",
+    "vivid", "witty", "zesty", "badger", "crane", "dingo", "eagle", "falcon", "gecko", "heron",
+    "ibis", "jaguar", "koala", "lemur", "marmot", "newt", "otter", "panda", "quail", "raven",
+    "salmon", "tapir", "urchin", "vole", "walrus", "yak", "zebra",
+];
+
+/// Generates a random string by joining words randomly selected from a word list.
+///
+/// Words are selected independently, so that the same word may appear several times.
+///
+/// # Parameters
+/// - `words`: The word list from which to select words, e.g., [`DEFAULT_WORDLIST`].
+/// - `count`: The number of words to join.
+/// - `separator`: The separator inserted between consecutive words.
+///
+/// # Returns
+/// - A `String` of `count` words joined by `separator`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+/// use regd_testing::rand::DEFAULT_WORDLIST;
+///
+/// let x = regd_testing::rand::generate_from_wordlist(DEFAULT_WORDLIST, 3, "-");
+/// assert_eq!(x.split('-').count(), 3);
+/// assert!(x.split('-').all(|w| DEFAULT_WORDLIST.contains(&w)));
+/// ```
+///
+/// # Panics
+/// - This function will panic if `words` is empty and `count > 0`.
+pub fn generate_from_wordlist(words: &[&str], count: usize, separator: &str) -> String {
+    assert!(
+        count == 0 || !words.is_empty(),
+        "cannot sample empty wordlist"
+    );
+    let mut rng = super::rng();
+    let chosen: Vec<&str> = (0..count)
+        .filter_map(|_| words.choose(&mut rng).copied())
+        .collect();
+    chosen.join(separator)
+}
+
+/// Generates a random, human-readable identifier from the default word list.
+///
+/// Readable identifiers are much easier to spot in logs than random alphanumerics when
+/// debugging failed tests.
+///
+/// # Returns
+/// - A `String` of the form `word-word-NNNN`, e.g., `brisk-otter-7421`.
+///
+/// # Examples
+/// ```
+/// use regd_testing;
+///
+/// let x = regd_testing::rand::generate_readable_id();
+/// let parts: Vec<&str> = x.split('-').collect();
+/// assert_eq!(parts.len(), 3);
+/// assert_eq!(parts[2].len(), 4);
+/// ```
+pub fn generate_readable_id() -> String {
+    let number = super::rng().random_range(0..10000);
+    format!(
+        "{}-{number:04}",
+        generate_from_wordlist(DEFAULT_WORDLIST, 2, "-")
+    )
+}
@@ -0,0 +1,239 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of `serde` implementations.
+//!
+//! A value is serialized and deserialized through every format enabled by the features of this
+//! crate: JSON with `json`, CBOR with `cbor`, and bincode with `bincode`.
+
+use std::fmt::{self, Debug, Write};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::diff;
+use crate::rand::CheckOpts;
+use crate::rand::generator::Gen;
+
+/// A serialization format checked by the roundtrip assertions.
+#[derive(Clone, Copy, Debug)]
+enum Format {
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "cbor")]
+    Cbor,
+    #[cfg(feature = "bincode")]
+    Bincode,
+}
+
+/// Every format enabled by the features of this crate.
+const FORMATS: &[Format] = &[
+    #[cfg(feature = "json")]
+    Format::Json,
+    #[cfg(feature = "cbor")]
+    Format::Cbor,
+    #[cfg(feature = "bincode")]
+    Format::Bincode,
+];
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            #[cfg(feature = "json")]
+            Self::Json => write!(f, "JSON"),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => write!(f, "CBOR"),
+            #[cfg(feature = "bincode")]
+            Self::Bincode => write!(f, "bincode"),
+        }
+    }
+}
+
+impl Format {
+    fn serialize<T: Serialize>(self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json => serde_json::to_vec_pretty(value).map_err(|e| e.to_string()),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|e| e.to_string())?;
+                Ok(bytes)
+            }
+            #[cfg(feature = "bincode")]
+            Self::Bincode => bincode::serde::encode_to_vec(value, bincode::config::standard())
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    fn deserialize<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, String> {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => ciborium::from_reader(bytes).map_err(|e| e.to_string()),
+            #[cfg(feature = "bincode")]
+            Self::Bincode => bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+                .map(|(value, _)| value)
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Renders encoded bytes as text for JSON, or as a hex dump for binary formats.
+    fn render(self, bytes: &[u8]) -> String {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json => String::from_utf8_lossy(bytes).into_owned(),
+            #[allow(unreachable_patterns)]
+            _ => {
+                let mut dump = String::new();
+                for (row, chunk) in bytes.chunks(16).enumerate() {
+                    let _ = write!(dump, "{:08x} ", row * 16);
+                    for byte in chunk {
+                        let _ = write!(dump, " {byte:02x}");
+                    }
+                    dump.push('\n');
+                }
+                dump
+            }
+        }
+    }
+}
+
+/// The cause of a failed roundtrip, whose `Debug` renders the multi-line report verbatim.
+struct Failure(String);
+
+impl Debug for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Roundtrips a value through every enabled format, returning the report of the first failure.
+fn roundtrip<T>(value: &T) -> Result<(), Failure>
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    for &format in FORMATS {
+        let encoded = format
+            .serialize(value)
+            .map_err(|e| Failure(format!("cannot serialize {value:?} to {format}: {e}")))?;
+        let decoded: T = format.deserialize(&encoded).map_err(|e| {
+            Failure(format!(
+                "cannot deserialize {value:?} from {format}: {e}\nencoded:\n{}",
+                format.render(&encoded)
+            ))
+        })?;
+        if decoded == *value {
+            continue;
+        }
+        let mut report = format!("{format} roundtrip changed the value\n");
+        report.push_str(&diff::unified(
+            "original",
+            &format!("{value:#?}"),
+            "roundtripped",
+            &format!("{decoded:#?}"),
+        ));
+        match format.serialize(&decoded) {
+            Ok(reencoded) => report.push_str(&diff::unified(
+                &format!("original {format}"),
+                &format.render(&encoded),
+                &format!("roundtripped {format}"),
+                &format.render(&reencoded),
+            )),
+            Err(e) => {
+                let _ = write!(
+                    report,
+                    "cannot serialize roundtripped value to {format}: {e}"
+                );
+            }
+        }
+        return Err(Failure(report.trim_end().to_string()));
+    }
+    Ok(())
+}
+
+/// Asserts that a value is unchanged by serializing and deserializing it.
+///
+/// The value is roundtripped through every format enabled by the features of this crate.
+///
+/// # Parameters
+/// - `value`: The value to roundtrip.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "json")]
+/// # {
+/// use std::collections::BTreeMap;
+///
+/// use regd_testing::{assert, serde};
+///
+/// let value: BTreeMap<String, Vec<Option<u32>>> =
+///     BTreeMap::from([("a".to_string(), vec![Some(1), None])]);
+/// serde::assert_roundtrip(&value);
+///
+/// // NaN is serialized to JSON as `null`, which cannot be deserialized into a float.
+/// assert::panics_with("cannot deserialize NaN from JSON", || {
+///     serde::assert_roundtrip(&f64::NAN)
+/// });
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic if the value cannot be serialized or deserialized, or with a
+///   diff of the values and of their encodings if the deserialized value differs.
+#[track_caller]
+pub fn assert_roundtrip<T>(value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    if let Err(Failure(report)) = roundtrip(value) {
+        panic!("{report}");
+    }
+}
+
+/// Asserts that random values are unchanged by serializing and deserializing them.
+///
+/// The values are checked through [`check_with`](crate::rand::check_with), so that a failing
+/// value is shrunk to a minimal counterexample before being reported.
+///
+/// # Parameters
+/// - `generator`: The generator of the values.
+/// - `cases`: The number of values to check.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "json")]
+/// # {
+/// use regd_testing::rand::generator::{self as g, Gen};
+/// use regd_testing::serde;
+///
+/// serde::assert_roundtrip_many(g::any::<(u8, bool)>(), 100);
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic with the minimal counterexample and its report if a value fails
+///   to roundtrip.
+#[track_caller]
+pub fn assert_roundtrip_many<T>(generator: impl Gen<T>, cases: usize)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug + Clone,
+{
+    let opts = CheckOpts {
+        cases,
+        ..Default::default()
+    };
+    crate::rand::check_with(&opts, generator, |value: T| roundtrip(&value));
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of golden-file (snapshot) testing.
+//!
+//! Snapshots are stored in the `tests/snapshots` directory of the package under test, i.e.,
+//! `$CARGO_MANIFEST_DIR/tests/snapshots/<name>.snap`. When a value does not match its snapshot,
+//! the assertion panics with a unified diff. Running the tests with the `REGD_TESTING_UPDATE=1`
+//! environment variable writes the current values as the new snapshots instead.
+
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use crate::diff;
+
+/// The environment variable which enables regenerating snapshots.
+pub const UPDATE_ENV: &str = "REGD_TESTING_UPDATE";
+
+/// The directory of snapshots relative to the manifest directory.
+const SNAPSHOTS_DIR: &str = "tests/snapshots";
+
+/// Returns the snapshots directory of the package under test.
+///
+/// # Panics
+/// - This function will panic if `CARGO_MANIFEST_DIR` is not set, i.e., when not run by Cargo.
+pub fn dir() -> PathBuf {
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")
+        .unwrap_or_else(|| panic!("cannot locate snapshots: CARGO_MANIFEST_DIR is not set"));
+    Path::new(&manifest_dir).join(SNAPSHOTS_DIR)
+}
+
+/// Returns `true` if snapshots should be regenerated rather than compared.
+fn updating() -> bool {
+    env::var(UPDATE_ENV).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+}
+
+/// Asserts that the pretty-printed `Debug` representation of a value matches its snapshot.
+///
+/// # Parameters
+/// - `name`: The name of the snapshot, which may contain `/` to organize snapshots in
+///   directories.
+/// - `value`: The value to compare against the snapshot.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing::snapshot;
+///
+/// #[derive(Debug)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let user = User {
+///     name: "Alice".to_string(),
+///     age: 42,
+/// };
+/// snapshot::assert_matches("users/alice", &user);
+/// ```
+///
+/// # Panics
+/// - This function will panic with a unified diff if the value does not match its snapshot, or if
+///   the snapshot does not exist, unless `REGD_TESTING_UPDATE=1` is set.
+pub fn assert_matches<T>(name: &str, value: &T)
+where
+    T: Debug + ?Sized,
+{
+    assert_text_matches(name, &format!("{value:#?}"));
+}
+
+/// Asserts that a text matches its snapshot verbatim.
+///
+/// # Parameters
+/// - `name`: The name of the snapshot, which may contain `/` to organize snapshots in
+///   directories.
+/// - `text`: The text to compare against the snapshot.
+///
+/// # Examples
+/// ```no_run
+/// use regd_testing::snapshot;
+///
+/// let rendered = format!("<h1>{}</h1>", "Hello");
+/// snapshot::assert_text_matches("pages/hello", &rendered);
+/// ```
+///
+/// # Panics
+/// - This function will panic with a unified diff if the text does not match its snapshot, or if
+///   the snapshot does not exist, unless `REGD_TESTING_UPDATE=1` is set.
+pub fn assert_text_matches(name: &str, text: &str) {
+    let path = dir().join(format!("{name}.snap"));
+    let mut actual = text.to_string();
+    if !actual.ends_with('\n') {
+        actual.push('\n');
+    }
+
+    if updating() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .unwrap_or_else(|e| panic!("cannot create {}: {e}", parent.display()));
+        }
+        fs::write(&path, &actual).unwrap_or_else(|e| panic!("cannot write snapshot `{name}`: {e}"));
+        return;
+    }
+
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!(
+            "cannot find snapshot `{name}` at {}; rerun with {UPDATE_ENV}=1 to create it\n{actual}",
+            path.display()
+        );
+    };
+    let diff = diff::unified("snapshot", &expected, "actual", &actual);
+    if !diff.is_empty() {
+        panic!("snapshot `{name}` does not match; rerun with {UPDATE_ENV}=1 to update it\n{diff}");
+    }
+}
@@ -0,0 +1,430 @@
+// Copyright 2025 Shingo OKAWA. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains a set of testing utilities of time.
+//!
+//! Code under test which takes an `impl Clock` rather than calling [`SystemTime::now`] and
+//! [`std::thread::sleep`] directly becomes fully deterministic with respect to time when given a
+//! [`MockClock`].
+
+use std::cell::RefCell;
+use std::panic;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, mpsc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// A source of the current time, which can also wait for time to pass.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+
+    /// Blocks the current thread until the given duration has passed on this clock.
+    fn sleep(&self, duration: Duration);
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration);
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration);
+    }
+}
+
+/// The real clock, backed by [`SystemTime::now`] and [`std::thread::sleep`].
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::time::{Clock, SystemClock};
+///
+/// let before = SystemClock.now();
+/// SystemClock.sleep(Duration::from_millis(1));
+/// assert!(SystemClock.now() > before);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// The state shared between the clones of a [`MockClock`].
+#[derive(Debug)]
+struct State {
+    now: SystemTime,
+    sleepers: usize,
+}
+
+/// A clock whose time only changes when a test sets or advances it.
+///
+/// Clones share the same time, so that a test can keep one clone while handing another to the
+/// code under test. [`Clock::sleep`] blocks until the time is advanced past its deadline by
+/// another thread.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use regd_testing::time::{Clock, MockClock};
+///
+/// let clock = MockClock::new(UNIX_EPOCH);
+/// let sleeper = {
+///     let clock = clock.clone();
+///     std::thread::spawn(move || clock.sleep(Duration::from_secs(60)))
+/// };
+///
+/// while clock.sleepers() == 0 {
+///     std::thread::yield_now();
+/// }
+/// clock.advance(Duration::from_secs(60));
+/// sleeper.join().unwrap();
+/// assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(60));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MockClock {
+    shared: Arc<(Mutex<State>, Condvar)>,
+}
+
+impl MockClock {
+    /// Creates a clock set to the given time.
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            shared: Arc::new((Mutex::new(State { now, sleepers: 0 }), Condvar::new())),
+        }
+    }
+
+    /// Locks the state, ignoring the poisoning caused by a panicking thread.
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.shared.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sets the time of the clock, waking the sleeps whose deadline has passed.
+    ///
+    /// The time may also be set backwards, e.g., to simulate a clock adjustment.
+    pub fn set(&self, now: SystemTime) {
+        self.lock().now = now;
+        self.shared.1.notify_all();
+    }
+
+    /// Advances the time of the clock, waking the sleeps whose deadline has passed.
+    ///
+    /// # Panics
+    /// - This method will panic if the time overflows.
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.lock();
+        state.now = state
+            .now
+            .checked_add(duration)
+            .unwrap_or_else(|| panic!("cannot advance clock by {duration:?}"));
+        drop(state);
+        self.shared.1.notify_all();
+    }
+
+    /// Advances the time of the clock together with the paused clock of the current Tokio
+    /// runtime, so that both `tokio::time` timers and code reading this clock observe the same
+    /// elapsed time.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "async")]
+    /// # {
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// use regd_testing::time::{Clock, MockClock};
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread()
+    ///     .enable_time()
+    ///     .start_paused(true)
+    ///     .build()
+    ///     .unwrap();
+    /// runtime.block_on(async {
+    ///     let clock = MockClock::new(UNIX_EPOCH);
+    ///     let start = tokio::time::Instant::now();
+    ///     clock.advance_async(Duration::from_secs(3600)).await;
+    ///     assert_eq!(start.elapsed(), Duration::from_secs(3600));
+    ///     assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(3600));
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// - This method will panic if the time overflows, or if it is not called from a
+    ///   current-thread Tokio runtime whose clock is paused, e.g., in
+    ///   `#[tokio::test(start_paused = true)]`.
+    #[cfg(feature = "async")]
+    pub async fn advance_async(&self, duration: Duration) {
+        self.advance(duration);
+        tokio::time::advance(duration).await;
+    }
+
+    /// Returns the number of threads currently blocked in [`Clock::sleep`].
+    ///
+    /// This is useful for waiting until the code under test sleeps before advancing the time.
+    pub fn sleepers(&self) -> usize {
+        self.lock().sleepers
+    }
+}
+
+impl Default for MockClock {
+    /// Creates a clock set to the Unix epoch.
+    fn default() -> Self {
+        Self::new(SystemTime::UNIX_EPOCH)
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        self.lock().now
+    }
+
+    fn sleep(&self, duration: Duration) {
+        let mut state = self.lock();
+        let Some(deadline) = state.now.checked_add(duration) else {
+            panic!("cannot sleep for {duration:?}");
+        };
+        state.sleepers += 1;
+        while state.now < deadline {
+            state = self
+                .shared
+                .1
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        state.sleepers -= 1;
+    }
+}
+
+thread_local! {
+    /// The clock installed by [`frozen_at`] on the current thread, if any.
+    static FROZEN: RefCell<Option<MockClock>> = const { RefCell::new(None) };
+}
+
+/// Restores the previously frozen clock of the current thread on drop.
+struct Unfreeze(Option<MockClock>);
+
+impl Drop for Unfreeze {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        FROZEN.with(|frozen| *frozen.borrow_mut() = previous);
+    }
+}
+
+/// Runs a closure with the time of the current thread frozen at the given timestamp.
+///
+/// Within the closure, [`now`] returns the frozen time, and both [`sleep`] and [`advance`] move it
+/// forward without blocking, so that TTL and expiry logic can be tested without real sleeps.
+/// Calls may be nested, in which case the innermost timestamp applies.
+///
+/// # Parameters
+/// - `timestamp`: The time to freeze at.
+/// - `f`: The closure to run.
+///
+/// # Returns
+/// - The value returned by `f`. The previous time is restored even if `f` panics.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use regd_testing::time;
+///
+/// let timestamp = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+/// time::frozen_at(timestamp, || {
+///     assert_eq!(time::now(), timestamp);
+///     time::advance(Duration::from_secs(30));
+///     assert_eq!(time::now(), timestamp + Duration::from_secs(30));
+///     time::sleep(Duration::from_secs(3600));
+///     assert_eq!(time::now(), timestamp + Duration::from_secs(3630));
+/// });
+/// ```
+///
+/// # Notes
+/// - The frozen time is local to the current thread; threads spawned within the closure observe
+///   the real time unless they are given [`frozen_clock`] explicitly.
+pub fn frozen_at<T>(timestamp: SystemTime, f: impl FnOnce() -> T) -> T {
+    let clock = MockClock::new(timestamp);
+    let previous = FROZEN.with(|frozen| frozen.borrow_mut().replace(clock));
+    let _unfreeze = Unfreeze(previous);
+    f()
+}
+
+/// Returns the clock frozen by [`frozen_at`] on the current thread, if any.
+pub fn frozen_clock() -> Option<MockClock> {
+    FROZEN.with(|frozen| frozen.borrow().clone())
+}
+
+/// Returns the current time, which is the frozen time within [`frozen_at`].
+pub fn now() -> SystemTime {
+    frozen_clock().map_or_else(SystemTime::now, |clock| clock.now())
+}
+
+/// Blocks the current thread for the given duration.
+///
+/// Within [`frozen_at`], this function advances the frozen time by `duration` and returns at
+/// once instead, since the frozen time is local to the current thread, which is then the only
+/// one able to advance it.
+///
+/// # Panics
+/// - This function will panic if the frozen time overflows.
+pub fn sleep(duration: Duration) {
+    match frozen_clock() {
+        Some(clock) => clock.advance(duration),
+        None => thread::sleep(duration),
+    }
+}
+
+/// Advances the frozen time of the current thread.
+///
+/// # Panics
+/// - This function will panic if it is not called within [`frozen_at`], or if the time
+///   overflows.
+pub fn advance(duration: Duration) {
+    frozen_clock()
+        .unwrap_or_else(|| panic!("cannot advance time: not within `frozen_at`"))
+        .advance(duration);
+}
+
+/// Asserts that a closure completes within the given duration.
+///
+/// The closure runs on a separate thread watched by the calling thread, so that an accidental
+/// blocking call fails the test instead of hanging it. A panic of the closure is propagated to
+/// the calling thread.
+///
+/// # Parameters
+/// - `duration`: The time budget of the closure.
+/// - `f`: The closure to run.
+///
+/// # Returns
+/// - The value returned by `f`.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use regd_testing::time;
+///
+/// let x = time::assert_completes_within(Duration::from_secs(5), || 1 + 1);
+/// assert_eq!(x, 2);
+/// ```
+///
+/// ```should_panic
+/// use std::time::Duration;
+///
+/// use regd_testing::time;
+///
+/// time::assert_completes_within(Duration::from_millis(10), || {
+///     std::thread::sleep(Duration::from_secs(1));
+/// });
+/// ```
+///
+/// # Panics
+/// - This function will panic with the elapsed time if `f` does not complete within `duration`,
+///   or if `f` panics.
+///
+/// # Notes
+/// - The thread running `f` cannot be cancelled, so that it keeps running in the background
+///   after a timeout.
+#[track_caller]
+pub fn assert_completes_within<T, F>(duration: Duration, f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let start = Instant::now();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(panic::catch_unwind(panic::AssertUnwindSafe(f)));
+    });
+    match receiver.recv_timeout(duration) {
+        Ok(Ok(value)) => value,
+        Ok(Err(payload)) => panic::resume_unwind(payload),
+        Err(_) => panic!(
+            "closure did not complete within {duration:?}; elapsed {:?}",
+            start.elapsed()
+        ),
+    }
+}
+
+/// Asserts that a future completes within the given duration.
+///
+/// This is the async variant of [`assert_completes_within`], which must be awaited within a
+/// Tokio runtime with the time driver enabled.
+///
+/// # Parameters
+/// - `duration`: The time budget of the future.
+/// - `future`: The future to await.
+///
+/// # Returns
+/// - The output of `future`.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "async")]
+/// # {
+/// use std::time::Duration;
+///
+/// use regd_testing::time;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread()
+///     .enable_time()
+///     .build()
+///     .unwrap();
+/// let x = runtime.block_on(time::assert_completes_within_async(
+///     Duration::from_secs(5),
+///     async { 1 + 1 },
+/// ));
+/// assert_eq!(x, 2);
+/// # }
+/// ```
+///
+/// # Panics
+/// - This function will panic with the elapsed time if `future` does not complete within
+///   `duration`.
+///
+/// # Notes
+/// - A future blocking the thread without yielding cannot be interrupted; use
+///   [`assert_completes_within`] to guard against blocking calls.
+#[cfg(feature = "async")]
+pub async fn assert_completes_within_async<F>(duration: Duration, future: F) -> F::Output
+where
+    F: Future,
+{
+    let start = Instant::now();
+    match tokio::time::timeout(duration, future).await {
+        Ok(output) => output,
+        Err(_) => panic!(
+            "future did not complete within {duration:?}; elapsed {:?}",
+            start.elapsed()
+        ),
+    }
+}